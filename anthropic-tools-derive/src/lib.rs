@@ -0,0 +1,319 @@
+//! Procedural macros for `anthropic-tools`.
+//!
+//! Enable the `derive` feature of `anthropic-tools` and use the re-exports
+//! from `anthropic_tools::common::tool` and `anthropic_tools::tools::handler`
+//! instead of depending on this crate directly.
+//!
+//! - `#[derive(ToolInput)]` on a struct with named fields generates a tool
+//!   input schema: fields become properties, doc comments become
+//!   descriptions, and `Option<T>` or `#[serde(default)]` fields are not
+//!   required
+//! - `#[derive(ToolInput)]` on an enum of unit variants generates a string
+//!   property with the variant names as `enum` values
+//!
+//! - `#[tool]` on a function generates a `ToolHandler` whose schema comes
+//!   from the parameters and whose `execute` calls the function
+//!
+//! `#[serde(rename = "...")]`, `#[serde(rename_all = "...")]`, and
+//! `#[serde(skip)]` are honored, so the schema matches what serde accepts.
+
+mod tool_fn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Expr, Fields, Lit, Meta, parse_macro_input};
+
+/// Derive `ToolInput` (structs) or `ToolInputType` (unit enums)
+#[proc_macro_derive(ToolInput, attributes(serde))]
+pub fn derive_tool_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let expanded = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => expand_struct(&input, fields),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "ToolInput can only be derived for structs with named fields",
+            )),
+        },
+        Data::Enum(data) => expand_enum(&input, data),
+        Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "ToolInput cannot be derived for unions",
+        )),
+    };
+    expanded
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turn a function into a tool handler
+///
+/// See `anthropic_tools::tools::handler::tool` for the documentation.
+#[proc_macro_attribute]
+pub fn tool(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(item as syn::ItemFn);
+    tool_fn::expand(attr.into(), function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_struct(input: &DeriveInput, fields: &syn::FieldsNamed) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let serde = SerdeAttrs::parse(&input.attrs)?;
+    let description = option_tokens(doc_comment(&input.attrs));
+
+    let mut properties = Vec::new();
+    for field in &fields.named {
+        let field_serde = SerdeAttrs::parse(&field.attrs)?;
+        if field_serde.skip {
+            continue;
+        }
+        let name = match &field_serde.rename {
+            Some(name) => name.clone(),
+            None => {
+                let ident = field.ident.as_ref().expect("named field").to_string();
+                let ident = ident.trim_start_matches("r#").to_string();
+                match &serde.rename_all {
+                    Some(rule) => rename(&ident, rule)?,
+                    None => ident,
+                }
+            }
+        };
+        let ty = &field.ty;
+        let field_description = option_tokens(doc_comment(&field.attrs));
+        let has_default = field_serde.default;
+        properties.push(quote! {
+            properties.insert(
+                #name.to_string(),
+                <#ty as ::anthropic_tools::common::tool::ToolInputType>::property_def(#field_description),
+            );
+            if <#ty as ::anthropic_tools::common::tool::ToolInputType>::REQUIRED && !#has_default {
+                required.push(#name.to_string());
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::anthropic_tools::common::tool::ToolInput for #ident #ty_generics #where_clause {
+            fn input_schema() -> ::anthropic_tools::common::tool::JsonSchema {
+                let mut properties = ::std::collections::HashMap::new();
+                let mut required: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(#properties)*
+                let mut schema = ::anthropic_tools::common::tool::JsonSchema::object();
+                schema.properties = ::std::option::Option::Some(properties);
+                schema.required = (!required.is_empty()).then_some(required);
+                schema
+            }
+
+            fn description() -> ::std::option::Option<&'static str> {
+                #description
+            }
+        }
+
+        impl #impl_generics ::anthropic_tools::common::tool::ToolInputType for #ident #ty_generics #where_clause {
+            fn property_def(
+                description: ::std::option::Option<::std::string::String>,
+            ) -> ::anthropic_tools::common::tool::PropertyDef {
+                ::anthropic_tools::common::tool::PropertyDef::from_input::<Self>(description)
+            }
+        }
+    })
+}
+
+fn expand_enum(input: &DeriveInput, data: &syn::DataEnum) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let serde = SerdeAttrs::parse(&input.attrs)?;
+    let description = option_tokens(doc_comment(&input.attrs));
+
+    let mut values = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "ToolInput can only be derived for enums with unit variants",
+            ));
+        }
+        let variant_serde = SerdeAttrs::parse(&variant.attrs)?;
+        if variant_serde.skip {
+            continue;
+        }
+        let name = match variant_serde.rename {
+            Some(name) => name,
+            None => {
+                let ident = variant.ident.to_string();
+                match &serde.rename_all {
+                    Some(rule) => rename(&ident, rule)?,
+                    None => ident,
+                }
+            }
+        };
+        values.push(name);
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::anthropic_tools::common::tool::ToolInputType for #ident #ty_generics #where_clause {
+            fn property_def(
+                description: ::std::option::Option<::std::string::String>,
+            ) -> ::anthropic_tools::common::tool::PropertyDef {
+                let default_description: ::std::option::Option<&'static str> = #description;
+                ::anthropic_tools::common::tool::PropertyDef::enum_type(
+                    description.or_else(|| default_description.map(::std::string::ToString::to_string)),
+                    ::std::vec![#(#values.to_string()),*],
+                )
+            }
+        }
+    })
+}
+
+/// `Some("...")` or `None` as tokens
+pub(crate) fn option_tokens(value: Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { ::std::option::Option::Some(#value.into()) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+/// Doc comment lines joined with spaces
+pub(crate) fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(lit) => Some(lit.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// The `#[serde(...)]` options that affect the schema
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    default: bool,
+    skip: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = SerdeAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if let Ok(value) = meta.value() {
+                        parsed.rename = Some(value.parse::<syn::LitStr>()?.value());
+                    } else {
+                        // rename(serialize = "...", deserialize = "..."): use deserialize
+                        meta.parse_nested_meta(|inner| {
+                            let value = inner.value()?.parse::<syn::LitStr>()?.value();
+                            if inner.path.is_ident("deserialize") {
+                                parsed.rename = Some(value);
+                            }
+                            Ok(())
+                        })?;
+                    }
+                } else if meta.path.is_ident("rename_all") {
+                    if let Ok(value) = meta.value() {
+                        parsed.rename_all = Some(value.parse::<syn::LitStr>()?.value());
+                    } else {
+                        meta.parse_nested_meta(|inner| {
+                            let value = inner.value()?.parse::<syn::LitStr>()?.value();
+                            if inner.path.is_ident("deserialize") {
+                                parsed.rename_all = Some(value);
+                            }
+                            Ok(())
+                        })?;
+                    }
+                } else if meta.path.is_ident("default") {
+                    parsed.default = true;
+                    if let Ok(value) = meta.value() {
+                        value.parse::<syn::LitStr>()?;
+                    }
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    parsed.skip = true;
+                } else if let Ok(value) = meta.value() {
+                    // Other options (with, alias, ...) don't change the schema
+                    value.parse::<Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|inner| {
+                        if let Ok(value) = inner.value() {
+                            value.parse::<Expr>()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Apply a serde `rename_all` rule to a field or variant name
+fn rename(name: &str, rule: &str) -> syn::Result<String> {
+    let words = split_words(name);
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            None => String::new(),
+        }
+    };
+    Ok(match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "PascalCase" => words.iter().map(capitalize).collect(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                if i == 0 {
+                    word.clone()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        _ => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!("unknown rename_all rule: {}", rule),
+            ));
+        }
+    })
+}
+
+/// Split a snake_case or PascalCase identifier into lowercase words
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let mut word = String::new();
+        let mut prev_lower = false;
+        for c in part.chars() {
+            if c.is_uppercase() && prev_lower && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+            word.extend(c.to_lowercase());
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words
+}
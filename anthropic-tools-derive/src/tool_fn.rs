@@ -0,0 +1,245 @@
+//! `#[tool]`: a tool handler from a function.
+
+use crate::{doc_comment, option_tokens};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{FnArg, GenericArgument, ItemFn, LitStr, Pat, PathArguments, ReturnType, Type};
+
+/// Options of `#[tool(...)]`
+#[derive(Default)]
+struct ToolAttrs {
+    name: Option<String>,
+    description: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl ToolAttrs {
+    fn parse(attr: TokenStream) -> syn::Result<Self> {
+        let mut parsed = ToolAttrs::default();
+        let parser = syn::meta::parser(|meta| {
+            if meta.path.is_ident("name") {
+                parsed.name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("description") {
+                parsed.description = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("params") {
+                meta.parse_nested_meta(|param| {
+                    let name = param.path.require_ident()?.to_string();
+                    let description = param.value()?.parse::<LitStr>()?.value();
+                    parsed.params.push((name, description));
+                    Ok(())
+                })?;
+            } else {
+                return Err(meta.error("expected `name`, `description`, or `params`"));
+            }
+            Ok(())
+        });
+        syn::parse::Parser::parse2(parser, attr)?;
+        Ok(parsed)
+    }
+}
+
+pub(crate) fn expand(attr: TokenStream, function: ItemFn) -> syn::Result<TokenStream> {
+    let attrs = ToolAttrs::parse(attr)?;
+    let sig = &function.sig;
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "tool functions cannot be generic",
+        ));
+    }
+
+    let fn_ident = &sig.ident;
+    let fn_name = fn_ident.to_string().trim_start_matches("r#").to_string();
+    let tool_name = attrs.name.clone().unwrap_or_else(|| fn_name.clone());
+    let description = option_tokens(
+        attrs
+            .description
+            .clone()
+            .or_else(|| doc_comment(&function.attrs)),
+    );
+    let handler = format_ident!("{}Tool", pascal_case(&fn_name), span = fn_ident.span());
+    let vis = &function.vis;
+
+    let mut properties = Vec::new();
+    let mut bindings = Vec::new();
+    let mut args = Vec::new();
+    for input in &sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "tool functions cannot take `self`",
+            ));
+        };
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "tool parameters must be plain identifiers",
+            ));
+        };
+        let ident = &pat.ident;
+        let name = ident.to_string().trim_start_matches("r#").to_string();
+        let ty = &arg.ty;
+        let param_description = option_tokens(
+            attrs
+                .params
+                .iter()
+                .find(|(param, _)| *param == name)
+                .map(|(_, description)| description.clone()),
+        );
+        properties.push(quote! {
+            properties.insert(
+                #name.to_string(),
+                <#ty as ::anthropic_tools::common::tool::ToolInputType>::property_def(#param_description),
+            );
+            if <#ty as ::anthropic_tools::common::tool::ToolInputType>::REQUIRED {
+                required.push(#name.to_string());
+            }
+        });
+        bindings.push(quote! {
+            let #ident: #ty = match ::anthropic_tools::__private::serde_json::from_value(
+                input
+                    .get(#name)
+                    .cloned()
+                    .unwrap_or(::anthropic_tools::__private::serde_json::Value::Null),
+            ) {
+                ::std::result::Result::Ok(value) => value,
+                ::std::result::Result::Err(err) => {
+                    return ::std::result::Result::Ok(
+                        ::anthropic_tools::tools::handler::ToolOutput::error(
+                            ::std::format!("invalid `{}`: {}", #name, err),
+                        ),
+                    );
+                }
+            };
+        });
+        args.push(ident);
+    }
+    if let Some((param, _)) = attrs
+        .params
+        .iter()
+        .find(|(param, _)| !args.iter().any(|arg| *arg == param))
+    {
+        return Err(syn::Error::new_spanned(
+            fn_ident,
+            format!("`params` describes unknown parameter `{}`", param),
+        ));
+    }
+
+    let call = if sig.asyncness.is_some() {
+        quote! { #fn_ident(#(#args),*).await }
+    } else {
+        quote! { #fn_ident(#(#args),*) }
+    };
+    let output = match result_ok_type(&sig.output) {
+        Some(ok) => {
+            let convert = convert_output(ok);
+            quote! {
+                match #call {
+                    ::std::result::Result::Ok(value) => #convert,
+                    ::std::result::Result::Err(err) => ::std::result::Result::Ok(
+                        ::anthropic_tools::tools::handler::ToolOutput::error(
+                            ::std::string::ToString::to_string(&err),
+                        ),
+                    ),
+                }
+            }
+        }
+        None => {
+            let convert = match &sig.output {
+                ReturnType::Type(_, ty) => convert_output(ty),
+                ReturnType::Default => convert_output(&syn::parse_quote!(())),
+            };
+            quote! {{
+                let value = #call;
+                #convert
+            }}
+        }
+    };
+
+    let handler_doc = format!("Tool handler for [`{}`]", fn_name);
+    Ok(quote! {
+        #function
+
+        #[doc = #handler_doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct #handler;
+
+        impl ::anthropic_tools::tools::handler::ToolHandler for #handler {
+            fn name(&self) -> &str {
+                #tool_name
+            }
+
+            fn definition(&self) -> ::anthropic_tools::common::tool::Tool {
+                let mut properties = ::std::collections::HashMap::new();
+                let mut required: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(#properties)*
+                let mut tool = ::anthropic_tools::common::tool::Tool::new(#tool_name);
+                tool.description = #description;
+                tool.input_schema.properties = ::std::option::Option::Some(properties);
+                tool.input_schema.required = (!required.is_empty()).then_some(required);
+                tool
+            }
+
+            fn execute(
+                &self,
+                input: ::anthropic_tools::__private::serde_json::Value,
+            ) -> ::anthropic_tools::common::future::BoxFuture<
+                '_,
+                ::anthropic_tools::common::errors::Result<::anthropic_tools::tools::handler::ToolOutput>,
+            > {
+                ::anthropic_tools::common::future::boxed(async move {
+                    #(#bindings)*
+                    #output
+                })
+            }
+        }
+    })
+}
+
+/// `T` of a `Result<T>` or `Result<T, E>` return type
+fn result_ok_type(output: &ReturnType) -> Option<&Type> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let Type::Path(path) = &**ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(generics) = &segment.arguments else {
+        return None;
+    };
+    match generics.args.first()? {
+        GenericArgument::Type(ok) => Some(ok),
+        _ => None,
+    }
+}
+
+/// Expression turning `value` into `Result<ToolOutput>`
+fn convert_output(ty: &Type) -> TokenStream {
+    let is_tool_output = matches!(
+        ty,
+        Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "ToolOutput")
+    );
+    if is_tool_output {
+        quote! { ::std::result::Result::Ok(value) }
+    } else {
+        quote! { ::anthropic_tools::tools::handler::ToolOutput::json(&value) }
+    }
+}
+
+/// `get_weather` -> `GetWeather`
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,445 @@
+//! Admin API: organization usage and cost reports.
+//!
+//! The Admin API needs an admin key (`sk-ant-admin...`), separate from the
+//! API keys used for requests. [`Admin::new`] reads it from
+//! `ANTHROPIC_ADMIN_KEY`.
+//!
+//! - [`Admin::usage_report`] - Token usage per time bucket (`/v1/organizations/usage_report/messages`)
+//! - [`Admin::cost_report`] - Spend per time bucket (`/v1/organizations/cost_report`)
+//!
+//! Both reports can be grouped, e.g. by workspace, API key, or model, and are
+//! paginated with the `page` cursor returned as [`Report::next_page`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::admin::{Admin, BucketWidth, ReportParams};
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let admin = Admin::new();
+//! let mut params = ReportParams::new("2025-06-01T00:00:00Z");
+//! params
+//!     .ending_at("2025-06-08T00:00:00Z")
+//!     .bucket_width(BucketWidth::Day)
+//!     .group_by("workspace_id");
+//!
+//! let costs = admin.cost_report(&params).await?;
+//! for bucket in &costs.data {
+//!     for cost in &bucket.results {
+//!         println!("{} {:?}: {} {}", bucket.starting_at, cost.workspace_id, cost.amount, cost.currency);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::Result;
+use crate::common::pagination::encode;
+use crate::messages::request::Messages;
+use serde::{Deserialize, Serialize};
+use std::env;
+use strum::{Display, EnumString};
+
+/// Path of the usage report endpoint below the base URL
+pub(crate) const USAGE_REPORT_PATH: &str = "/v1/organizations/usage_report/messages";
+
+/// Path of the cost report endpoint below the base URL
+pub(crate) const COST_REPORT_PATH: &str = "/v1/organizations/cost_report";
+
+/// Width of the time buckets in a report
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum BucketWidth {
+    /// One minute
+    #[serde(rename = "1m")]
+    #[strum(serialize = "1m")]
+    Minute,
+
+    /// One hour
+    #[serde(rename = "1h")]
+    #[strum(serialize = "1h")]
+    Hour,
+
+    /// One day
+    #[serde(rename = "1d")]
+    #[strum(serialize = "1d")]
+    Day,
+}
+
+/// Time range, grouping, and filters of a report request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportParams {
+    /// Start of the first bucket (RFC 3339, inclusive)
+    pub starting_at: String,
+
+    /// End of the last bucket (RFC 3339, exclusive)
+    pub ending_at: Option<String>,
+
+    /// Bucket width (the cost report only supports days)
+    pub bucket_width: Option<BucketWidth>,
+
+    /// Fields to group results by (e.g. `workspace_id`, `api_key_id`, `model`)
+    pub group_by: Vec<String>,
+
+    /// Only include these models (usage report)
+    pub models: Vec<String>,
+
+    /// Only include these API keys (usage report)
+    pub api_key_ids: Vec<String>,
+
+    /// Only include these workspaces (usage report)
+    pub workspace_ids: Vec<String>,
+
+    /// Maximum number of buckets per page
+    pub limit: Option<u32>,
+
+    /// Page cursor from [`Report::next_page`]
+    pub page: Option<String>,
+}
+
+impl ReportParams {
+    /// Create parameters starting at the given time
+    pub fn new<T: AsRef<str>>(starting_at: T) -> Self {
+        ReportParams {
+            starting_at: starting_at.as_ref().to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the end of the report
+    pub fn ending_at<T: AsRef<str>>(&mut self, ending_at: T) -> &mut Self {
+        self.ending_at = Some(ending_at.as_ref().to_string());
+        self
+    }
+
+    /// Set the bucket width
+    pub fn bucket_width(&mut self, width: BucketWidth) -> &mut Self {
+        self.bucket_width = Some(width);
+        self
+    }
+
+    /// Group results by a field
+    pub fn group_by<T: AsRef<str>>(&mut self, field: T) -> &mut Self {
+        self.group_by.push(field.as_ref().to_string());
+        self
+    }
+
+    /// Only include a model
+    pub fn model<T: AsRef<str>>(&mut self, model: T) -> &mut Self {
+        self.models.push(model.as_ref().to_string());
+        self
+    }
+
+    /// Only include an API key
+    pub fn api_key_id<T: AsRef<str>>(&mut self, id: T) -> &mut Self {
+        self.api_key_ids.push(id.as_ref().to_string());
+        self
+    }
+
+    /// Only include a workspace
+    pub fn workspace_id<T: AsRef<str>>(&mut self, id: T) -> &mut Self {
+        self.workspace_ids.push(id.as_ref().to_string());
+        self
+    }
+
+    /// Set the maximum number of buckets per page
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Continue from a page cursor
+    pub fn page<T: AsRef<str>>(&mut self, page: T) -> &mut Self {
+        self.page = Some(page.as_ref().to_string());
+        self
+    }
+
+    /// Render as a URL query string
+    pub fn query(&self) -> String {
+        let mut pairs = vec![format!("starting_at={}", encode(&self.starting_at))];
+        if let Some(ending_at) = &self.ending_at {
+            pairs.push(format!("ending_at={}", encode(ending_at)));
+        }
+        if let Some(width) = self.bucket_width {
+            pairs.push(format!("bucket_width={}", width));
+        }
+        for (name, values) in [
+            ("group_by", &self.group_by),
+            ("models", &self.models),
+            ("api_key_ids", &self.api_key_ids),
+            ("workspace_ids", &self.workspace_ids),
+        ] {
+            for value in values {
+                pairs.push(format!("{}[]={}", name, encode(value)));
+            }
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(format!("limit={}", limit));
+        }
+        if let Some(page) = &self.page {
+            pairs.push(format!("page={}", encode(page)));
+        }
+        format!("?{}", pairs.join("&"))
+    }
+}
+
+/// Page of a time-bucketed report
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Report<T> {
+    /// Time buckets on this page
+    pub data: Vec<TimeBucket<T>>,
+
+    /// Whether more buckets follow
+    #[serde(default)]
+    pub has_more: bool,
+
+    /// Cursor for the next page
+    #[serde(default)]
+    pub next_page: Option<String>,
+}
+
+/// Results for one time bucket
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimeBucket<T> {
+    /// Start of the bucket (RFC 3339)
+    pub starting_at: String,
+
+    /// End of the bucket (RFC 3339)
+    pub ending_at: String,
+
+    /// One result per group
+    pub results: Vec<T>,
+}
+
+/// Cache creation tokens by cache lifetime
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheCreationUsage {
+    #[serde(default)]
+    pub ephemeral_5m_input_tokens: u64,
+    #[serde(default)]
+    pub ephemeral_1h_input_tokens: u64,
+}
+
+/// Server tool usage counts
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ServerToolUsage {
+    #[serde(default)]
+    pub web_search_requests: u64,
+}
+
+/// Token usage of one group in a time bucket
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageResult {
+    #[serde(default)]
+    pub uncached_input_tokens: u64,
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    #[serde(default)]
+    pub cache_creation: CacheCreationUsage,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub server_tool_use: ServerToolUsage,
+
+    /// Group keys (set when grouped by the field)
+    #[serde(default)]
+    pub api_key_id: Option<String>,
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub service_tier: Option<String>,
+    #[serde(default)]
+    pub context_window: Option<String>,
+}
+
+impl UsageResult {
+    /// Get all input tokens (uncached, cache reads, and cache writes)
+    pub fn input_tokens(&self) -> u64 {
+        self.uncached_input_tokens
+            + self.cache_read_input_tokens
+            + self.cache_creation.ephemeral_5m_input_tokens
+            + self.cache_creation.ephemeral_1h_input_tokens
+    }
+}
+
+/// Cost of one group in a time bucket
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct CostResult {
+    /// Currency code (e.g. "USD")
+    pub currency: String,
+
+    /// Cost in the lowest currency unit (e.g. cents), as a decimal string
+    pub amount: String,
+
+    /// Group keys (set when grouped by the field)
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Cost breakdown (set when grouped by `description`)
+    #[serde(default)]
+    pub cost_type: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub token_type: Option<String>,
+    #[serde(default)]
+    pub service_tier: Option<String>,
+    #[serde(default)]
+    pub context_window: Option<String>,
+}
+
+impl CostResult {
+    /// Parse the amount
+    pub fn amount_value(&self) -> Option<f64> {
+        self.amount.parse().ok()
+    }
+}
+
+impl Report<CostResult> {
+    /// Sum of all amounts on this page (assumes a single currency)
+    pub fn total_amount(&self) -> f64 {
+        self.data
+            .iter()
+            .flat_map(|bucket| &bucket.results)
+            .filter_map(CostResult::amount_value)
+            .sum()
+    }
+}
+
+/// Client for the Admin API
+///
+/// Wraps a [`Messages`] client whose API key is an admin key, reusing its
+/// base URL, transport, and other connection settings.
+#[derive(Debug, Clone)]
+pub struct Admin {
+    client: Messages,
+}
+
+impl Default for Admin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Admin {
+    /// Create a client, loading the admin key from `ANTHROPIC_ADMIN_KEY`
+    ///
+    /// The base URL is read from `ANTHROPIC_BASE_URL` if set, as for [`Messages::new`].
+    pub fn new() -> Self {
+        let admin_key = env::var("ANTHROPIC_ADMIN_KEY").unwrap_or_default();
+        let mut client = Messages::with_api_key(admin_key);
+        if let Ok(base_url) = env::var("ANTHROPIC_BASE_URL")
+            && !base_url.is_empty()
+        {
+            client.base_url(base_url);
+        }
+        Admin::from_client(client)
+    }
+
+    /// Create a client with an explicit admin key
+    pub fn with_admin_key<T: AsRef<str>>(admin_key: T) -> Self {
+        Admin::from_client(Messages::with_api_key(admin_key))
+    }
+
+    /// Use a Messages client configured with an admin key
+    pub fn from_client(client: Messages) -> Self {
+        Admin { client }
+    }
+
+    /// Get the underlying Messages client
+    pub fn client(&self) -> &Messages {
+        &self.client
+    }
+
+    /// Get token usage per time bucket
+    pub async fn usage_report(&self, params: &ReportParams) -> Result<Report<UsageResult>> {
+        let path = format!("{}{}", USAGE_REPORT_PATH, params.query());
+        self.client.send_json("GET", &path, None::<&()>).await
+    }
+
+    /// Get costs per time bucket
+    pub async fn cost_report(&self, params: &ReportParams) -> Result<Report<CostResult>> {
+        let path = format!("{}{}", COST_REPORT_PATH, params.query());
+        self.client.send_json("GET", &path, None::<&()>).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::future::{BoxFuture, boxed};
+    use crate::common::transport::{HttpRequest, HttpResponse, HttpTransport};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct RecordingTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl HttpTransport for RecordingTransport {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            let body = if request.url.contains("cost_report") {
+                r#"{"data":[{"starting_at":"2025-06-01T00:00:00Z","ending_at":"2025-06-02T00:00:00Z",
+                    "results":[{"currency":"USD","amount":"1250.5","workspace_id":"wrkspc_1"},
+                               {"currency":"USD","amount":"49.5","workspace_id":null}]}],
+                    "has_more":true,"next_page":"page_2"}"#
+            } else {
+                r#"{"data":[{"starting_at":"2025-06-01T00:00:00Z","ending_at":"2025-06-02T00:00:00Z",
+                    "results":[{"uncached_input_tokens":100,"cache_read_input_tokens":50,
+                                "cache_creation":{"ephemeral_5m_input_tokens":10,"ephemeral_1h_input_tokens":0},
+                                "output_tokens":20,"server_tool_use":{"web_search_requests":1},
+                                "model":"claude-sonnet-4-20250514"}]}],
+                    "has_more":false,"next_page":null}"#
+            };
+            self.requests.lock().unwrap().push(request);
+            boxed(async move { Ok(HttpResponse::from_bytes(200, body)) })
+        }
+    }
+
+    #[test]
+    fn test_report_query() {
+        let mut params = ReportParams::new("2025-06-01T00:00:00Z");
+        params
+            .bucket_width(BucketWidth::Hour)
+            .group_by("model")
+            .group_by("workspace_id")
+            .model("claude-sonnet-4-20250514")
+            .page("page_2");
+        assert_eq!(
+            params.query(),
+            "?starting_at=2025-06-01T00%3A00%3A00Z&bucket_width=1h&group_by[]=model\
+             &group_by[]=workspace_id&models[]=claude-sonnet-4-20250514&page=page_2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_usage_and_cost_reports() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mut client = Messages::with_api_key("sk-ant-admin-test");
+        client.transport(Arc::clone(&transport));
+        let admin = Admin::from_client(client);
+        let params = ReportParams::new("2025-06-01T00:00:00Z");
+
+        let usage = admin.usage_report(&params).await.unwrap();
+        let result = &usage.data[0].results[0];
+        assert_eq!(result.input_tokens(), 160);
+        assert_eq!(result.server_tool_use.web_search_requests, 1);
+        assert!(usage.next_page.is_none());
+
+        let costs = admin.cost_report(&params).await.unwrap();
+        assert_eq!(costs.total_amount(), 1300.0);
+        assert_eq!(costs.next_page.as_deref(), Some("page_2"));
+
+        let requests = transport.requests.lock().unwrap();
+        assert!(
+            requests[0]
+                .url
+                .contains("/v1/organizations/usage_report/messages?starting_at=")
+        );
+        assert!(requests[1].url.contains("/v1/organizations/cost_report?"));
+        assert_eq!(requests[1].header("x-api-key"), Some("sk-ant-admin-test"));
+    }
+}
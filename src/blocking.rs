@@ -0,0 +1,185 @@
+//! Synchronous client for code without an async runtime.
+//!
+//! [`Messages`] wraps the async [`messages::request::Messages`] client together
+//! with a private single-threaded tokio runtime. Builder methods are reached
+//! through `Deref`, so configuration reads exactly like the async API; only
+//! the sending methods differ and block the calling thread:
+//!
+//! - [`Messages::post`] - Send the request and wait for the [`Response`]
+//! - [`Messages::post_stream`] - Iterate over stream events as they arrive
+//! - [`Messages::dry_run`] - Render the request without sending it
+//!
+//! Requires the `blocking` feature. The blocking methods panic if called from
+//! inside an async runtime; use the async client there instead.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::blocking::Messages;
+//!
+//! fn main() -> anthropic_tools::Result<()> {
+//!     let mut client = Messages::new()?;
+//!     client
+//!         .model("claude-sonnet-4-20250514")
+//!         .max_tokens(1024)
+//!         .user("Hello!");
+//!
+//!     let response = client.post()?;
+//!     println!("{}", response.get_text());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`messages::request::Messages`]: crate::messages::request::Messages
+
+use crate::common::errors::Result;
+use crate::messages::request::Messages as AsyncMessages;
+use crate::messages::request::dry_run::DryRun;
+use crate::messages::response::Response;
+use crate::messages::streaming::{EventStream, StreamEvent};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use tokio::runtime::{Builder, Runtime};
+
+/// Blocking Messages API client
+#[derive(Debug, Clone)]
+pub struct Messages {
+    inner: AsyncMessages,
+    runtime: Arc<Runtime>,
+}
+
+impl Messages {
+    /// Create a client, loading the API key from `ANTHROPIC_API_KEY`
+    pub fn new() -> Result<Self> {
+        Self::from_async(AsyncMessages::new())
+    }
+
+    /// Create a client with an explicit API key
+    pub fn with_api_key<T: AsRef<str>>(api_key: T) -> Result<Self> {
+        Self::from_async(AsyncMessages::with_api_key(api_key))
+    }
+
+    /// Wrap an already configured async client
+    pub fn from_async(inner: AsyncMessages) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Messages {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Get the wrapped async client
+    pub fn into_async(self) -> AsyncMessages {
+        self.inner
+    }
+
+    /// Send the request and wait for the response
+    pub fn post(&self) -> Result<Response> {
+        self.runtime.block_on(self.inner.post())
+    }
+
+    /// Send the request with additional headers and wait for the response
+    pub fn post_with_headers<I, K, V>(&self, headers: I) -> Result<Response>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.runtime.block_on(self.inner.post_with_headers(headers))
+    }
+
+    /// Send the request with streaming enabled and iterate over the events
+    pub fn post_stream(&self) -> Result<EventIter> {
+        let events = self.runtime.block_on(self.inner.post_stream())?;
+        Ok(EventIter {
+            events,
+            runtime: Arc::clone(&self.runtime),
+        })
+    }
+
+    /// Render the request that would be sent, without sending it
+    pub fn dry_run(&self) -> Result<DryRun> {
+        self.runtime.block_on(self.inner.dry_run())
+    }
+}
+
+impl Deref for Messages {
+    type Target = AsyncMessages;
+
+    fn deref(&self) -> &AsyncMessages {
+        &self.inner
+    }
+}
+
+impl DerefMut for Messages {
+    fn deref_mut(&mut self) -> &mut AsyncMessages {
+        &mut self.inner
+    }
+}
+
+/// Blocking iterator over the events of a streaming request
+#[derive(Debug)]
+pub struct EventIter {
+    events: EventStream,
+    runtime: Arc<Runtime>,
+}
+
+impl Iterator for EventIter {
+    type Item = Result<StreamEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.events.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::future::{BoxFuture, boxed};
+    use crate::common::transport::{HttpRequest, HttpResponse, HttpTransport};
+
+    #[derive(Debug)]
+    struct StaticTransport(&'static str);
+
+    impl HttpTransport for StaticTransport {
+        fn send(&self, _request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            boxed(async move { Ok(HttpResponse::from_bytes(200, self.0)) })
+        }
+    }
+
+    #[test]
+    fn test_blocking_post() {
+        let mut client = Messages::with_api_key("test_key").unwrap();
+        client
+            .model("claude-sonnet-4-20250514")
+            .max_tokens(64)
+            .user("Hello!")
+            .transport(StaticTransport(
+                r#"{"id":"msg_1","type":"message","role":"assistant","content":[{"type":"text","text":"Hi!"}],"model":"claude-sonnet-4-20250514","stop_reason":"end_turn","usage":{"input_tokens":3,"output_tokens":2}}"#,
+            ));
+
+        let response = client.post().unwrap();
+        assert_eq!(response.get_text(), "Hi!");
+        assert!(client.dry_run().unwrap().body.contains("Hello!"));
+    }
+
+    #[test]
+    fn test_blocking_stream() {
+        let mut client = Messages::with_api_key("test_key").unwrap();
+        client
+            .model("claude-sonnet-4-20250514")
+            .max_tokens(64)
+            .user("Hello!")
+            .transport(StaticTransport(
+                "data: {\"type\":\"ping\"}\n\ndata: {\"type\":\"message_stop\"}\n\n",
+            ));
+
+        let events: Vec<_> = client
+            .post_stream()
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], StreamEvent::MessageStop));
+    }
+}
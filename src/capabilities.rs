@@ -0,0 +1,261 @@
+//! Machine-readable description of this build.
+//!
+//! [`capabilities()`] reports what the crate supports as compiled, taking the
+//! enabled cargo features into account: API features, endpoints, beta flags,
+//! and content block types. Orchestration layers can feature-detect with it
+//! instead of comparing crate versions. The report serializes to JSON.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::capabilities;
+//!
+//! let caps = capabilities();
+//! assert!(caps.supports("streaming"));
+//! assert!(caps.has_endpoint("/v1/messages/count_tokens"));
+//! assert!(caps.has_content_block("tool_use"));
+//!
+//! let json = serde_json::to_string(&caps).unwrap();
+//! assert!(json.contains("\"api_version\""));
+//! ```
+
+use crate::common::beta::Beta;
+use crate::messages::request::ANTHROPIC_VERSION;
+use crate::messages::request::content::ContentBlock;
+use serde::Serialize;
+
+/// API endpoint supported by the client
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// HTTP method
+    pub method: &'static str,
+    /// Path below the base URL (`{id}` marks a path parameter)
+    pub path: &'static str,
+    /// Client method that calls the endpoint
+    pub operation: &'static str,
+}
+
+/// Capabilities of this build
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Crate version
+    pub crate_version: &'static str,
+    /// Default `anthropic-version` header
+    pub api_version: &'static str,
+    /// API features the client supports
+    pub features: Vec<&'static str>,
+    /// Endpoints the client calls
+    pub endpoints: Vec<Endpoint>,
+    /// Beta flags with a named [`Beta`] variant
+    pub betas: Vec<String>,
+    /// Content block types that can be sent and received
+    pub content_blocks: Vec<&'static str>,
+    /// Ready-made tools compiled in
+    pub builtin_tools: Vec<&'static str>,
+    /// Enabled cargo features
+    pub cargo_features: Vec<&'static str>,
+}
+
+impl Capabilities {
+    /// Check whether an API feature is supported
+    pub fn supports(&self, feature: &str) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Check whether an endpoint path is supported
+    pub fn has_endpoint(&self, path: &str) -> bool {
+        self.endpoints.iter().any(|endpoint| endpoint.path == path)
+    }
+
+    /// Check whether a beta flag (header value) is known
+    pub fn has_beta(&self, beta: &str) -> bool {
+        self.betas.iter().any(|known| known == beta)
+    }
+
+    /// Check whether a content block type is supported
+    pub fn has_content_block(&self, block_type: &str) -> bool {
+        self.content_blocks.contains(&block_type)
+    }
+
+    /// Check whether a ready-made tool is compiled in
+    pub fn has_builtin_tool(&self, name: &str) -> bool {
+        self.builtin_tools.contains(&name)
+    }
+}
+
+/// Describe the capabilities of this build
+pub fn capabilities() -> Capabilities {
+    let mut features = vec![
+        "messages",
+        "streaming",
+        "tool_use",
+        "tool_loop",
+        "vision",
+        "pdf_documents",
+        "prompt_caching",
+        "extended_thinking",
+        "mcp_connector",
+        "token_counting",
+        "message_batches",
+        "bulk_submission",
+        "files_api",
+        "models_api",
+        "admin_reports",
+        "text_completions",
+        "web_search",
+        "computer_use",
+        "bash_tool",
+        "text_editor_tool",
+        "code_execution",
+        "citations",
+        "structured_outputs",
+        "context_management",
+    ];
+    if cfg!(feature = "image") {
+        features.push("image_files");
+    }
+    if cfg!(feature = "image-convert") {
+        features.push("image_conversion");
+    }
+    if cfg!(feature = "blocking") {
+        features.push("blocking_client");
+    }
+    if cfg!(feature = "metrics") {
+        features.push("metrics");
+    }
+    if cfg!(feature = "opentelemetry") {
+        features.push("opentelemetry");
+    }
+    if cfg!(feature = "schemars") {
+        features.push("schemars_schemas");
+    }
+    if cfg!(feature = "yaml") {
+        features.push("yaml_tool_files");
+    }
+    if cfg!(feature = "mcp-client") {
+        features.push("mcp_client");
+    }
+    if cfg!(feature = "derive") {
+        features.push("derive_tool_input");
+        features.push("tool_attribute");
+    }
+
+    let mut builtin_tools = Vec::new();
+    if cfg!(feature = "tool-fs") {
+        builtin_tools.push("fs");
+    }
+    if cfg!(feature = "tool-http") {
+        builtin_tools.push("http");
+    }
+    if cfg!(feature = "tool-shell") {
+        builtin_tools.push("shell");
+    }
+    if cfg!(feature = "tool-bash") {
+        builtin_tools.push("bash");
+    }
+    if cfg!(feature = "tool-text-editor") {
+        builtin_tools.push("text_editor");
+    }
+    if cfg!(feature = "tool-sql") {
+        builtin_tools.push("sql");
+    }
+
+    let cargo_features = [
+        ("image", cfg!(feature = "image")),
+        ("image-convert", cfg!(feature = "image-convert")),
+        ("rt-tokio", cfg!(feature = "rt-tokio")),
+        ("blocking", cfg!(feature = "blocking")),
+        ("metrics", cfg!(feature = "metrics")),
+        ("opentelemetry", cfg!(feature = "opentelemetry")),
+        ("derive", cfg!(feature = "derive")),
+        ("schemars", cfg!(feature = "schemars")),
+        ("yaml", cfg!(feature = "yaml")),
+        ("tool-fs", cfg!(feature = "tool-fs")),
+        ("tool-http", cfg!(feature = "tool-http")),
+        ("tool-shell", cfg!(feature = "tool-shell")),
+        ("tool-bash", cfg!(feature = "tool-bash")),
+        ("tool-text-editor", cfg!(feature = "tool-text-editor")),
+        ("tool-sql", cfg!(feature = "tool-sql")),
+        ("mcp-client", cfg!(feature = "mcp-client")),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect();
+
+    Capabilities {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        api_version: ANTHROPIC_VERSION,
+        features,
+        endpoints: endpoints(),
+        betas: Beta::known()
+            .iter()
+            .map(|beta| beta.as_str().to_string())
+            .collect(),
+        content_blocks: ContentBlock::TYPES.to_vec(),
+        builtin_tools,
+        cargo_features,
+    }
+}
+
+/// Endpoints called by the clients
+fn endpoints() -> Vec<Endpoint> {
+    let endpoint = |method, path, operation| Endpoint {
+        method,
+        path,
+        operation,
+    };
+    vec![
+        endpoint("POST", "/v1/messages", "Messages::post"),
+        endpoint(
+            "POST",
+            "/v1/messages/count_tokens",
+            "Messages::count_tokens",
+        ),
+        endpoint("POST", "/v1/messages/batches", "Batches::create"),
+        endpoint("GET", "/v1/messages/batches", "Batches::list"),
+        endpoint("GET", "/v1/messages/batches/{id}", "Batches::get"),
+        endpoint(
+            "POST",
+            "/v1/messages/batches/{id}/cancel",
+            "Batches::cancel",
+        ),
+        endpoint(
+            "GET",
+            "/v1/messages/batches/{id}/results",
+            "Batches::results",
+        ),
+        endpoint("POST", "/v1/complete", "Completions::post"),
+        endpoint("POST", "/v1/files", "Files::upload"),
+        endpoint("GET", "/v1/files", "Files::list"),
+        endpoint("GET", "/v1/files/{id}", "Files::get"),
+        endpoint("DELETE", "/v1/files/{id}", "Files::delete"),
+        endpoint("GET", "/v1/models", "Models::list"),
+        endpoint("GET", "/v1/models/{id}", "Models::get"),
+        endpoint(
+            "GET",
+            "/v1/organizations/usage_report/messages",
+            "Admin::usage_report",
+        ),
+        endpoint("GET", "/v1/organizations/cost_report", "Admin::cost_report"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        let caps = capabilities();
+        assert_eq!(caps.api_version, "2023-06-01");
+        assert!(caps.supports("message_batches"));
+        assert!(!caps.supports("teleportation"));
+        assert!(caps.has_beta("files-api-2025-04-14"));
+        assert!(caps.has_content_block("document"));
+        assert_eq!(caps.supports("image_files"), cfg!(feature = "image"));
+        assert_eq!(caps.has_builtin_tool("shell"), cfg!(feature = "tool-shell"));
+
+        let json = serde_json::to_value(&caps).unwrap();
+        assert_eq!(json["endpoints"][0]["path"], "/v1/messages");
+    }
+}
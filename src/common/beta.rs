@@ -0,0 +1,192 @@
+//! Beta feature flags for the `anthropic-beta` header.
+//!
+//! Many API features are gated behind beta flags sent in the `anthropic-beta`
+//! header. This module provides the [`Beta`] enum so flags can be selected by
+//! name instead of copying raw header strings:
+//!
+//! - [`Beta`] - Known beta flags plus [`Beta::Other`] for new ones
+//! - [`header_value`] - Join flags into a header value
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::beta::{Beta, header_value};
+//!
+//! let value = header_value(&[Beta::TokenEfficientTools, Beta::Output128k]);
+//! assert_eq!(value, "token-efficient-tools-2025-02-19,output-128k-2025-02-19");
+//!
+//! let custom: Beta = "some-new-feature-2025-09-01".parse().unwrap();
+//! assert_eq!(custom, Beta::Other("some-new-feature-2025-09-01".to_string()));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// Name of the header carrying beta flags
+pub const BETA_HEADER: &str = "anthropic-beta";
+
+/// Beta feature flag
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum Beta {
+    /// Message Batches API
+    MessageBatches,
+    /// Token counting endpoint
+    TokenCounting,
+    /// 1-hour prompt cache TTL
+    ExtendedCacheTtl,
+    /// Token-efficient tool use
+    TokenEfficientTools,
+    /// 128k output tokens
+    Output128k,
+    /// Computer use tools
+    ComputerUse,
+    /// Code execution tool
+    CodeExecution,
+    /// Files API
+    FilesApi,
+    /// MCP connector (`mcp_servers`)
+    McpClient,
+    /// Interleaved thinking between tool calls
+    InterleavedThinking,
+    /// Fine-grained tool input streaming
+    FineGrainedToolStreaming,
+    /// 1M token context window
+    Context1m,
+    /// Context management (automatic context editing)
+    ContextManagement,
+    /// Structured outputs (`output_format`)
+    StructuredOutputs,
+    /// Any other beta flag
+    Other(String),
+}
+
+impl Beta {
+    /// Get the header value for this flag
+    pub fn as_str(&self) -> &str {
+        match self {
+            Beta::MessageBatches => "message-batches-2024-09-24",
+            Beta::TokenCounting => "token-counting-2024-11-01",
+            Beta::ExtendedCacheTtl => "extended-cache-ttl-2025-04-11",
+            Beta::TokenEfficientTools => "token-efficient-tools-2025-02-19",
+            Beta::Output128k => "output-128k-2025-02-19",
+            Beta::ComputerUse => "computer-use-2025-01-24",
+            Beta::CodeExecution => "code-execution-2025-05-22",
+            Beta::FilesApi => "files-api-2025-04-14",
+            Beta::McpClient => "mcp-client-2025-04-04",
+            Beta::InterleavedThinking => "interleaved-thinking-2025-05-14",
+            Beta::FineGrainedToolStreaming => "fine-grained-tool-streaming-2025-05-14",
+            Beta::Context1m => "context-1m-2025-08-07",
+            Beta::ContextManagement => "context-management-2025-06-27",
+            Beta::StructuredOutputs => "structured-outputs-2025-11-13",
+            Beta::Other(value) => value,
+        }
+    }
+
+    /// All known (non-`Other`) beta flags
+    pub fn known() -> Vec<Beta> {
+        vec![
+            Beta::MessageBatches,
+            Beta::TokenCounting,
+            Beta::ExtendedCacheTtl,
+            Beta::TokenEfficientTools,
+            Beta::Output128k,
+            Beta::ComputerUse,
+            Beta::CodeExecution,
+            Beta::FilesApi,
+            Beta::McpClient,
+            Beta::InterleavedThinking,
+            Beta::FineGrainedToolStreaming,
+            Beta::Context1m,
+            Beta::ContextManagement,
+            Beta::StructuredOutputs,
+        ]
+    }
+}
+
+impl fmt::Display for Beta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Beta {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(Beta::known()
+            .into_iter()
+            .find(|beta| beta.as_str() == s)
+            .unwrap_or_else(|| Beta::Other(s.to_string())))
+    }
+}
+
+impl From<String> for Beta {
+    fn from(value: String) -> Self {
+        value.parse().unwrap_or(Beta::Other(value))
+    }
+}
+
+impl From<&str> for Beta {
+    fn from(value: &str) -> Self {
+        Beta::from(value.to_string())
+    }
+}
+
+impl From<Beta> for String {
+    fn from(beta: Beta) -> Self {
+        beta.as_str().to_string()
+    }
+}
+
+/// Join beta flags into an `anthropic-beta` header value, dropping duplicates
+pub fn header_value(betas: &[Beta]) -> String {
+    let mut values: Vec<&str> = Vec::new();
+    for beta in betas {
+        if !values.contains(&beta.as_str()) {
+            values.push(beta.as_str());
+        }
+    }
+    values.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_roundtrip() {
+        for beta in Beta::known() {
+            let parsed: Beta = beta.as_str().parse().unwrap();
+            assert_eq!(parsed, beta);
+        }
+    }
+
+    #[test]
+    fn test_other() {
+        let beta = Beta::from("custom-2099-01-01");
+        assert_eq!(beta, Beta::Other("custom-2099-01-01".to_string()));
+        assert_eq!(beta.to_string(), "custom-2099-01-01");
+    }
+
+    #[test]
+    fn test_header_value_dedup() {
+        let value = header_value(&[
+            Beta::FilesApi,
+            Beta::from("files-api-2025-04-14"),
+            Beta::ComputerUse,
+        ]);
+        assert_eq!(value, "files-api-2025-04-14,computer-use-2025-01-24");
+    }
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Beta::McpClient).unwrap();
+        assert_eq!(json, "\"mcp-client-2025-04-04\"");
+        let beta: Beta = serde_json::from_str(&json).unwrap();
+        assert_eq!(beta, Beta::McpClient);
+    }
+}
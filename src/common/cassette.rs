@@ -0,0 +1,256 @@
+//! Record/replay of API traffic (VCR-style cassettes).
+//!
+//! A [`Cassette`] stores request/response pairs in a JSON file. In record mode
+//! every real API call is appended to the file; in replay mode calls are
+//! answered from the file without touching the network (and without an API
+//! key). Streaming responses are stored as the raw SSE text, so replayed
+//! streams yield exactly the recorded event sequence.
+//!
+//! - [`Cassette`] - Cassette file attached to a client
+//! - [`CassetteMode`] - Record, replay, or decide automatically
+//! - [`Interaction`] - One recorded request/response pair
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::common::cassette::Cassette;
+//! use anthropic_tools::messages::request::Messages;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! // Records on the first run, replays once the file exists
+//! let cassette = Cassette::new("tests/cassettes/basic_message.json")?;
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .cassette(cassette)
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(100)
+//!     .user("What is 2 + 2?");
+//!
+//! let response = client.post().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests are matched by method, URL, and JSON body. Headers (including
+//! the API key) are never written to the cassette.
+
+use crate::common::errors::{AnthropicToolError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// How a cassette treats API calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CassetteMode {
+    /// Send real requests and overwrite the cassette with the new interactions
+    Record,
+    /// Answer requests from the cassette; fail if no interaction matches
+    Replay,
+    /// Replay if the cassette file exists, otherwise record
+    #[default]
+    Auto,
+}
+
+/// One recorded request/response pair
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// Request as stored in a cassette (headers are not recorded)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub body: Value,
+}
+
+/// Response as stored in a cassette
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Raw response body (JSON, or SSE text for streaming responses)
+    pub body: String,
+}
+
+/// On-disk cassette format
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+/// Mutable cassette state
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+/// Cassette file that records or replays API calls
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    replaying: bool,
+    state: Mutex<CassetteState>,
+}
+
+impl Cassette {
+    /// Open a cassette in [`CassetteMode::Auto`]
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_mode(path, CassetteMode::Auto)
+    }
+
+    /// Open a cassette in the given mode
+    ///
+    /// Replay mode loads the file immediately and fails if it is missing.
+    pub fn with_mode<P: AsRef<Path>>(path: P, mode: CassetteMode) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let replaying = match mode {
+            CassetteMode::Record => false,
+            CassetteMode::Replay => true,
+            CassetteMode::Auto => path.exists(),
+        };
+
+        let interactions = if replaying {
+            let file: CassetteFile = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            file.interactions
+        } else {
+            Vec::new()
+        };
+
+        Ok(Cassette {
+            path,
+            replaying,
+            state: Mutex::new(CassetteState {
+                used: vec![false; interactions.len()],
+                interactions,
+            }),
+        })
+    }
+
+    /// Get the cassette file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Check whether requests are answered from the cassette
+    pub fn is_replaying(&self) -> bool {
+        self.replaying
+    }
+
+    /// Get a copy of the interactions loaded or recorded so far
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().interactions.clone()
+    }
+
+    /// Find the first unused interaction matching the request
+    pub(crate) fn replay(&self, request: &RecordedRequest) -> Result<RecordedResponse> {
+        let mut state = self.lock();
+        let CassetteState { interactions, used } = &mut *state;
+        let index = interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| !used && interaction.request == *request)
+            .ok_or_else(|| {
+                AnthropicToolError::CassetteMiss(format!(
+                    "{} {} in {}",
+                    request.method,
+                    request.url,
+                    self.path.display()
+                ))
+            })?;
+        used[index] = true;
+        Ok(interactions[index].response.clone())
+    }
+
+    /// Append an interaction and write the cassette file
+    pub(crate) fn record(&self, interaction: Interaction) -> Result<()> {
+        let mut state = self.lock();
+        state.interactions.push(interaction);
+        state.used.push(true);
+
+        if let Some(parent) = self.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = CassetteFile {
+            interactions: state.interactions.clone(),
+        };
+        std::fs::write(&self.path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "anthropic-tools-cassette-{}-{}.json",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn request(text: &str) -> RecordedRequest {
+        RecordedRequest {
+            method: "POST".to_string(),
+            url: "https://api.anthropic.com/v1/messages".to_string(),
+            body: json!({"messages": [{"role": "user", "content": text}]}),
+        }
+    }
+
+    #[test]
+    fn test_record_then_replay() {
+        let path = temp_path("roundtrip");
+        let _ = std::fs::remove_file(&path);
+
+        let cassette = Cassette::new(&path).unwrap();
+        assert!(!cassette.is_replaying());
+        cassette
+            .record(Interaction {
+                request: request("hi"),
+                response: RecordedResponse {
+                    status: 200,
+                    body: "{}".to_string(),
+                },
+            })
+            .unwrap();
+
+        let cassette = Cassette::new(&path).unwrap();
+        assert!(cassette.is_replaying());
+        assert_eq!(cassette.replay(&request("hi")).unwrap().status, 200);
+        // Each interaction is replayed once
+        assert!(cassette.replay(&request("hi")).is_err());
+        assert!(matches!(
+            cassette.replay(&request("other")),
+            Err(AnthropicToolError::CassetteMiss(_))
+        ));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_requires_file() {
+        let path = temp_path("missing");
+        assert!(Cassette::with_mode(&path, CassetteMode::Replay).is_err());
+        assert!(
+            !Cassette::with_mode(&path, CassetteMode::Record)
+                .unwrap()
+                .is_replaying()
+        );
+    }
+}
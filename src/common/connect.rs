@@ -0,0 +1,97 @@
+//! Connection targets for the HTTP client.
+//!
+//! [`ConnectConfig`] points the underlying HTTP client at endpoints other than
+//! the public API over TCP, such as an internal gateway:
+//!
+//! - `unix_socket` - Send every connection through a Unix domain socket (Unix only)
+//! - `resolve` - Map host names to fixed addresses, bypassing DNS
+//!
+//! Combine these with `Messages::base_url` to choose the scheme and host used
+//! in request URLs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::connect::ConnectConfig;
+//!
+//! let mut connect = ConnectConfig::default();
+//! connect.resolve("gateway.internal", "10.0.0.5:8080".parse().unwrap());
+//! # #[cfg(unix)]
+//! connect.unix_socket("/run/llm-gateway.sock");
+//! ```
+
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+
+/// Custom connection targets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectConfig {
+    /// Unix domain socket every connection goes through
+    #[cfg(unix)]
+    pub unix_socket: Option<PathBuf>,
+
+    /// Fixed addresses for host names (host, address)
+    pub resolve: Vec<(String, SocketAddr)>,
+}
+
+impl ConnectConfig {
+    /// Send every connection through a Unix domain socket
+    ///
+    /// DNS resolution and proxies are skipped. `https` URLs still use TLS over
+    /// the socket, so local gateways are usually addressed with `http://`.
+    #[cfg(unix)]
+    pub fn unix_socket<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.unix_socket = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Connect to a fixed address for the given host name
+    ///
+    /// A port of `0` keeps the port from the request URL's scheme.
+    pub fn resolve<T: AsRef<str>>(&mut self, host: T, addr: SocketAddr) -> &mut Self {
+        let host = host.as_ref().to_ascii_lowercase();
+        self.resolve.retain(|(existing, _)| *existing != host);
+        self.resolve.push((host, addr));
+        self
+    }
+
+    /// Apply the settings to an HTTP client builder
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn apply(&self, mut builder: request::ClientBuilder) -> request::ClientBuilder {
+        #[cfg(unix)]
+        if let Some(path) = &self.unix_socket {
+            builder = builder.unix_socket(path.as_path());
+        }
+        for (host, addr) in &self.resolve {
+            builder = builder.resolve(host, *addr);
+        }
+        builder
+    }
+
+    /// Apply the settings to an HTTP client builder
+    ///
+    /// The browser's `fetch` chooses connections itself, so this is a no-op on `wasm32`.
+    #[cfg(target_arch = "wasm32")]
+    pub fn apply(&self, builder: request::ClientBuilder) -> request::ClientBuilder {
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_replaces_host() {
+        let mut connect = ConnectConfig::default();
+        connect
+            .resolve("Gateway.Internal", "10.0.0.5:8080".parse().unwrap())
+            .resolve("gateway.internal", "10.0.0.6:8080".parse().unwrap());
+
+        assert_eq!(connect.resolve.len(), 1);
+        assert_eq!(connect.resolve[0].0, "gateway.internal");
+        assert_eq!(connect.resolve[0].1.to_string(), "10.0.0.6:8080");
+        assert!(connect.apply(request::Client::builder()).build().is_ok());
+    }
+}
@@ -30,6 +30,8 @@
 //! }
 //! ```
 
+use crate::messages::request::image_check::ImageViolation;
+use crate::messages::request::tool_loop::{LoopLimit, ToolLoop};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -78,10 +80,81 @@ pub enum AnthropicToolError {
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("No recorded interaction matches the request: {0}")]
+    CassetteMiss(String),
+
+    #[error("Invalid input for tool {tool} at `{path}`: {message}")]
+    InvalidToolInput {
+        tool: String,
+        /// Path of the offending field (`.` for the input itself)
+        path: String,
+        message: String,
+    },
+
+    #[error("Invalid structured output at `{path}`: {message}")]
+    InvalidOutput {
+        /// Path of the offending field (`.` for the output itself)
+        path: String,
+        message: String,
+    },
+
+    #[error("Cannot load image {location}: {message}")]
+    ImageError {
+        /// Path or URL of the image
+        location: String,
+        message: String,
+    },
+
+    #[error("Invalid images: {}", join_violations(.0))]
+    InvalidImages(Vec<ImageViolation>),
+
+    #[error("Tool loop stopped after reaching its limit of {limit}")]
+    LoopLimitExceeded {
+        limit: LoopLimit,
+        /// Conversation up to the limit, ending with tool results
+        transcript: Box<ToolLoop>,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, AnthropicToolError>;
 
+impl AnthropicToolError {
+    /// Get a short, stable identifier for the error kind (for logs and metrics)
+    pub fn error_type(&self) -> &str {
+        match self {
+            AnthropicToolError::ApiKeyNotSet => "api_key_not_set",
+            AnthropicToolError::MissingRequiredField(_) => "missing_required_field",
+            AnthropicToolError::InvalidParameter(_) => "invalid_parameter",
+            AnthropicToolError::RequestError(_) => "request_error",
+            AnthropicToolError::SerdeJsonError(_) => "serde_json_error",
+            AnthropicToolError::ApiError { error_type, .. } => error_type,
+            AnthropicToolError::InvalidRequestError(_) => "invalid_request_error",
+            AnthropicToolError::AuthenticationError(_) => "authentication_error",
+            AnthropicToolError::PermissionError(_) => "permission_error",
+            AnthropicToolError::NotFoundError(_) => "not_found_error",
+            AnthropicToolError::RateLimitError(_) => "rate_limit_error",
+            AnthropicToolError::OverloadedError(_) => "overloaded_error",
+            AnthropicToolError::IoError(_) => "io_error",
+            AnthropicToolError::CassetteMiss(_) => "cassette_miss",
+            AnthropicToolError::InvalidToolInput { .. } => "invalid_tool_input",
+            AnthropicToolError::InvalidOutput { .. } => "invalid_output",
+            AnthropicToolError::ImageError { .. } => "image_error",
+            AnthropicToolError::InvalidImages(_) => "invalid_images",
+            AnthropicToolError::LoopLimitExceeded { .. } => "loop_limit_exceeded",
+        }
+    }
+}
+
+/// Join image violations into one line
+fn join_violations(violations: &[ImageViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Error response from Anthropic API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -121,3 +194,34 @@ impl ErrorResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_response_into_error() {
+        let json = r#"{
+            "type": "error",
+            "error": {"type": "rate_limit_error", "message": "Slow down"}
+        }"#;
+        let response: ErrorResponse = serde_json::from_str(json).unwrap();
+        let error = response.into_error();
+        assert!(matches!(error, AnthropicToolError::RateLimitError(_)));
+        assert_eq!(error.error_type(), "rate_limit_error");
+    }
+
+    #[test]
+    fn test_error_type_passthrough() {
+        let error = AnthropicToolError::ApiError {
+            error_type: "api_error".to_string(),
+            message: "Internal".to_string(),
+            request_id: None,
+        };
+        assert_eq!(error.error_type(), "api_error");
+        assert_eq!(
+            AnthropicToolError::ApiKeyNotSet.error_type(),
+            "api_key_not_set"
+        );
+    }
+}
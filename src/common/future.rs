@@ -0,0 +1,112 @@
+//! Boxed futures for the crate's async extension points.
+//!
+//! Public traits with async methods (HTTP transports, tool handlers, ...) return
+//! a [`BoxFuture`] instead of using `async fn` in traits or the `async-trait`
+//! crate. This keeps every trait:
+//!
+//! - Object safe, so implementations can be stored as `Box<dyn Trait>` or `Arc<dyn Trait>`
+//! - Explicitly `Send`, so calls can be spawned on multi-threaded executors
+//!   (except on `wasm32`, where JS-backed futures are never `Send`; see [`MaybeSend`])
+//! - Usable on the crate's minimum supported Rust version (see `rust-version` in `Cargo.toml`)
+//!
+//! Implementations simply wrap an `async` block with [`boxed`] (or `Box::pin`).
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::future::{BoxFuture, boxed};
+//!
+//! trait Greeter: Send + Sync {
+//!     fn greet<'a>(&'a self, name: &'a str) -> BoxFuture<'a, String>;
+//! }
+//!
+//! struct English;
+//!
+//! impl Greeter for English {
+//!     fn greet<'a>(&'a self, name: &'a str) -> BoxFuture<'a, String> {
+//!         boxed(async move { format!("Hello, {}!", name) })
+//!     }
+//! }
+//!
+//! let greeters: Vec<Box<dyn Greeter>> = vec![Box::new(English)];
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+/// `Send` on native targets, no bound on `wasm32`
+///
+/// Browser and worker runtimes are single-threaded and their futures hold JS
+/// values, so the crate's traits only require `Send` where it can be satisfied.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// `Send` on native targets, no bound on `wasm32`
+///
+/// Browser and worker runtimes are single-threaded and their futures hold JS
+/// values, so the crate's traits only require `Send` where it can be satisfied.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// `Sync` on native targets, no bound on `wasm32`
+///
+/// The counterpart of [`MaybeSend`] for shared callbacks.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSync: Sync {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// `Sync` on native targets, no bound on `wasm32`
+///
+/// The counterpart of [`MaybeSend`] for shared callbacks.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSync {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// Heap-allocated, `Send` future returned by the crate's async trait methods
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Heap-allocated future returned by the crate's async trait methods
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Box a future into a [`BoxFuture`]
+pub fn boxed<'a, F>(future: F) -> BoxFuture<'a, F::Output>
+where
+    F: Future + MaybeSend + 'a,
+{
+    Box::pin(future)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    trait Doubler: Send + Sync {
+        fn double(&self, value: u32) -> BoxFuture<'_, u32>;
+    }
+
+    struct Simple;
+
+    impl Doubler for Simple {
+        fn double(&self, value: u32) -> BoxFuture<'_, u32> {
+            boxed(async move { value * 2 })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dyn_trait_with_boxed_future() {
+        let doubler: Box<dyn Doubler> = Box::new(Simple);
+        assert_eq!(doubler.double(21).await, 42);
+    }
+}
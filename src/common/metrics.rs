@@ -0,0 +1,142 @@
+//! Request metrics emitted through the [`metrics`](https://docs.rs/metrics) crate.
+//!
+//! Available with the `metrics` cargo feature. Every call made by
+//! [`Messages::post`](crate::messages::request::Messages::post) records:
+//!
+//! - [`REQUESTS_TOTAL`] - Counter of requests, labeled by `model` and `status`
+//! - [`REQUEST_ERRORS_TOTAL`] - Counter of failed requests, labeled by `model` and `error_type`
+//! - [`TOKENS_INPUT`] / [`TOKENS_OUTPUT`] - Counters of consumed tokens, labeled by `model`
+//! - [`REQUEST_DURATION_SECONDS`] - Histogram of request latency, labeled by `model`
+//!
+//! Install any `metrics` recorder (Prometheus exporter, StatsD, ...) in your
+//! application and the values show up without further glue code.
+//!
+//! # Example
+//!
+//! ```rust
+//! // Register descriptions and units once at startup
+//! anthropic_tools::common::metrics::describe();
+//! ```
+
+use crate::common::errors::AnthropicToolError;
+use crate::common::usage::Usage;
+use ::metrics::{Unit, counter, describe_counter, describe_histogram, histogram};
+use std::time::Duration;
+
+/// Counter of requests sent to the API
+pub const REQUESTS_TOTAL: &str = "anthropic_requests_total";
+
+/// Counter of requests that returned an error
+pub const REQUEST_ERRORS_TOTAL: &str = "anthropic_request_errors_total";
+
+/// Counter of input tokens consumed
+pub const TOKENS_INPUT: &str = "anthropic_tokens_input";
+
+/// Counter of output tokens generated
+pub const TOKENS_OUTPUT: &str = "anthropic_tokens_output";
+
+/// Histogram of request latency in seconds
+pub const REQUEST_DURATION_SECONDS: &str = "anthropic_request_duration_seconds";
+
+/// Register descriptions and units for all metrics emitted by this crate
+pub fn describe() {
+    describe_counter!(REQUESTS_TOTAL, "Total number of Anthropic API requests");
+    describe_counter!(
+        REQUEST_ERRORS_TOTAL,
+        "Total number of failed Anthropic API requests"
+    );
+    describe_counter!(TOKENS_INPUT, Unit::Count, "Input tokens consumed");
+    describe_counter!(TOKENS_OUTPUT, Unit::Count, "Output tokens generated");
+    describe_histogram!(
+        REQUEST_DURATION_SECONDS,
+        Unit::Seconds,
+        "Latency of Anthropic API requests"
+    );
+}
+
+/// Record the outcome of a single request
+pub(crate) fn record_request(
+    model: &str,
+    outcome: std::result::Result<&Usage, &AnthropicToolError>,
+    elapsed: Duration,
+) {
+    let model = model.to_string();
+    histogram!(REQUEST_DURATION_SECONDS, "model" => model.clone()).record(elapsed.as_secs_f64());
+
+    match outcome {
+        Ok(usage) => {
+            counter!(REQUESTS_TOTAL, "model" => model.clone(), "status" => "success").increment(1);
+            counter!(TOKENS_INPUT, "model" => model.clone()).increment(usage.input_tokens as u64);
+            counter!(TOKENS_OUTPUT, "model" => model).increment(usage.output_tokens as u64);
+        }
+        Err(error) => {
+            counter!(REQUESTS_TOTAL, "model" => model.clone(), "status" => "error").increment(1);
+            counter!(
+                REQUEST_ERRORS_TOTAL,
+                "model" => model,
+                "error_type" => error.error_type().to_string()
+            )
+            .increment(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    #[test]
+    fn test_record_success() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            record_request(
+                "claude-sonnet-4-20250514",
+                Ok(&Usage::new(100, 50)),
+                Duration::from_millis(250),
+            );
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let find = |name: &str| {
+            snapshot
+                .iter()
+                .find(|(key, _, _, _)| key.key().name() == name)
+                .map(|(_, _, _, value)| value)
+        };
+        assert_eq!(find(REQUESTS_TOTAL), Some(&DebugValue::Counter(1)));
+        assert_eq!(find(TOKENS_INPUT), Some(&DebugValue::Counter(100)));
+        assert_eq!(find(TOKENS_OUTPUT), Some(&DebugValue::Counter(50)));
+        assert!(matches!(
+            find(REQUEST_DURATION_SECONDS),
+            Some(&DebugValue::Histogram(_))
+        ));
+    }
+
+    #[test]
+    fn test_record_error() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let error = AnthropicToolError::RateLimitError("Slow down".to_string());
+            record_request("claude-sonnet-4-20250514", Err(&error), Duration::ZERO);
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let errors = snapshot
+            .iter()
+            .find(|(key, _, _, _)| key.key().name() == REQUEST_ERRORS_TOTAL)
+            .unwrap();
+        assert!(
+            errors
+                .0
+                .key()
+                .labels()
+                .any(|label| label.value() == "rate_limit_error")
+        );
+        assert_eq!(errors.3, DebugValue::Counter(1));
+    }
+}
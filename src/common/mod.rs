@@ -2,9 +2,20 @@
 //!
 //! This module contains shared types used across the library:
 //!
+//! - [`beta`] - Beta feature flags for the `anthropic-beta` header
+//! - [`cassette`] - Record/replay of API traffic for tests
+//! - [`connect`] - Connection targets (Unix sockets, fixed host addresses)
 //! - [`errors`] - Error types and result alias
+//! - [`future`] - Boxed futures used by async extension traits
+//! - [`model`] - Model IDs and aliases
+//! - [`pagination`] - Cursor pagination for list endpoints
+//! - [`pool`] - Connection pool tuning for the HTTP client
+//! - [`runtime`] - Executor-agnostic timers and file reads
+//! - [`server_tool`] - Anthropic-defined tools (web search, ...)
 //! - [`tool`] - Tool definitions for function calling
+//! - [`transport`] - Pluggable HTTP transport
 //! - [`usage`] - Token usage information
+//! - `metrics` - Request metrics via the `metrics` crate (feature `metrics`)
 //!
 //! # Example
 //!
@@ -21,10 +32,27 @@
 //! assert_eq!(usage.total_tokens(), 150);
 //! ```
 
+pub mod beta;
+pub mod cassette;
+pub mod connect;
 pub mod errors;
+pub mod future;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod model;
+pub mod pagination;
+pub mod pool;
+pub mod runtime;
+pub mod server_tool;
 pub mod tool;
+pub mod transport;
 pub mod usage;
 
+pub use beta::Beta;
+pub use connect::ConnectConfig;
 pub use errors::{AnthropicToolError, ErrorDetail, ErrorResponse, Result};
+pub use model::Model;
+pub use pool::PoolConfig;
 pub use tool::{CacheControl, JsonSchema, PropertyDef, Tool};
-pub use usage::Usage;
+pub use transport::{HttpRequest, HttpResponse, HttpTransport};
+pub use usage::{Usage, UsageReport};
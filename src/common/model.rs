@@ -0,0 +1,214 @@
+//! Model identifiers.
+//!
+//! The [`Model`] enum names the Claude models so IDs do not have to be typed
+//! as raw strings:
+//!
+//! - Dated snapshots such as [`Model::ClaudeSonnet4`] always run the same model
+//! - Aliases such as [`Model::SonnetLatest`] move to the newest snapshot of
+//!   their family; resolve them with [`Models::resolve`](crate::models::Models::resolve)
+//! - [`Model::Other`] carries any other ID
+//!
+//! `Model` implements `AsRef<str>`, so it can be passed to every builder that
+//! takes a model name.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::model::Model;
+//! use anthropic_tools::messages::request::Messages;
+//!
+//! let mut client = Messages::new();
+//! client.model(Model::ClaudeSonnet4).max_tokens(1024);
+//!
+//! assert_eq!(Model::SonnetLatest.as_str(), "claude-sonnet-4-5");
+//! let custom: Model = "claude-next-2099-01-01".parse().unwrap();
+//! assert_eq!(custom, Model::Other("claude-next-2099-01-01".to_string()));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// Claude model ID
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum Model {
+    /// Claude Opus 4.1
+    ClaudeOpus4_1,
+    /// Claude Opus 4
+    ClaudeOpus4,
+    /// Claude Sonnet 4.5
+    ClaudeSonnet4_5,
+    /// Claude Sonnet 4
+    ClaudeSonnet4,
+    /// Claude Sonnet 3.7
+    ClaudeSonnet3_7,
+    /// Claude Haiku 4.5
+    ClaudeHaiku4_5,
+    /// Claude Haiku 3.5
+    ClaudeHaiku3_5,
+    /// Claude Haiku 3
+    ClaudeHaiku3,
+    /// Newest Opus snapshot (alias)
+    OpusLatest,
+    /// Newest Sonnet snapshot (alias)
+    SonnetLatest,
+    /// Newest Haiku snapshot (alias)
+    HaikuLatest,
+    /// Any other model ID
+    Other(String),
+}
+
+impl Model {
+    /// Get the model ID sent to the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            Model::ClaudeOpus4_1 => "claude-opus-4-1-20250805",
+            Model::ClaudeOpus4 => "claude-opus-4-20250514",
+            Model::ClaudeSonnet4_5 => "claude-sonnet-4-5-20250929",
+            Model::ClaudeSonnet4 => "claude-sonnet-4-20250514",
+            Model::ClaudeSonnet3_7 => "claude-3-7-sonnet-20250219",
+            Model::ClaudeHaiku4_5 => "claude-haiku-4-5-20251001",
+            Model::ClaudeHaiku3_5 => "claude-3-5-haiku-20241022",
+            Model::ClaudeHaiku3 => "claude-3-haiku-20240307",
+            Model::OpusLatest => "claude-opus-4-1",
+            Model::SonnetLatest => "claude-sonnet-4-5",
+            Model::HaikuLatest => "claude-haiku-4-5",
+            Model::Other(value) => value,
+        }
+    }
+
+    /// Check whether the ID is an alias that moves to newer snapshots
+    pub fn is_alias(&self) -> bool {
+        matches!(
+            self,
+            Model::OpusLatest | Model::SonnetLatest | Model::HaikuLatest
+        )
+    }
+
+    /// Largest `max_tokens` the model accepts, if known
+    ///
+    /// `extended_output` tells whether the `output-128k` beta flag is
+    /// enabled, which raises the limit of Claude Sonnet 3.7 to 128k tokens.
+    pub fn max_output_tokens(&self, extended_output: bool) -> Option<usize> {
+        match self {
+            Model::ClaudeOpus4_1 | Model::ClaudeOpus4 | Model::OpusLatest => Some(32_000),
+            Model::ClaudeSonnet4_5
+            | Model::ClaudeSonnet4
+            | Model::SonnetLatest
+            | Model::ClaudeHaiku4_5
+            | Model::HaikuLatest => Some(64_000),
+            Model::ClaudeSonnet3_7 if extended_output => Some(128_000),
+            Model::ClaudeSonnet3_7 => Some(64_000),
+            Model::ClaudeHaiku3_5 => Some(8_192),
+            Model::ClaudeHaiku3 => Some(4_096),
+            Model::Other(_) => None,
+        }
+    }
+
+    /// All known (non-`Other`) models
+    pub fn known() -> Vec<Model> {
+        vec![
+            Model::ClaudeOpus4_1,
+            Model::ClaudeOpus4,
+            Model::ClaudeSonnet4_5,
+            Model::ClaudeSonnet4,
+            Model::ClaudeSonnet3_7,
+            Model::ClaudeHaiku4_5,
+            Model::ClaudeHaiku3_5,
+            Model::ClaudeHaiku3,
+            Model::OpusLatest,
+            Model::SonnetLatest,
+            Model::HaikuLatest,
+        ]
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for Model {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl FromStr for Model {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(Model::known()
+            .into_iter()
+            .find(|model| model.as_str() == s)
+            .unwrap_or_else(|| Model::Other(s.to_string())))
+    }
+}
+
+impl From<String> for Model {
+    fn from(value: String) -> Self {
+        value.parse().unwrap_or(Model::Other(value))
+    }
+}
+
+impl From<&str> for Model {
+    fn from(value: &str) -> Self {
+        Model::from(value.to_string())
+    }
+}
+
+impl From<Model> for String {
+    fn from(model: Model) -> Self {
+        model.as_str().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_roundtrip() {
+        for model in Model::known() {
+            let parsed: Model = model.as_str().parse().unwrap();
+            assert_eq!(parsed, model);
+        }
+    }
+
+    #[test]
+    fn test_other_and_aliases() {
+        let model = Model::from("claude-next-2099-01-01");
+        assert_eq!(model, Model::Other("claude-next-2099-01-01".to_string()));
+        assert_eq!(model.to_string(), "claude-next-2099-01-01");
+        assert!(!model.is_alias());
+        assert!(Model::HaikuLatest.is_alias());
+        assert_eq!(String::from(Model::OpusLatest), "claude-opus-4-1");
+    }
+
+    #[test]
+    fn test_max_output_tokens() {
+        assert_eq!(Model::ClaudeOpus4.max_output_tokens(false), Some(32_000));
+        assert_eq!(Model::ClaudeHaiku3.max_output_tokens(true), Some(4_096));
+        assert_eq!(
+            Model::ClaudeSonnet3_7.max_output_tokens(false),
+            Some(64_000)
+        );
+        assert_eq!(
+            Model::ClaudeSonnet3_7.max_output_tokens(true),
+            Some(128_000)
+        );
+        assert_eq!(Model::from("claude-next").max_output_tokens(false), None);
+    }
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Model::ClaudeSonnet4).unwrap();
+        assert_eq!(json, "\"claude-sonnet-4-20250514\"");
+        let model: Model = serde_json::from_str(&json).unwrap();
+        assert_eq!(model, Model::ClaudeSonnet4);
+    }
+}
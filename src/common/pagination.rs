@@ -0,0 +1,145 @@
+//! Cursor pagination for list endpoints.
+//!
+//! List endpoints (batches, files, models) return one [`Page`] at a time.
+//! [`ListParams`] selects the page size and the cursor to continue from.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::pagination::ListParams;
+//!
+//! let mut params = ListParams::new();
+//! params.limit(50).after_id("msgbatch_01");
+//! assert_eq!(params.query(), "?limit=50&after_id=msgbatch_01");
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Page size and cursor for a list request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListParams {
+    /// Number of items per page (1 to 1000, API default 20)
+    pub limit: Option<u32>,
+
+    /// Return items before this ID
+    pub before_id: Option<String>,
+
+    /// Return items after this ID
+    pub after_id: Option<String>,
+}
+
+impl ListParams {
+    /// Create parameters for the first page with the default size
+    pub fn new() -> Self {
+        ListParams::default()
+    }
+
+    /// Set the number of items per page
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Return items before this ID
+    pub fn before_id<T: AsRef<str>>(&mut self, id: T) -> &mut Self {
+        self.before_id = Some(id.as_ref().to_string());
+        self
+    }
+
+    /// Return items after this ID
+    pub fn after_id<T: AsRef<str>>(&mut self, id: T) -> &mut Self {
+        self.after_id = Some(id.as_ref().to_string());
+        self
+    }
+
+    /// Render as a URL query string (empty, or starting with `?`)
+    pub fn query(&self) -> String {
+        let mut pairs = Vec::new();
+        if let Some(limit) = self.limit {
+            pairs.push(format!("limit={}", limit));
+        }
+        if let Some(id) = &self.before_id {
+            pairs.push(format!("before_id={}", encode(id)));
+        }
+        if let Some(id) = &self.after_id {
+            pairs.push(format!("after_id={}", encode(id)));
+        }
+        if pairs.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", pairs.join("&"))
+        }
+    }
+}
+
+/// One page of a list response
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Page<T> {
+    /// Items on this page
+    pub data: Vec<T>,
+
+    /// Whether more items follow
+    #[serde(default)]
+    pub has_more: bool,
+
+    /// ID of the first item (cursor for the previous page)
+    #[serde(default)]
+    pub first_id: Option<String>,
+
+    /// ID of the last item (cursor for the next page)
+    #[serde(default)]
+    pub last_id: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Parameters for the next page, if there is one
+    pub fn next_params(&self, limit: Option<u32>) -> Option<ListParams> {
+        if !self.has_more {
+            return None;
+        }
+        let last_id = self.last_id.as_ref()?;
+        let mut params = ListParams::new();
+        params.after_id(last_id);
+        params.limit = limit;
+        Some(params)
+    }
+}
+
+/// Percent-encode a query value (everything but unreserved characters)
+pub(crate) fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        assert_eq!(ListParams::new().query(), "");
+        let mut params = ListParams::new();
+        params.before_id("id with/space");
+        assert_eq!(params.query(), "?before_id=id%20with%2Fspace");
+    }
+
+    #[test]
+    fn test_next_params() {
+        let page: Page<String> = serde_json::from_str(
+            r#"{"data":["a","b"],"has_more":true,"first_id":"a","last_id":"b"}"#,
+        )
+        .unwrap();
+        let next = page.next_params(Some(2)).unwrap();
+        assert_eq!(next.query(), "?limit=2&after_id=b");
+
+        let last: Page<String> = serde_json::from_str(r#"{"data":[],"has_more":false}"#).unwrap();
+        assert!(last.next_params(None).is_none());
+    }
+}
@@ -0,0 +1,145 @@
+//! Connection pool tuning for the HTTP client.
+//!
+//! [`PoolConfig`] exposes the connection reuse knobs of the underlying HTTP
+//! client so high-throughput services can tune them without supplying a
+//! custom client:
+//!
+//! - `pool_max_idle_per_host` - Idle connections kept open per host
+//! - `pool_idle_timeout` - How long an idle connection is kept
+//! - `http2_keep_alive_*` - HTTP/2 PING frames that keep connections warm
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::pool::PoolConfig;
+//! use std::time::Duration;
+//!
+//! let mut pool = PoolConfig::default();
+//! pool.max_idle_per_host(32)
+//!     .idle_timeout(Some(Duration::from_secs(300)))
+//!     .http2_keep_alive_interval(Duration::from_secs(30))
+//!     .http2_keep_alive_while_idle(true);
+//!
+//! let client = pool.build_client().unwrap();
+//! ```
+
+use crate::common::errors::Result;
+use std::time::Duration;
+
+/// Connection pool and keep-alive settings
+///
+/// Unset fields keep the HTTP client defaults (unlimited idle connections per
+/// host, 90 second idle timeout, HTTP/2 keep-alive disabled).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Maximum idle connections kept per host
+    pub max_idle_per_host: Option<usize>,
+
+    /// Idle connection timeout (`Some(None)` disables the timeout)
+    pub idle_timeout: Option<Option<Duration>>,
+
+    /// Interval between HTTP/2 keep-alive pings
+    pub http2_keep_alive_interval: Option<Duration>,
+
+    /// Time to wait for a keep-alive ping acknowledgement
+    pub http2_keep_alive_timeout: Option<Duration>,
+
+    /// Whether keep-alive pings are sent on idle connections
+    pub http2_keep_alive_while_idle: bool,
+}
+
+impl PoolConfig {
+    /// Set the maximum number of idle connections kept per host
+    pub fn max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the idle connection timeout (`None` keeps idle connections forever)
+    pub fn idle_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Send HTTP/2 keep-alive pings at the given interval
+    pub fn http2_keep_alive_interval(&mut self, interval: Duration) -> &mut Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Close the connection if a keep-alive ping is not acknowledged in time
+    pub fn http2_keep_alive_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.http2_keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Also send keep-alive pings while no requests are in flight
+    pub fn http2_keep_alive_while_idle(&mut self, enabled: bool) -> &mut Self {
+        self.http2_keep_alive_while_idle = enabled;
+        self
+    }
+
+    /// Apply the settings to an HTTP client builder
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn apply(&self, mut builder: request::ClientBuilder) -> request::ClientBuilder {
+        if let Some(max) = self.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder = builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder = builder.http2_keep_alive_timeout(timeout);
+        }
+        builder.http2_keep_alive_while_idle(self.http2_keep_alive_while_idle)
+    }
+
+    /// Apply the settings to an HTTP client builder
+    ///
+    /// The browser's `fetch` manages connections itself, so this is a no-op on `wasm32`.
+    #[cfg(target_arch = "wasm32")]
+    pub fn apply(&self, builder: request::ClientBuilder) -> request::ClientBuilder {
+        builder
+    }
+
+    /// Build an HTTP client with these settings
+    pub fn build_client(&self) -> Result<request::Client> {
+        Ok(self.apply(request::Client::builder()).build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let mut pool = PoolConfig::default();
+        pool.max_idle_per_host(8)
+            .idle_timeout(None)
+            .http2_keep_alive_interval(Duration::from_secs(20))
+            .http2_keep_alive_timeout(Duration::from_secs(5))
+            .http2_keep_alive_while_idle(true);
+
+        assert_eq!(pool.max_idle_per_host, Some(8));
+        assert_eq!(pool.idle_timeout, Some(None));
+        assert_eq!(
+            pool.http2_keep_alive_interval,
+            Some(Duration::from_secs(20))
+        );
+        assert!(pool.http2_keep_alive_while_idle);
+    }
+
+    #[test]
+    fn test_build_client() {
+        assert!(PoolConfig::default().build_client().is_ok());
+
+        let mut pool = PoolConfig::default();
+        pool.max_idle_per_host(0)
+            .http2_keep_alive_interval(Duration::from_secs(10));
+        assert!(pool.build_client().is_ok());
+    }
+}
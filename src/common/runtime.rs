@@ -0,0 +1,106 @@
+//! Executor-agnostic async helpers.
+//!
+//! The client only awaits futures and never spawns tasks, so it runs on any
+//! executor (tokio, async-std, smol, or a browser event loop). Timers and
+//! file reads are the runtime-specific pieces, and are isolated here:
+//!
+//! - With the `rt-tokio` feature (default), [`sleep`] uses `tokio::time` and
+//!   [`read_file`] uses `tokio::fs`
+//! - Without it, [`sleep`] uses `futures-timer` (a background timer thread on
+//!   native targets, `setTimeout` on `wasm32`) and [`read_file`] reads the
+//!   file synchronously
+//!
+//! The default [`ReqwestTransport`](crate::common::transport::ReqwestTransport)
+//! is built on hyper and needs a tokio reactor on native targets. On other
+//! executors, plug in an [`HttpTransport`](crate::common::transport::HttpTransport)
+//! backed by that runtime's HTTP client.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::runtime;
+//! use std::time::Duration;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let slow = runtime::sleep(Duration::from_secs(60));
+//! assert!(runtime::timeout(Duration::from_millis(10), slow).await.is_none());
+//! # }
+//! ```
+
+use futures::future::{Either, select};
+use std::future::Future;
+use std::path::Path;
+use std::pin::pin;
+use std::time::Duration;
+
+/// Wait for the given duration
+#[cfg(all(feature = "rt-tokio", not(target_arch = "wasm32")))]
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Wait for the given duration
+#[cfg(not(all(feature = "rt-tokio", not(target_arch = "wasm32"))))]
+pub async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await;
+}
+
+/// Read a whole file
+#[cfg(all(feature = "rt-tokio", not(target_arch = "wasm32")))]
+pub async fn read_file<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<u8>> {
+    tokio::fs::read(path).await
+}
+
+/// Read a whole file
+#[cfg(not(all(feature = "rt-tokio", not(target_arch = "wasm32"))))]
+pub async fn read_file<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<u8>> {
+    std::fs::read(path)
+}
+
+/// Run a future with a time limit, returning `None` if it did not finish in time
+pub async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    match select(pin!(future), pin!(sleep(duration))).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timeout_completes() {
+        let result = timeout(Duration::from_secs(5), async { 42 }).await;
+        assert_eq!(result, Some(42));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_elapses() {
+        let result = timeout(Duration::from_millis(10), sleep(Duration::from_secs(60))).await;
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_file() {
+        let path = std::env::temp_dir().join(format!(
+            "anthropic-tools-runtime-read-{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"hello").unwrap();
+        assert_eq!(read_file(&path).await.unwrap(), b"hello");
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_file(&path).await.is_err());
+    }
+
+    #[cfg(not(feature = "rt-tokio"))]
+    #[test]
+    fn test_sleep_without_tokio() {
+        let result = futures::executor::block_on(timeout(
+            Duration::from_millis(10),
+            sleep(Duration::from_secs(60)),
+        ));
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,1009 @@
+//! Anthropic-defined tools.
+//!
+//! Unlike [`Tool`](super::tool::Tool), these tools have no input schema: the
+//! API knows them by their versioned `type`, and the model is trained to use
+//! them. Server tools run on Anthropic's side and their results arrive in the
+//! response; client tools (like the computer) arrive as `tool_use` blocks and
+//! are executed by the application.
+//!
+//! - [`WebSearchTool`] - Hosted web search (`web_search_20250305`)
+//! - [`UserLocation`] - Approximate location to localize search results
+//! - [`WebSearchResult`] - One search result in a `web_search_tool_result` block
+//! - [`WebSearchToolResultError`] - Error returned instead of search results
+//! - [`WebSearchResultLocation`] - Citation pointing at a search result
+//! - [`ComputerTool`] - Screen, mouse, and keyboard control (`computer_20250124`)
+//! - [`ComputerAction`] - Action requested in a computer `tool_use` block
+//! - [`BashTool`] - Persistent bash session (`bash_20250124`)
+//! - [`BashInput`] - Input of a bash `tool_use` block
+//! - [`TextEditorTool`] - File viewing and editing (`text_editor_20250728`)
+//! - [`TextEditorCommand`] - Command in a text editor `tool_use` block
+//! - [`CodeExecutionTool`] - Sandboxed Python execution (`code_execution_20250522`)
+//! - [`CodeExecutionResult`] - Output of one code execution
+//! - [`CodeExecutionToolResultError`] - Error returned instead of an output
+//! - [`Container`] - Code execution container, reusable across requests
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::server_tool::{UserLocation, WebSearchTool};
+//! use anthropic_tools::prelude::*;
+//!
+//! let mut location = UserLocation::new();
+//! location.city("Tokyo").country("JP").timezone("Asia/Tokyo");
+//!
+//! let mut search = WebSearchTool::new();
+//! search
+//!     .max_uses(3)
+//!     .allowed_domains(vec!["jma.go.jp", "weather.com"])
+//!     .user_location(location);
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .web_search(search)
+//!     .user("Will it rain in Tokyo tomorrow?");
+//! ```
+//!
+//! # Code Execution
+//!
+//! [`Messages::code_execution`](crate::messages::request::Messages::code_execution)
+//! enables the `code-execution` beta flag along with the tool. The code runs
+//! in a container whose ID comes back in
+//! [`Response::container`](crate::messages::response::Response::container);
+//! pass it to the next request to keep files and state:
+//!
+//! ```rust,no_run
+//! use anthropic_tools::common::server_tool::CodeExecutionTool;
+//! use anthropic_tools::prelude::*;
+//!
+//! # async fn example() -> Result<()> {
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(4096)
+//!     .code_execution(CodeExecutionTool::new())
+//!     .user("Compute the first 20 Fibonacci numbers and save them to fib.csv");
+//!
+//! let response = client.post().await?;
+//! client
+//!     .reuse_container(&response)
+//!     .assistant(response.get_text())
+//!     .user("Now plot fib.csv");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Computer Use
+//!
+//! [`Messages::computer`](crate::messages::request::Messages::computer)
+//! enables the `computer-use` beta flag along with the tool. Each `tool_use`
+//! block of the response is one [`ComputerAction`]:
+//!
+//! ```rust,no_run
+//! use anthropic_tools::common::server_tool::{ComputerAction, ComputerTool};
+//! use anthropic_tools::prelude::*;
+//!
+//! # async fn example() -> Result<()> {
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .computer(ComputerTool::new(1280, 800))
+//!     .user("Open the settings window");
+//!
+//! let response = client.post().await?;
+//! for (id, action) in response.get_computer_actions()? {
+//!     match action {
+//!         ComputerAction::Screenshot => println!("{}: take a screenshot", id),
+//!         ComputerAction::LeftClick { coordinate, .. } => println!("{}: click {:?}", id, coordinate),
+//!         other => println!("{}: {:?}", id, other),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::tool::CacheControl;
+use serde::{Deserialize, Serialize};
+
+/// Type of the web search tool
+pub const WEB_SEARCH_TYPE: &str = "web_search_20250305";
+
+/// Name of the web search tool
+pub const WEB_SEARCH_NAME: &str = "web_search";
+
+/// Type of the computer use tool
+pub const COMPUTER_TYPE: &str = "computer_20250124";
+
+/// Name of the computer use tool
+pub const COMPUTER_NAME: &str = "computer";
+
+/// Type of the bash tool
+pub const BASH_TYPE: &str = "bash_20250124";
+
+/// Name of the bash tool
+pub const BASH_NAME: &str = "bash";
+
+/// Type of the text editor tool
+pub const TEXT_EDITOR_TYPE: &str = "text_editor_20250728";
+
+/// Name of the text editor tool
+pub const TEXT_EDITOR_NAME: &str = "str_replace_based_edit_tool";
+
+/// Type of the code execution tool
+pub const CODE_EXECUTION_TYPE: &str = "code_execution_20250522";
+
+/// Name of the code execution tool
+pub const CODE_EXECUTION_NAME: &str = "code_execution";
+
+/// Hosted web search tool
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebSearchTool {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    pub name: String,
+
+    /// Maximum number of searches per request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+
+    /// Only include results from these domains
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_domains: Option<Vec<String>>,
+
+    /// Never include results from these domains
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_domains: Option<Vec<String>>,
+
+    /// Location used to localize results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<UserLocation>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for WebSearchTool {
+    fn default() -> Self {
+        WebSearchTool {
+            type_name: WEB_SEARCH_TYPE.to_string(),
+            name: WEB_SEARCH_NAME.to_string(),
+            max_uses: None,
+            allowed_domains: None,
+            blocked_domains: None,
+            user_location: None,
+            cache_control: None,
+        }
+    }
+}
+
+impl WebSearchTool {
+    /// Create a web search tool without restrictions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of searches per request
+    pub fn max_uses(&mut self, max_uses: u32) -> &mut Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Only include results from these domains
+    pub fn allowed_domains<S: AsRef<str>>(&mut self, domains: Vec<S>) -> &mut Self {
+        self.allowed_domains = Some(domains.iter().map(|d| d.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Never include results from these domains
+    pub fn blocked_domains<S: AsRef<str>>(&mut self, domains: Vec<S>) -> &mut Self {
+        self.blocked_domains = Some(domains.iter().map(|d| d.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Set the location used to localize results
+    pub fn user_location(&mut self, location: UserLocation) -> &mut Self {
+        self.user_location = Some(location);
+        self
+    }
+
+    /// Enable prompt caching
+    pub fn with_cache(&mut self) -> &mut Self {
+        self.cache_control = Some(CacheControl::ephemeral());
+        self
+    }
+
+    /// Check the options against the API's rules
+    ///
+    /// `allowed_domains` and `blocked_domains` cannot be combined, and
+    /// `max_uses` must be at least 1.
+    pub fn validate(&self) -> Result<()> {
+        if self.allowed_domains.is_some() && self.blocked_domains.is_some() {
+            return Err(AnthropicToolError::InvalidParameter(
+                "web_search: allowed_domains and blocked_domains cannot be combined".to_string(),
+            ));
+        }
+        if self.max_uses == Some(0) {
+            return Err(AnthropicToolError::InvalidParameter(
+                "web_search: max_uses must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Convert to serde_json::Value
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+/// Approximate user location for localized search results
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UserLocation {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// ISO 3166-1 alpha-2 country code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+
+    /// IANA time zone (e.g. `America/New_York`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl Default for UserLocation {
+    fn default() -> Self {
+        UserLocation {
+            type_name: "approximate".to_string(),
+            city: None,
+            region: None,
+            country: None,
+            timezone: None,
+        }
+    }
+}
+
+impl UserLocation {
+    /// Create an empty approximate location
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the city
+    pub fn city<S: AsRef<str>>(&mut self, city: S) -> &mut Self {
+        self.city = Some(city.as_ref().to_string());
+        self
+    }
+
+    /// Set the region (state, prefecture, ...)
+    pub fn region<S: AsRef<str>>(&mut self, region: S) -> &mut Self {
+        self.region = Some(region.as_ref().to_string());
+        self
+    }
+
+    /// Set the ISO 3166-1 alpha-2 country code
+    pub fn country<S: AsRef<str>>(&mut self, country: S) -> &mut Self {
+        self.country = Some(country.as_ref().to_string());
+        self
+    }
+
+    /// Set the IANA time zone
+    pub fn timezone<S: AsRef<str>>(&mut self, timezone: S) -> &mut Self {
+        self.timezone = Some(timezone.as_ref().to_string());
+        self
+    }
+}
+
+/// One result of a web search
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebSearchResult {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    pub url: String,
+
+    pub title: String,
+
+    /// Page content, to be passed back verbatim in multi-turn conversations
+    pub encrypted_content: String,
+
+    /// When the page was last updated, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_age: Option<String>,
+}
+
+/// Content of a `web_search_tool_result` block: results or an error
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum WebSearchToolResultContent {
+    Results(Vec<WebSearchResult>),
+    Error(WebSearchToolResultError),
+}
+
+impl WebSearchToolResultContent {
+    /// Get the results, or an empty slice for an error
+    pub fn results(&self) -> &[WebSearchResult] {
+        match self {
+            WebSearchToolResultContent::Results(results) => results,
+            WebSearchToolResultContent::Error(_) => &[],
+        }
+    }
+}
+
+/// Error returned by the web search tool instead of results
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebSearchToolResultError {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// `too_many_requests`, `invalid_input`, `max_uses_exceeded`,
+    /// `query_too_long`, or `unavailable`
+    pub error_code: String,
+}
+
+/// Citation of a web search result in a text block
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebSearchResultLocation {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    pub url: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Reference to the cited passage, to be passed back verbatim
+    pub encrypted_index: String,
+
+    /// Cited text (up to 150 characters)
+    pub cited_text: String,
+}
+
+/// Computer use tool
+///
+/// Requires the [`Beta::ComputerUse`](crate::common::beta::Beta::ComputerUse)
+/// flag, which [`Messages::computer`](crate::messages::request::Messages::computer)
+/// enables. Coordinates in the actions are pixels of a display of this size,
+/// so scale screenshots to it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ComputerTool {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    pub name: String,
+
+    /// Display width in pixels
+    pub display_width_px: u32,
+
+    /// Display height in pixels
+    pub display_height_px: u32,
+
+    /// X11 display number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_number: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl ComputerTool {
+    /// Create a computer tool for a display of the given size
+    pub fn new(display_width_px: u32, display_height_px: u32) -> Self {
+        ComputerTool {
+            type_name: COMPUTER_TYPE.to_string(),
+            name: COMPUTER_NAME.to_string(),
+            display_width_px,
+            display_height_px,
+            display_number: None,
+            cache_control: None,
+        }
+    }
+
+    /// Set the X11 display number
+    pub fn display_number(&mut self, display_number: u32) -> &mut Self {
+        self.display_number = Some(display_number);
+        self
+    }
+
+    /// Enable prompt caching
+    pub fn with_cache(&mut self) -> &mut Self {
+        self.cache_control = Some(CacheControl::ephemeral());
+        self
+    }
+
+    /// Convert to serde_json::Value
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+/// Screen position `[x, y]` in pixels
+pub type Coordinate = [u32; 2];
+
+/// Scroll direction of [`ComputerAction::Scroll`]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Action in the input of a computer `tool_use` block
+///
+/// Click and scroll actions may carry `text` with modifier keys to hold
+/// (e.g. `shift`, `ctrl`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ComputerAction {
+    /// Press a key or key combination (xdotool syntax, e.g. `ctrl+s`)
+    Key { text: String },
+
+    /// Hold a key for `duration` seconds
+    HoldKey { text: String, duration: f64 },
+
+    /// Type a string
+    Type { text: String },
+
+    /// Report the cursor position
+    CursorPosition,
+
+    /// Move the cursor
+    MouseMove { coordinate: Coordinate },
+
+    /// Press the left button without releasing it
+    LeftMouseDown,
+
+    /// Release the left button
+    LeftMouseUp,
+
+    /// Left click, at the cursor when `coordinate` is missing
+    LeftClick {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        coordinate: Option<Coordinate>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+
+    /// Drag with the left button from `start_coordinate` to `coordinate`
+    LeftClickDrag {
+        start_coordinate: Coordinate,
+        coordinate: Coordinate,
+    },
+
+    /// Right click
+    RightClick {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        coordinate: Option<Coordinate>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+
+    /// Middle click
+    MiddleClick {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        coordinate: Option<Coordinate>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+
+    /// Double click
+    DoubleClick {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        coordinate: Option<Coordinate>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+
+    /// Triple click
+    TripleClick {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        coordinate: Option<Coordinate>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+
+    /// Scroll `scroll_amount` wheel clicks
+    Scroll {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        coordinate: Option<Coordinate>,
+        scroll_direction: ScrollDirection,
+        scroll_amount: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+
+    /// Wait for `duration` seconds
+    Wait { duration: f64 },
+
+    /// Take a screenshot
+    Screenshot,
+}
+
+impl ComputerAction {
+    /// Whether the application should answer with a fresh screenshot
+    ///
+    /// True for the screenshot action itself; the other actions can be
+    /// answered with text or a screenshot, as the application prefers.
+    pub fn is_screenshot(&self) -> bool {
+        matches!(self, ComputerAction::Screenshot)
+    }
+}
+
+/// Bash tool
+///
+/// The commands are run by the application; with the `tool-bash` feature,
+/// [`BashExecutor`](crate::tools::builtin::bash::BashExecutor) runs them
+/// locally.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BashTool {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for BashTool {
+    fn default() -> Self {
+        BashTool {
+            type_name: BASH_TYPE.to_string(),
+            name: BASH_NAME.to_string(),
+            cache_control: None,
+        }
+    }
+}
+
+impl BashTool {
+    /// Create a bash tool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable prompt caching
+    pub fn with_cache(&mut self) -> &mut Self {
+        self.cache_control = Some(CacheControl::ephemeral());
+        self
+    }
+
+    /// Convert to serde_json::Value
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+/// Input of a bash `tool_use` block: a command to run, or a restart request
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BashInput {
+    /// Command to run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Restart the session instead of running a command
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub restart: bool,
+}
+
+/// Text editor tool
+///
+/// The commands are run by the application; with the `tool-text-editor`
+/// feature,
+/// [`TextEditorExecutor`](crate::tools::builtin::text_editor::TextEditorExecutor)
+/// runs them on a directory.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TextEditorTool {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    pub name: String,
+
+    /// Truncate file contents returned by `view` to this many characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_characters: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for TextEditorTool {
+    fn default() -> Self {
+        TextEditorTool {
+            type_name: TEXT_EDITOR_TYPE.to_string(),
+            name: TEXT_EDITOR_NAME.to_string(),
+            max_characters: None,
+            cache_control: None,
+        }
+    }
+}
+
+impl TextEditorTool {
+    /// Create a text editor tool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of characters after which viewed files are truncated
+    pub fn max_characters(&mut self, max_characters: u32) -> &mut Self {
+        self.max_characters = Some(max_characters);
+        self
+    }
+
+    /// Enable prompt caching
+    pub fn with_cache(&mut self) -> &mut Self {
+        self.cache_control = Some(CacheControl::ephemeral());
+        self
+    }
+
+    /// Convert to serde_json::Value
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+/// Command in the input of a text editor `tool_use` block
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum TextEditorCommand {
+    /// Show a file with line numbers, or list a directory
+    View {
+        path: String,
+        /// First and last line to show (1-based, `-1` for the end of the file)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        view_range: Option<[i64; 2]>,
+    },
+
+    /// Replace the only occurrence of `old_str` with `new_str`
+    StrReplace {
+        path: String,
+        old_str: String,
+        #[serde(default)]
+        new_str: String,
+    },
+
+    /// Create (or overwrite) a file
+    Create { path: String, file_text: String },
+
+    /// Insert text after line `insert_line` (0 for the start of the file)
+    Insert {
+        path: String,
+        insert_line: usize,
+        #[serde(alias = "new_str")]
+        insert_text: String,
+    },
+}
+
+impl TextEditorCommand {
+    /// Path the command operates on
+    pub fn path(&self) -> &str {
+        match self {
+            TextEditorCommand::View { path, .. }
+            | TextEditorCommand::StrReplace { path, .. }
+            | TextEditorCommand::Create { path, .. }
+            | TextEditorCommand::Insert { path, .. } => path,
+        }
+    }
+}
+
+/// Code execution tool
+///
+/// Requires the [`Beta::CodeExecution`](crate::common::beta::Beta::CodeExecution)
+/// flag, which
+/// [`Messages::code_execution`](crate::messages::request::Messages::code_execution)
+/// enables.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CodeExecutionTool {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for CodeExecutionTool {
+    fn default() -> Self {
+        CodeExecutionTool {
+            type_name: CODE_EXECUTION_TYPE.to_string(),
+            name: CODE_EXECUTION_NAME.to_string(),
+            cache_control: None,
+        }
+    }
+}
+
+impl CodeExecutionTool {
+    /// Create a code execution tool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable prompt caching
+    pub fn with_cache(&mut self) -> &mut Self {
+        self.cache_control = Some(CacheControl::ephemeral());
+        self
+    }
+
+    /// Convert to serde_json::Value
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
+
+/// Output of one code execution
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CodeExecutionResult {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    #[serde(default)]
+    pub stdout: String,
+
+    #[serde(default)]
+    pub stderr: String,
+
+    pub return_code: i32,
+
+    /// Files created by the code
+    #[serde(default)]
+    pub content: Vec<CodeExecutionOutput>,
+}
+
+impl CodeExecutionResult {
+    /// Check whether the code exited with status 0
+    pub fn succeeded(&self) -> bool {
+        self.return_code == 0
+    }
+
+    /// IDs of the files created by the code, for the Files API
+    pub fn file_ids(&self) -> Vec<&str> {
+        self.content
+            .iter()
+            .map(|output| output.file_id.as_str())
+            .collect()
+    }
+}
+
+/// Content of a `code_execution_tool_result` block: an output or an error
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CodeExecutionToolResultContent {
+    Result(CodeExecutionResult),
+    Error(CodeExecutionToolResultError),
+}
+
+/// File created by a code execution
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CodeExecutionOutput {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// ID for the Files API
+    pub file_id: String,
+}
+
+/// Error returned by the code execution tool instead of an output
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CodeExecutionToolResultError {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// `unavailable`, `code_execution_exceeded`, `container_expired`, or
+    /// `too_many_requests`
+    pub error_code: String,
+}
+
+/// Container the code of a request ran in
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Container {
+    /// ID to pass as `container` to reuse the container
+    pub id: String,
+
+    /// When the container expires (RFC 3339)
+    pub expires_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_web_search_tool() {
+        let mut location = UserLocation::new();
+        location.city("San Francisco").country("US");
+        let mut tool = WebSearchTool::new();
+        tool.max_uses(5)
+            .blocked_domains(vec!["example.com"])
+            .user_location(location);
+        assert!(tool.validate().is_ok());
+
+        let json = tool.to_value();
+        assert_eq!(json["type"], "web_search_20250305");
+        assert_eq!(json["name"], "web_search");
+        assert_eq!(json["max_uses"], 5);
+        assert_eq!(json["blocked_domains"][0], "example.com");
+        assert_eq!(json["user_location"]["type"], "approximate");
+        assert_eq!(json["user_location"]["city"], "San Francisco");
+        assert!(json.get("allowed_domains").is_none());
+
+        tool.allowed_domains(vec!["rust-lang.org"]);
+        assert!(tool.validate().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_web_search_result() {
+        let result: WebSearchResult = serde_json::from_value(serde_json::json!({
+            "type": "web_search_result",
+            "url": "https://www.rust-lang.org",
+            "title": "Rust",
+            "encrypted_content": "EqgfCioIARgBIiQ3YTAwMjY1Mi1mZjM5LTQ1NGUtODgxNC1kNjNjNTk1ZWI3Y2YSDAdRK",
+            "page_age": "April 30, 2025"
+        }))
+        .unwrap();
+        assert_eq!(result.title, "Rust");
+        assert_eq!(result.page_age.as_deref(), Some("April 30, 2025"));
+
+        let citation: WebSearchResultLocation = serde_json::from_value(serde_json::json!({
+            "type": "web_search_result_location",
+            "url": "https://www.rust-lang.org",
+            "title": "Rust",
+            "encrypted_index": "Eo8BCioIAhgBIiQyYjQ0OWJmZi1lNm",
+            "cited_text": "A language empowering everyone"
+        }))
+        .unwrap();
+        assert_eq!(citation.cited_text, "A language empowering everyone");
+    }
+
+    #[test]
+    fn test_computer_tool() {
+        let mut tool = ComputerTool::new(1024, 768);
+        tool.display_number(1);
+        let json = tool.to_value();
+        assert_eq!(json["type"], "computer_20250124");
+        assert_eq!(json["name"], "computer");
+        assert_eq!(json["display_width_px"], 1024);
+        assert_eq!(json["display_height_px"], 768);
+        assert_eq!(json["display_number"], 1);
+        assert!(json.get("cache_control").is_none());
+    }
+
+    #[test]
+    fn test_deserialize_computer_action() {
+        let parse =
+            |value| serde_json::from_value::<ComputerAction>(value).expect("valid computer action");
+        assert_eq!(
+            parse(serde_json::json!({"action": "screenshot"})),
+            ComputerAction::Screenshot
+        );
+        assert_eq!(
+            parse(serde_json::json!({"action": "left_click", "coordinate": [120, 45]})),
+            ComputerAction::LeftClick {
+                coordinate: Some([120, 45]),
+                text: None
+            }
+        );
+        assert_eq!(
+            parse(serde_json::json!({
+                "action": "scroll",
+                "coordinate": [500, 400],
+                "scroll_direction": "down",
+                "scroll_amount": 3
+            })),
+            ComputerAction::Scroll {
+                coordinate: Some([500, 400]),
+                scroll_direction: ScrollDirection::Down,
+                scroll_amount: 3,
+                text: None
+            }
+        );
+        assert_eq!(
+            parse(serde_json::json!({"action": "key", "text": "ctrl+s"})),
+            ComputerAction::Key {
+                text: "ctrl+s".to_string()
+            }
+        );
+        assert!(
+            serde_json::from_value::<ComputerAction>(serde_json::json!({"action": "fly"})).is_err()
+        );
+
+        let json = serde_json::to_value(ComputerAction::Wait { duration: 1.5 }).unwrap();
+        assert_eq!(json, serde_json::json!({"action": "wait", "duration": 1.5}));
+    }
+
+    #[test]
+    fn test_bash_tool() {
+        assert_eq!(
+            BashTool::new().to_value(),
+            serde_json::json!({"type": "bash_20250124", "name": "bash"})
+        );
+
+        let input: BashInput =
+            serde_json::from_value(serde_json::json!({"command": "ls -la"})).unwrap();
+        assert_eq!(input.command.as_deref(), Some("ls -la"));
+        assert!(!input.restart);
+        let input: BashInput =
+            serde_json::from_value(serde_json::json!({"restart": true})).unwrap();
+        assert!(input.restart && input.command.is_none());
+    }
+
+    #[test]
+    fn test_code_execution_types() {
+        assert_eq!(
+            CodeExecutionTool::new().to_value(),
+            serde_json::json!({"type": "code_execution_20250522", "name": "code_execution"})
+        );
+
+        let result: CodeExecutionResult = serde_json::from_value(serde_json::json!({
+            "type": "code_execution_result",
+            "stdout": "saved\n",
+            "stderr": "",
+            "return_code": 0,
+            "content": [{"type": "code_execution_output", "file_id": "file_011"}]
+        }))
+        .unwrap();
+        assert!(result.succeeded());
+        assert_eq!(result.file_ids(), vec!["file_011"]);
+
+        let container: Container = serde_json::from_value(serde_json::json!({
+            "id": "container_011",
+            "expires_at": "2025-05-23T21:13:31.749448Z"
+        }))
+        .unwrap();
+        assert_eq!(container.id, "container_011");
+    }
+
+    #[test]
+    fn test_text_editor_tool() {
+        let mut tool = TextEditorTool::new();
+        tool.max_characters(10000);
+        assert_eq!(
+            tool.to_value(),
+            serde_json::json!({
+                "type": "text_editor_20250728",
+                "name": "str_replace_based_edit_tool",
+                "max_characters": 10000
+            })
+        );
+
+        let command: TextEditorCommand = serde_json::from_value(serde_json::json!({
+            "command": "view",
+            "path": "src/main.rs",
+            "view_range": [1, -1]
+        }))
+        .unwrap();
+        assert_eq!(
+            command,
+            TextEditorCommand::View {
+                path: "src/main.rs".to_string(),
+                view_range: Some([1, -1])
+            }
+        );
+        let command: TextEditorCommand = serde_json::from_value(serde_json::json!({
+            "command": "insert",
+            "path": "notes.txt",
+            "insert_line": 2,
+            "insert_text": "new line"
+        }))
+        .unwrap();
+        assert_eq!(command.path(), "notes.txt");
+        assert!(
+            serde_json::from_value::<TextEditorCommand>(serde_json::json!({
+                "command": "undo_edit",
+                "path": "notes.txt"
+            }))
+            .is_err()
+        );
+    }
+}
@@ -5,7 +5,16 @@
 //! - [`Tool`] - Main tool definition with name, description, and input schema
 //! - [`JsonSchema`] - JSON Schema for tool input parameters
 //! - [`PropertyDef`] - Property definitions within a schema
+//! - [`ObjectBuilder`] - Properties of a nested object, built in a closure
 //! - [`CacheControl`] - Cache control for prompt caching
+//! - [`ToolDefinition`] - Entry of the request's `tools` array (custom,
+//!   Anthropic-defined, or MCP toolset)
+//! - [`ToolInput`] / [`ToolInputType`] - Schemas generated from Rust types
+//! - [`parse_input`] - Typed access to the input of a `tool_use` block
+//!
+//! Tool definitions can be kept in data files shared with other SDKs:
+//! [`Tool::from_json_file`] and [`Tool::to_json_file`] (plus
+//! `from_yaml_file`/`to_yaml_file` with the `yaml` feature).
 //!
 //! # Example
 //!
@@ -22,6 +31,70 @@
 //! let json = tool.to_value();
 //! ```
 //!
+//! # Nested Objects
+//!
+//! Object properties, and arrays of objects, are built in a closure instead
+//! of assembling a `HashMap<String, PropertyDef>` by hand:
+//!
+//! ```rust
+//! use anthropic_tools::common::tool::Tool;
+//!
+//! let mut tool = Tool::new("create_order");
+//! tool.add_object_property("customer", Some("Who ordered"), true, |customer| {
+//!     customer
+//!         .string("name", Some("Full name"), true)
+//!         .string("email", None, false);
+//! })
+//! .add_array_of_objects_property("items", Some("Ordered items"), true, |item| {
+//!     item.string("sku", Some("Product code"), true)
+//!         .integer("quantity", None, true);
+//! });
+//!
+//! let json = tool.to_value();
+//! assert_eq!(json["input_schema"]["properties"]["items"]["items"]["required"][0], "sku");
+//! ```
+//!
+//! # Deterministic Serialization
+//!
+//! Schema properties are serialized in sorted key order, so the same tool
+//! always produces byte-identical JSON. This keeps request bodies stable for
+//! prompt caching, fingerprinting, and golden tests.
+//!
+//! # Schemas from Rust Types
+//!
+//! With the `derive` feature, `#[derive(ToolInput)]` generates the input
+//! schema from a struct: fields become properties, doc comments become
+//! descriptions, `Option<T>` fields are optional, and unit enums become
+//! `enum` values. Serde `rename`, `rename_all`, `default`, and `skip`
+//! attributes are honored.
+//!
+//! ```rust,ignore
+//! use anthropic_tools::common::tool::{Tool, ToolInput};
+//! use serde::Deserialize;
+//!
+//! /// Temperature unit
+//! #[derive(ToolInput, Deserialize)]
+//! #[serde(rename_all = "lowercase")]
+//! enum Unit {
+//!     Celsius,
+//!     Fahrenheit,
+//! }
+//!
+//! /// Get the current weather for a location
+//! #[derive(ToolInput, Deserialize)]
+//! struct GetWeather {
+//!     /// City and state, e.g. San Francisco, CA
+//!     location: String,
+//!     unit: Option<Unit>,
+//! }
+//!
+//! let tool = Tool::from_input::<GetWeather>("get_weather");
+//! ```
+//!
+//! With the `schemars` feature, [`Tool::from_schema`] builds the schema of
+//! any `schemars::JsonSchema` type instead, keeping keywords such as
+//! `format`, `minimum`, or `anyOf` that the builder has no fields for.
+//!
 //! # With Prompt Caching
 //!
 //! ```rust
@@ -32,8 +105,18 @@
 //!     .with_cache();  // Enable prompt caching
 //! ```
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::common::errors::AnthropicToolError;
+use crate::common::server_tool::{
+    BASH_TYPE, BashTool, CODE_EXECUTION_TYPE, COMPUTER_TYPE, CodeExecutionTool, ComputerTool,
+    TEXT_EDITOR_TYPE, TextEditorTool, WEB_SEARCH_TYPE, WebSearchTool,
+};
+use crate::messages::request::mcp::{MCP_TOOLSET_TYPE, McpToolset};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+#[cfg(feature = "derive")]
+pub use anthropic_tools_derive::ToolInput;
 
 /// Tool definition for the Anthropic API
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -54,18 +137,44 @@ pub struct Tool {
 }
 
 /// Cache control for prompt caching
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// Shared by tools, system blocks, and content blocks (re-exported from
+/// [`content`](crate::messages::request::content)).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct CacheControl {
     #[serde(rename = "type")]
-    pub type_name: String,
+    pub type_name: String, // "ephemeral"
+
+    /// Lifetime of the cache entry: `"5m"` (default) or `"1h"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<String>,
 }
 
 impl CacheControl {
+    /// Cache the prefix up to this point with the default 5-minute lifetime
     pub fn ephemeral() -> Self {
         CacheControl {
             type_name: "ephemeral".to_string(),
+            ttl: None,
+        }
+    }
+
+    /// Cache the prefix up to this point for 1 hour
+    ///
+    /// Requires the [`Beta::ExtendedCacheTtl`](crate::common::beta::Beta::ExtendedCacheTtl)
+    /// flag, which [`Messages`](crate::messages::request::Messages) enables
+    /// automatically for requests using it.
+    pub fn ephemeral_1h() -> Self {
+        CacheControl {
+            type_name: "ephemeral".to_string(),
+            ttl: Some("1h".to_string()),
         }
     }
+
+    /// Check whether the entry lives for 1 hour
+    pub fn is_1h(&self) -> bool {
+        self.ttl.as_deref() == Some("1h")
+    }
 }
 
 /// JSON Schema for tool input
@@ -74,7 +183,10 @@ pub struct JsonSchema {
     #[serde(rename = "type")]
     pub type_name: String,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_sorted"
+    )]
     pub properties: Option<HashMap<String, PropertyDef>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -82,12 +194,17 @@ pub struct JsonSchema {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<bool>,
+
+    /// Other schema keywords (`$defs`, ...)
+    #[serde(flatten, default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Property definition in JSON schema
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PropertyDef {
-    #[serde(rename = "type")]
+    /// JSON type; empty for untyped definitions (e.g. a bare `anyOf`)
+    #[serde(rename = "type", default, skip_serializing_if = "String::is_empty")]
     pub type_name: String,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -99,7 +216,10 @@ pub struct PropertyDef {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<PropertyDef>>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_sorted"
+    )]
     pub properties: Option<HashMap<String, PropertyDef>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -107,9 +227,150 @@ pub struct PropertyDef {
 
     #[serde(rename = "default", skip_serializing_if = "Option::is_none")]
     pub default_value: Option<serde_json::Value>,
+
+    /// Smallest allowed number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+
+    /// Largest allowed number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+
+    /// Regular expression a string must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    /// String format (`date-time`, `email`, `uri`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Smallest allowed number of array items
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<u64>,
+
+    /// Largest allowed number of array items
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u64>,
+
+    /// Other schema keywords (`anyOf`, `minLength`, ...)
+    #[serde(flatten, default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Deserialize the input of a `tool_use` block into a typed struct
+///
+/// Errors name the tool and the path of the offending field, e.g.
+/// ``Invalid input for tool get_weather at `location.city`: invalid type``.
+pub fn parse_input<T: serde::de::DeserializeOwned>(
+    tool: &str,
+    input: &serde_json::Value,
+) -> crate::common::errors::Result<T> {
+    serde_path_to_error::deserialize(input).map_err(|err| AnthropicToolError::InvalidToolInput {
+        tool: tool.to_string(),
+        path: err.path().to_string(),
+        message: err.inner().to_string(),
+    })
+}
+
+/// Serialize a property map with keys in sorted order
+fn serialize_sorted<S: Serializer>(
+    properties: &Option<HashMap<String, PropertyDef>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    properties
+        .as_ref()
+        .map(|props| props.iter().collect::<BTreeMap<_, _>>())
+        .serialize(serializer)
+}
+
+/// Type with a generated tool input schema
+///
+/// Usually derived with `#[derive(ToolInput)]` (feature `derive`).
+pub trait ToolInput {
+    /// Object schema of the input
+    fn input_schema() -> JsonSchema;
+
+    /// Tool description (the doc comment of the type)
+    fn description() -> Option<&'static str> {
+        None
+    }
+}
+
+/// Type that maps to a property in a tool input schema
+///
+/// Implemented for strings, numbers, booleans, `Option`, `Vec`, sets, and
+/// `Box`, and derived with `#[derive(ToolInput)]` for structs (objects) and
+/// unit enums (string enums).
+pub trait ToolInputType {
+    /// Whether a field of this type is required (`false` for `Option`)
+    const REQUIRED: bool = true;
+
+    /// Property definition for this type
+    fn property_def(description: Option<String>) -> PropertyDef;
+}
+
+macro_rules! impl_tool_input_type {
+    ($constructor:ident: $($ty:ty),*) => {
+        $(
+            impl ToolInputType for $ty {
+                fn property_def(description: Option<String>) -> PropertyDef {
+                    PropertyDef::$constructor(description)
+                }
+            }
+        )*
+    };
+}
+
+impl_tool_input_type!(string: String, char);
+impl_tool_input_type!(boolean: bool);
+impl_tool_input_type!(integer: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_tool_input_type!(number: f32, f64);
+
+impl<T: ToolInputType> ToolInputType for Option<T> {
+    const REQUIRED: bool = false;
+
+    fn property_def(description: Option<String>) -> PropertyDef {
+        T::property_def(description)
+    }
+}
+
+impl<T: ToolInputType> ToolInputType for Box<T> {
+    const REQUIRED: bool = T::REQUIRED;
+
+    fn property_def(description: Option<String>) -> PropertyDef {
+        T::property_def(description)
+    }
+}
+
+impl<T: ToolInputType> ToolInputType for Vec<T> {
+    fn property_def(description: Option<String>) -> PropertyDef {
+        PropertyDef::array(description, T::property_def(None))
+    }
+}
+
+impl<T: ToolInputType> ToolInputType for HashSet<T> {
+    fn property_def(description: Option<String>) -> PropertyDef {
+        PropertyDef::array(description, T::property_def(None))
+    }
+}
+
+impl<T: ToolInputType> ToolInputType for BTreeSet<T> {
+    fn property_def(description: Option<String>) -> PropertyDef {
+        PropertyDef::array(description, T::property_def(None))
+    }
 }
 
 impl Tool {
+    /// Create a tool whose input schema and description come from a [`ToolInput`] type
+    pub fn from_input<T: ToolInput>(name: &str) -> Self {
+        Tool {
+            name: name.to_string(),
+            description: T::description().map(str::to_string),
+            input_schema: T::input_schema(),
+            cache_control: None,
+        }
+    }
+
     /// Create a new tool with name only
     pub fn new<S: AsRef<str>>(name: S) -> Self {
         Tool {
@@ -201,6 +462,44 @@ impl Tool {
         )
     }
 
+    /// Add an object property whose properties are built in a closure
+    pub fn add_object_property<S, F>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+        build: F,
+    ) -> &mut Self
+    where
+        S: AsRef<str>,
+        F: FnOnce(&mut ObjectBuilder),
+    {
+        self.add_property(
+            name.as_ref(),
+            PropertyDef::object_with(description.map(|s| s.as_ref().to_string()), build),
+            required,
+        )
+    }
+
+    /// Add an array property whose items are objects built in a closure
+    pub fn add_array_of_objects_property<S, F>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+        build: F,
+    ) -> &mut Self
+    where
+        S: AsRef<str>,
+        F: FnOnce(&mut ObjectBuilder),
+    {
+        self.add_property(
+            name.as_ref(),
+            PropertyDef::array_of_objects(description.map(|s| s.as_ref().to_string()), build),
+            required,
+        )
+    }
+
     /// Add a property with custom PropertyDef
     fn add_property(&mut self, name: &str, prop: PropertyDef, required: bool) -> &mut Self {
         if self.input_schema.properties.is_none() {
@@ -229,6 +528,114 @@ impl Tool {
         self
     }
 
+    /// Check the definition against the API's rules
+    ///
+    /// The name must match `^[a-zA-Z0-9_-]{1,64}$`, the input schema must be
+    /// an object, and at every level `required` must list existing properties
+    /// without duplicates (adding the same required property twice overwrites
+    /// the first definition).
+    pub fn validate(&self) -> crate::common::errors::Result<()> {
+        let invalid = |message: String| {
+            AnthropicToolError::InvalidParameter(format!("tool `{}`: {}", self.name, message))
+        };
+        if !is_valid_tool_name(&self.name) {
+            return Err(invalid(
+                "name must be 1-64 characters of a-z, A-Z, 0-9, `_` or `-`".to_string(),
+            ));
+        }
+        if self.input_schema.type_name != "object" {
+            return Err(invalid(format!(
+                "input_schema must have type `object`, not `{}`",
+                self.input_schema.type_name
+            )));
+        }
+        check_required(
+            "input_schema",
+            self.input_schema.properties.as_ref(),
+            self.input_schema.required.as_ref(),
+        )
+        .map_err(invalid)
+    }
+
+    /// Check the `input` of a `tool_use` block against the input schema
+    ///
+    /// Checks JSON types, required fields, enum membership, numeric and
+    /// array length bounds, and (with
+    /// `additional_properties: false`) unknown top-level fields. Untyped
+    /// definitions accept any value. The error is
+    /// [`AnthropicToolError::InvalidToolInput`] for the first offending field.
+    pub fn validate_input(&self, input: &serde_json::Value) -> crate::common::errors::Result<()> {
+        let invalid = |(path, message): (String, String)| AnthropicToolError::InvalidToolInput {
+            tool: self.name.clone(),
+            path,
+            message,
+        };
+        let schema = &self.input_schema;
+        check_type(".", &schema.type_name, input).map_err(invalid)?;
+        check_object(
+            ".",
+            schema.properties.as_ref(),
+            schema.required.as_ref(),
+            input,
+        )
+        .map_err(invalid)?;
+        if schema.additional_properties == Some(false)
+            && let Some(fields) = input.as_object()
+            && let Some(field) = fields.keys().find(|field| {
+                !schema
+                    .properties
+                    .as_ref()
+                    .is_some_and(|props| props.contains_key(*field))
+            })
+        {
+            return Err(invalid((field.clone(), "unknown field".to_string())));
+        }
+        Ok(())
+    }
+
+    /// Read a tool definition from a JSON file
+    ///
+    /// The file holds one tool as sent to the API. Errors name the file and
+    /// the offending field, and the tool is checked with [`Tool::validate`].
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> crate::common::errors::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let mut deserializer = serde_json::Deserializer::from_str(&text);
+        load_tool(path, serde_path_to_error::deserialize(&mut deserializer))
+    }
+
+    /// Write the tool definition to a JSON file (pretty-printed)
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> crate::common::errors::Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Read a tool definition from a YAML file
+    ///
+    /// Same layout and checks as [`Tool::from_json_file`].
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_file<P: AsRef<Path>>(path: P) -> crate::common::errors::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        load_tool(
+            path,
+            serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(&text)),
+        )
+    }
+
+    /// Write the tool definition to a YAML file
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml_file<P: AsRef<Path>>(&self, path: P) -> crate::common::errors::Result<()> {
+        let path = path.as_ref();
+        let yaml = serde_yaml::to_string(self).map_err(|err| {
+            AnthropicToolError::InvalidParameter(format!("{}: {}", path.display(), err))
+        })?;
+        std::fs::write(path, yaml)?;
+        Ok(())
+    }
+
     /// Build the tool and return ownership
     pub fn build(self) -> Self {
         self
@@ -240,6 +647,445 @@ impl Tool {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl Tool {
+    /// Create a tool whose input schema comes from a `schemars::JsonSchema` type
+    ///
+    /// Subschemas are inlined (only recursive types keep `$ref`s into
+    /// `$defs`), and `null` is dropped from the types of optional fields.
+    /// Fails if the type's schema is not an object.
+    pub fn from_schema<T: schemars::JsonSchema>(
+        name: &str,
+        description: &str,
+    ) -> crate::common::errors::Result<Self> {
+        let schema = schema_for::<T>()?;
+        if schema.get("type").and_then(serde_json::Value::as_str) != Some("object") {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "input schema of {} must be an object",
+                T::schema_name()
+            )));
+        }
+        Ok(Tool {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            input_schema: serde_json::from_value(schema)?,
+            cache_control: None,
+        })
+    }
+}
+
+/// Generate the schema of a `schemars::JsonSchema` type, simplified for the API
+///
+/// The title and description of the root are dropped.
+#[cfg(feature = "schemars")]
+pub(crate) fn schema_for<T: schemars::JsonSchema>()
+-> crate::common::errors::Result<serde_json::Value> {
+    let generator = schemars::generate::SchemaSettings::default()
+        .with(|settings| {
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator();
+    let mut schema = serde_json::to_value(generator.into_root_schema_for::<T>())?;
+    normalize_schema(&mut schema);
+    if let Some(root) = schema.as_object_mut() {
+        root.remove("title");
+        root.remove("description");
+    }
+    Ok(schema)
+}
+
+/// Simplify a generated schema for tool use
+///
+/// `"type": ["string", "null"]` becomes `"type": "string"`, `anyOf` with a
+/// `null` branch becomes the other branch, and `null` is removed from `enum`s.
+#[cfg(feature = "schemars")]
+fn normalize_schema(schema: &mut serde_json::Value) {
+    use serde_json::Value;
+
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    let is_null = |value: &Value| value.get("type").and_then(Value::as_str) == Some("null");
+
+    if let Some(Value::Array(types)) = object.get_mut("type") {
+        types.retain(|name| name != "null");
+        if types.len() == 1 {
+            let name = types.remove(0);
+            object.insert("type".to_string(), name);
+        } else {
+            let variants = types
+                .iter()
+                .map(|name| serde_json::json!({ "type": name }))
+                .collect();
+            object.remove("type");
+            object.insert("anyOf".to_string(), Value::Array(variants));
+        }
+    }
+    if let Some(Value::Array(variants)) = object.get("anyOf")
+        && variants.len() == 2
+        && variants.iter().any(is_null)
+    {
+        let variant = variants.iter().find(|variant| !is_null(variant)).cloned();
+        object.remove("anyOf");
+        if let Some(Value::Object(variant)) = variant {
+            for (key, value) in variant {
+                object.entry(key).or_insert(value);
+            }
+        }
+    }
+    if let Some(Value::Array(values)) = object.get_mut("enum") {
+        values.retain(|value| !value.is_null());
+    }
+
+    for key in ["properties", "$defs"] {
+        if let Some(Value::Object(map)) = object.get_mut(key) {
+            map.values_mut().for_each(normalize_schema);
+        }
+    }
+    for key in ["items", "additionalProperties", "not"] {
+        if let Some(value) = object.get_mut(key) {
+            normalize_schema(value);
+        }
+    }
+    for key in ["anyOf", "oneOf", "allOf", "prefixItems"] {
+        if let Some(Value::Array(values)) = object.get_mut(key) {
+            values.iter_mut().for_each(normalize_schema);
+        }
+    }
+}
+
+/// Finish loading a tool file, naming the file in errors
+fn load_tool<E: std::fmt::Display>(
+    path: &Path,
+    parsed: std::result::Result<Tool, serde_path_to_error::Error<E>>,
+) -> crate::common::errors::Result<Tool> {
+    let tool = parsed.map_err(|err| {
+        AnthropicToolError::InvalidParameter(format!(
+            "{}: invalid tool definition at `{}`: {}",
+            path.display(),
+            err.path(),
+            err.inner()
+        ))
+    })?;
+    tool.validate().map_err(|err| match err {
+        AnthropicToolError::InvalidParameter(message) => {
+            AnthropicToolError::InvalidParameter(format!("{}: {}", path.display(), message))
+        }
+        err => err,
+    })?;
+    Ok(tool)
+}
+
+/// Check a tool name against `^[a-zA-Z0-9_-]{1,64}$`
+fn is_valid_tool_name(name: &str) -> bool {
+    (1..=64).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Check the `required` lists of a schema and its nested properties
+fn check_required(
+    path: &str,
+    properties: Option<&HashMap<String, PropertyDef>>,
+    required: Option<&Vec<String>>,
+) -> std::result::Result<(), String> {
+    let mut seen = HashSet::new();
+    for name in required.into_iter().flatten() {
+        if !seen.insert(name) {
+            return Err(format!("{}: `{}` is listed twice in required", path, name));
+        }
+        if !properties.is_some_and(|props| props.contains_key(name)) {
+            return Err(format!(
+                "{}: required property `{}` is not defined",
+                path, name
+            ));
+        }
+    }
+    for (name, prop) in properties.into_iter().flatten() {
+        check_property(&format!("{}.{}", path, name), prop)?;
+    }
+    Ok(())
+}
+
+fn check_property(path: &str, prop: &PropertyDef) -> std::result::Result<(), String> {
+    check_required(path, prop.properties.as_ref(), prop.required.as_ref())?;
+    if let Some(items) = &prop.items {
+        check_property(&format!("{}[]", path), items)?;
+    }
+    Ok(())
+}
+
+/// Path of a field below `path` (`.` is the input itself)
+fn child_path(path: &str, field: &str) -> String {
+    if path == "." {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/// Check a value against a JSON type name
+fn check_type(
+    path: &str,
+    type_name: &str,
+    value: &serde_json::Value,
+) -> std::result::Result<(), (String, String)> {
+    let matches = match type_name {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    };
+    if matches {
+        Ok(())
+    } else {
+        Err((
+            path.to_string(),
+            format!("expected {}, found {}", type_name, value),
+        ))
+    }
+}
+
+/// Check the fields of an object value
+fn check_object(
+    path: &str,
+    properties: Option<&HashMap<String, PropertyDef>>,
+    required: Option<&Vec<String>>,
+    value: &serde_json::Value,
+) -> std::result::Result<(), (String, String)> {
+    let Some(fields) = value.as_object() else {
+        return Ok(());
+    };
+    for name in required.into_iter().flatten() {
+        if !fields.contains_key(name) {
+            return Err((path.to_string(), format!("missing field `{}`", name)));
+        }
+    }
+    for (name, prop) in properties.into_iter().flatten() {
+        if let Some(field) = fields.get(name) {
+            check_value(&child_path(path, name), prop, field)?;
+        }
+    }
+    Ok(())
+}
+
+/// Check a value against a property definition
+fn check_value(
+    path: &str,
+    prop: &PropertyDef,
+    value: &serde_json::Value,
+) -> std::result::Result<(), (String, String)> {
+    check_type(path, &prop.type_name, value)?;
+    if let Some(values) = &prop.enum_values
+        && !value
+            .as_str()
+            .is_some_and(|v| values.iter().any(|e| e == v))
+    {
+        return Err((
+            path.to_string(),
+            format!("{} is not one of {}", value, values.join(", ")),
+        ));
+    }
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = prop.minimum
+            && number < minimum
+        {
+            return Err((
+                path.to_string(),
+                format!("{} is less than {}", value, minimum),
+            ));
+        }
+        if let Some(maximum) = prop.maximum
+            && number > maximum
+        {
+            return Err((
+                path.to_string(),
+                format!("{} is greater than {}", value, maximum),
+            ));
+        }
+    }
+    if let Some(elements) = value.as_array() {
+        let len = elements.len() as u64;
+        if prop.min_items.is_some_and(|min| len < min)
+            || prop.max_items.is_some_and(|max| len > max)
+        {
+            return Err((
+                path.to_string(),
+                format!("{} items is outside the allowed range", len),
+            ));
+        }
+    }
+    check_object(
+        path,
+        prop.properties.as_ref(),
+        prop.required.as_ref(),
+        value,
+    )?;
+    if let (Some(items), Some(elements)) = (&prop.items, value.as_array()) {
+        for (i, element) in elements.iter().enumerate() {
+            check_value(&format!("{}[{}]", path, i), items, element)?;
+        }
+    }
+    Ok(())
+}
+
+/// Entry of the `tools` array of a request
+///
+/// Deserialization picks the variant from the `type` field: no type (or
+/// `custom`) is a [`Tool`], and known versioned types map to their typed
+/// definitions. Anything else, or a definition that does not match its type,
+/// is kept as [`ToolDefinition::Raw`] and sent unchanged, so tools this crate
+/// does not know yet can still be used.
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ToolDefinition {
+    /// Custom tool with an input schema
+    Custom(Tool),
+
+    /// Hosted web search
+    WebSearch(WebSearchTool),
+
+    /// Sandboxed code execution
+    CodeExecution(CodeExecutionTool),
+
+    /// Computer use
+    Computer(ComputerTool),
+
+    /// Bash session
+    Bash(BashTool),
+
+    /// Text editor
+    TextEditor(TextEditorTool),
+
+    /// Tools of an MCP server configured in `mcp_servers`
+    Mcp(McpToolset),
+
+    /// Definition sent as is
+    Raw(serde_json::Value),
+}
+
+impl ToolDefinition {
+    /// Interpret a JSON definition, falling back to [`ToolDefinition::Raw`]
+    pub fn from_value(value: serde_json::Value) -> Self {
+        fn typed<T: serde::de::DeserializeOwned>(
+            value: &serde_json::Value,
+            wrap: fn(T) -> ToolDefinition,
+        ) -> Option<ToolDefinition> {
+            T::deserialize(value).ok().map(wrap)
+        }
+
+        let parsed = match value.get("type").and_then(serde_json::Value::as_str) {
+            None | Some("custom") => typed(&value, ToolDefinition::Custom),
+            Some(WEB_SEARCH_TYPE) => typed(&value, ToolDefinition::WebSearch),
+            Some(CODE_EXECUTION_TYPE) => typed(&value, ToolDefinition::CodeExecution),
+            Some(COMPUTER_TYPE) => typed(&value, ToolDefinition::Computer),
+            Some(BASH_TYPE) => typed(&value, ToolDefinition::Bash),
+            Some(TEXT_EDITOR_TYPE) => typed(&value, ToolDefinition::TextEditor),
+            Some(MCP_TOOLSET_TYPE) => typed(&value, ToolDefinition::Mcp),
+            Some(_) => None,
+        };
+        parsed.unwrap_or(ToolDefinition::Raw(value))
+    }
+
+    /// Cache control of the definition, if set
+    pub fn cache_control(&self) -> Option<CacheControl> {
+        match self {
+            ToolDefinition::Custom(tool) => tool.cache_control.clone(),
+            ToolDefinition::WebSearch(tool) => tool.cache_control.clone(),
+            ToolDefinition::CodeExecution(tool) => tool.cache_control.clone(),
+            ToolDefinition::Computer(tool) => tool.cache_control.clone(),
+            ToolDefinition::Bash(tool) => tool.cache_control.clone(),
+            ToolDefinition::TextEditor(tool) => tool.cache_control.clone(),
+            ToolDefinition::Mcp(toolset) => toolset
+                .extra
+                .get("cache_control")
+                .and_then(|value| CacheControl::deserialize(value).ok()),
+            ToolDefinition::Raw(value) => value
+                .get("cache_control")
+                .and_then(|value| CacheControl::deserialize(value).ok()),
+        }
+    }
+
+    /// Tool name, if the definition has one
+    ///
+    /// MCP toolsets return the server name.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            ToolDefinition::Custom(tool) => Some(&tool.name),
+            ToolDefinition::WebSearch(tool) => Some(&tool.name),
+            ToolDefinition::CodeExecution(tool) => Some(&tool.name),
+            ToolDefinition::Computer(tool) => Some(&tool.name),
+            ToolDefinition::Bash(tool) => Some(&tool.name),
+            ToolDefinition::TextEditor(tool) => Some(&tool.name),
+            ToolDefinition::Mcp(toolset) => Some(&toolset.mcp_server_name),
+            ToolDefinition::Raw(value) => value.get("name").and_then(serde_json::Value::as_str),
+        }
+    }
+
+    /// Convert to serde_json::Value
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+
+    /// Check the definition against the API's rules
+    ///
+    /// Custom tools use [`Tool::validate`] and web search
+    /// [`WebSearchTool::validate`]; raw definitions are not checked.
+    pub fn validate(&self) -> crate::common::errors::Result<()> {
+        match self {
+            ToolDefinition::Custom(tool) => tool.validate(),
+            ToolDefinition::WebSearch(tool) => tool.validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ToolDefinition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_json::Value::deserialize(deserializer).map(ToolDefinition::from_value)
+    }
+}
+
+macro_rules! impl_from_tool {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        $(
+            impl From<$ty> for ToolDefinition {
+                fn from(tool: $ty) -> Self {
+                    ToolDefinition::$variant(tool)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_tool!(
+    Custom(Tool),
+    WebSearch(WebSearchTool),
+    CodeExecution(CodeExecutionTool),
+    Computer(ComputerTool),
+    Bash(BashTool),
+    TextEditor(TextEditorTool),
+    Mcp(McpToolset),
+);
+
+impl From<&Tool> for ToolDefinition {
+    fn from(tool: &Tool) -> Self {
+        ToolDefinition::Custom(tool.clone())
+    }
+}
+
+impl From<serde_json::Value> for ToolDefinition {
+    fn from(value: serde_json::Value) -> Self {
+        ToolDefinition::from_value(value)
+    }
+}
+
 impl JsonSchema {
     /// Create an object schema
     pub fn object() -> Self {
@@ -248,6 +1094,7 @@ impl JsonSchema {
             properties: Some(HashMap::new()),
             required: None,
             additional_properties: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -258,6 +1105,7 @@ impl JsonSchema {
             properties: None,
             required: None,
             additional_properties: None,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -273,6 +1121,13 @@ impl PropertyDef {
             properties: None,
             required: None,
             default_value: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            format: None,
+            min_items: None,
+            max_items: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -286,6 +1141,13 @@ impl PropertyDef {
             properties: None,
             required: None,
             default_value: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            format: None,
+            min_items: None,
+            max_items: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -299,6 +1161,13 @@ impl PropertyDef {
             properties: None,
             required: None,
             default_value: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            format: None,
+            min_items: None,
+            max_items: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -312,6 +1181,13 @@ impl PropertyDef {
             properties: None,
             required: None,
             default_value: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            format: None,
+            min_items: None,
+            max_items: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -325,6 +1201,13 @@ impl PropertyDef {
             properties: None,
             required: None,
             default_value: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            format: None,
+            min_items: None,
+            max_items: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -338,6 +1221,13 @@ impl PropertyDef {
             properties: None,
             required: None,
             default_value: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            format: None,
+            min_items: None,
+            max_items: None,
+            extra: serde_json::Map::new(),
         }
     }
 
@@ -351,14 +1241,257 @@ impl PropertyDef {
             properties: Some(properties),
             required: None,
             default_value: None,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            format: None,
+            min_items: None,
+            max_items: None,
+            extra: serde_json::Map::new(),
         }
     }
 
+    /// Create an object property whose properties are built in a closure
+    pub fn object_with<F: FnOnce(&mut ObjectBuilder)>(
+        description: Option<String>,
+        build: F,
+    ) -> Self {
+        let mut builder = ObjectBuilder::new();
+        build(&mut builder);
+        builder.build(description)
+    }
+
+    /// Create an array property whose items are objects built in a closure
+    pub fn array_of_objects<F: FnOnce(&mut ObjectBuilder)>(
+        description: Option<String>,
+        build: F,
+    ) -> Self {
+        PropertyDef::array(description, PropertyDef::object_with(None, build))
+    }
+
+    /// Create an object property from a [`ToolInput`] type
+    ///
+    /// Without a description, the type's own description is used.
+    pub fn from_input<T: ToolInput>(description: Option<String>) -> Self {
+        let schema = T::input_schema();
+        let mut property = PropertyDef::object(
+            description.or_else(|| T::description().map(str::to_string)),
+            schema.properties.unwrap_or_default(),
+        );
+        property.required = schema.required;
+        property
+    }
+
     /// Set a default value
     pub fn with_default(&mut self, value: serde_json::Value) -> &mut Self {
         self.default_value = Some(value);
         self
     }
+
+    /// Set the smallest allowed number
+    pub fn minimum(&mut self, minimum: f64) -> &mut Self {
+        self.minimum = Some(minimum);
+        self
+    }
+
+    /// Set the largest allowed number
+    pub fn maximum(&mut self, maximum: f64) -> &mut Self {
+        self.maximum = Some(maximum);
+        self
+    }
+
+    /// Set the regular expression a string must match
+    pub fn pattern<S: AsRef<str>>(&mut self, pattern: S) -> &mut Self {
+        self.pattern = Some(pattern.as_ref().to_string());
+        self
+    }
+
+    /// Set the string format (`date-time`, `email`, `uri`, ...)
+    pub fn format<S: AsRef<str>>(&mut self, format: S) -> &mut Self {
+        self.format = Some(format.as_ref().to_string());
+        self
+    }
+
+    /// Set the smallest allowed number of array items
+    pub fn min_items(&mut self, min_items: u64) -> &mut Self {
+        self.min_items = Some(min_items);
+        self
+    }
+
+    /// Set the largest allowed number of array items
+    pub fn max_items(&mut self, max_items: u64) -> &mut Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+/// Properties of a nested object
+///
+/// Passed to the closures of [`Tool::add_object_property`],
+/// [`Tool::add_array_of_objects_property`], and [`PropertyDef::object_with`].
+/// The methods mirror the `add_*_property` methods of [`Tool`].
+#[derive(Debug, Clone, Default)]
+pub struct ObjectBuilder {
+    properties: HashMap<String, PropertyDef>,
+    required: Vec<String>,
+}
+
+impl ObjectBuilder {
+    /// Create an object without properties
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a string property
+    pub fn string<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+    ) -> &mut Self {
+        self.property(
+            name,
+            PropertyDef::string(description.map(|s| s.as_ref().to_string())),
+            required,
+        )
+    }
+
+    /// Add a number property
+    pub fn number<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+    ) -> &mut Self {
+        self.property(
+            name,
+            PropertyDef::number(description.map(|s| s.as_ref().to_string())),
+            required,
+        )
+    }
+
+    /// Add an integer property
+    pub fn integer<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+    ) -> &mut Self {
+        self.property(
+            name,
+            PropertyDef::integer(description.map(|s| s.as_ref().to_string())),
+            required,
+        )
+    }
+
+    /// Add a boolean property
+    pub fn boolean<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+    ) -> &mut Self {
+        self.property(
+            name,
+            PropertyDef::boolean(description.map(|s| s.as_ref().to_string())),
+            required,
+        )
+    }
+
+    /// Add an enum (string with allowed values) property
+    pub fn enum_type<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        values: Vec<S>,
+        required: bool,
+    ) -> &mut Self {
+        self.property(
+            name,
+            PropertyDef::enum_type(
+                description.map(|s| s.as_ref().to_string()),
+                values.into_iter().map(|s| s.as_ref().to_string()).collect(),
+            ),
+            required,
+        )
+    }
+
+    /// Add an array property
+    pub fn array<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        items: PropertyDef,
+        required: bool,
+    ) -> &mut Self {
+        self.property(
+            name,
+            PropertyDef::array(description.map(|s| s.as_ref().to_string()), items),
+            required,
+        )
+    }
+
+    /// Add an object property whose properties are built in a closure
+    pub fn object<S, F>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+        build: F,
+    ) -> &mut Self
+    where
+        S: AsRef<str>,
+        F: FnOnce(&mut ObjectBuilder),
+    {
+        self.property(
+            name,
+            PropertyDef::object_with(description.map(|s| s.as_ref().to_string()), build),
+            required,
+        )
+    }
+
+    /// Add an array property whose items are objects built in a closure
+    pub fn array_of_objects<S, F>(
+        &mut self,
+        name: S,
+        description: Option<S>,
+        required: bool,
+        build: F,
+    ) -> &mut Self
+    where
+        S: AsRef<str>,
+        F: FnOnce(&mut ObjectBuilder),
+    {
+        self.property(
+            name,
+            PropertyDef::array_of_objects(description.map(|s| s.as_ref().to_string()), build),
+            required,
+        )
+    }
+
+    /// Add a property with a custom PropertyDef
+    pub fn property<S: AsRef<str>>(
+        &mut self,
+        name: S,
+        prop: PropertyDef,
+        required: bool,
+    ) -> &mut Self {
+        let name = name.as_ref().to_string();
+        if required {
+            self.required.push(name.clone());
+        }
+        self.properties.insert(name, prop);
+        self
+    }
+
+    /// Build the object property
+    pub fn build(self, description: Option<String>) -> PropertyDef {
+        let mut prop = PropertyDef::object(description, self.properties);
+        if !self.required.is_empty() {
+            prop.required = Some(self.required);
+        }
+        prop
+    }
 }
 
 #[cfg(test)]
@@ -389,7 +1522,11 @@ mod tests {
     fn test_tool_serialize() {
         let mut tool = Tool::new("get_weather");
         tool.description("Get the current weather in a given location")
-            .add_string_property("location", Some("The city and state, e.g. San Francisco, CA"), true)
+            .add_string_property(
+                "location",
+                Some("The city and state, e.g. San Francisco, CA"),
+                true,
+            )
             .add_enum_property(
                 "unit",
                 Some("Temperature unit"),
@@ -437,6 +1574,53 @@ mod tests {
         assert!(tool.cache_control.is_some());
     }
 
+    #[test]
+    fn test_cache_control_ttl() {
+        let json = serde_json::to_value(CacheControl::ephemeral()).unwrap();
+        assert_eq!(json, serde_json::json!({"type": "ephemeral"}));
+        let hour = CacheControl::ephemeral_1h();
+        assert_eq!(
+            serde_json::to_value(&hour).unwrap(),
+            serde_json::json!({"type": "ephemeral", "ttl": "1h"})
+        );
+        assert!(hour.is_1h() && !CacheControl::ephemeral().is_1h());
+
+        let raw = ToolDefinition::Raw(serde_json::json!({
+            "name": "lookup",
+            "cache_control": {"type": "ephemeral", "ttl": "1h"}
+        }));
+        assert_eq!(raw.cache_control(), Some(hour));
+        assert_eq!(
+            ToolDefinition::from(Tool::new("plain")).cache_control(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_properties_serialized_in_sorted_order() {
+        let build = || {
+            let mut nested = HashMap::new();
+            for name in ["zeta", "alpha", "mu"] {
+                nested.insert(name.to_string(), PropertyDef::string(None));
+            }
+            let mut tool = Tool::new("ordered");
+            for name in ["query", "limit", "offset", "b", "a", "z"] {
+                tool.add_string_property(name, None, false);
+            }
+            tool.add_property("filters", PropertyDef::object(None, nested), false);
+            serde_json::to_string(&tool).unwrap()
+        };
+
+        let json = build();
+        for _ in 0..10 {
+            assert_eq!(build(), json);
+        }
+        assert!(
+            json.contains(r#""properties":{"a":{"type":"string"},"b":{"type":"string"},"filters""#)
+        );
+        assert!(json.contains(r#""properties":{"alpha":{"type":"string"},"mu""#));
+    }
+
     #[test]
     fn test_tool_to_value() {
         let mut tool = Tool::new("test");
@@ -445,4 +1629,464 @@ mod tests {
         assert!(value.is_object());
         assert_eq!(value["name"], "test");
     }
+
+    #[cfg(feature = "derive")]
+    mod derive {
+        use super::super::*;
+        use serde::Deserialize;
+
+        /// Temperature unit
+        #[derive(ToolInput, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        #[allow(dead_code)]
+        enum Unit {
+            Celsius,
+            Fahrenheit,
+        }
+
+        #[derive(ToolInput, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        #[allow(dead_code)]
+        struct Location {
+            city_name: String,
+            country_code: Option<String>,
+        }
+
+        /// Get the current weather
+        /// for a location
+        #[derive(ToolInput, Deserialize)]
+        #[allow(dead_code)]
+        struct GetWeather {
+            /// Where to look
+            location: Location,
+            unit: Option<Unit>,
+            #[serde(default)]
+            days: u8,
+            tags: Vec<String>,
+            #[serde(skip)]
+            internal: bool,
+            #[serde(rename = "type")]
+            kind: String,
+        }
+
+        #[test]
+        fn test_derive_tool_input() {
+            let tool = Tool::from_input::<GetWeather>("get_weather");
+            let json = tool.to_value();
+            assert_eq!(
+                json["description"],
+                "Get the current weather for a location"
+            );
+            let properties = &json["input_schema"]["properties"];
+            assert_eq!(properties["unit"]["type"], "string");
+            assert_eq!(properties["unit"]["description"], "Temperature unit");
+            assert_eq!(
+                properties["unit"]["enum"],
+                serde_json::json!(["celsius", "fahrenheit"])
+            );
+            assert_eq!(properties["days"]["type"], "integer");
+            assert_eq!(properties["tags"]["items"]["type"], "string");
+            assert_eq!(properties["type"]["type"], "string");
+            assert!(properties.get("internal").is_none());
+
+            assert_eq!(properties["location"]["type"], "object");
+            assert_eq!(properties["location"]["description"], "Where to look");
+            assert_eq!(
+                properties["location"]["properties"]["cityName"]["type"],
+                "string"
+            );
+            assert_eq!(
+                properties["location"]["required"],
+                serde_json::json!(["cityName"])
+            );
+
+            let mut required: Vec<&str> = json["input_schema"]["required"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|name| name.as_str().unwrap())
+                .collect();
+            required.sort();
+            assert_eq!(required, vec!["location", "tags", "type"]);
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_from_schema() {
+        #[derive(schemars::JsonSchema)]
+        #[serde(rename_all = "lowercase")]
+        #[allow(dead_code)]
+        enum Unit {
+            Celsius,
+            Fahrenheit,
+        }
+
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Location {
+            /// City name
+            city: String,
+            #[schemars(range(min = -90.0, max = 90.0))]
+            latitude: Option<f64>,
+        }
+
+        /// Weather query
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct GetWeather {
+            location: Location,
+            unit: Option<Unit>,
+            days: u8,
+        }
+
+        let tool = Tool::from_schema::<GetWeather>("get_weather", "Get the weather").unwrap();
+        let json = tool.to_value();
+        assert_eq!(json["description"], "Get the weather");
+        let schema = &json["input_schema"];
+        assert_eq!(schema["type"], "object");
+        assert!(schema.get("title").is_none());
+        assert!(schema.get("$schema").is_none());
+
+        let mut required: Vec<&str> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|name| name.as_str().unwrap())
+            .collect();
+        required.sort();
+        assert_eq!(required, vec!["days", "location"]);
+
+        let properties = &schema["properties"];
+        assert_eq!(properties["days"]["type"], "integer");
+        assert_eq!(properties["days"]["format"], "uint8");
+        assert_eq!(properties["unit"]["type"], "string");
+        assert_eq!(
+            properties["unit"]["enum"],
+            serde_json::json!(["celsius", "fahrenheit"])
+        );
+        let location = &properties["location"];
+        assert_eq!(location["type"], "object");
+        assert_eq!(location["properties"]["city"]["description"], "City name");
+        assert_eq!(location["properties"]["latitude"]["type"], "number");
+        assert_eq!(location["properties"]["latitude"]["minimum"], -90.0);
+        assert_eq!(location["required"], serde_json::json!(["city"]));
+
+        assert!(Tool::from_schema::<String>("name", "Not an object").is_err());
+    }
+
+    #[test]
+    fn test_parse_input() {
+        #[derive(Deserialize, Debug)]
+        struct Location {
+            city: String,
+        }
+
+        #[derive(Deserialize, Debug)]
+        struct Input {
+            location: Location,
+            days: Option<u8>,
+        }
+
+        let input: Input = parse_input(
+            "get_weather",
+            &serde_json::json!({"location": {"city": "Tokyo"}, "days": 3}),
+        )
+        .unwrap();
+        assert_eq!(input.location.city, "Tokyo");
+        assert_eq!(input.days, Some(3));
+
+        let err = parse_input::<Input>(
+            "get_weather",
+            &serde_json::json!({"location": {"city": 42}}),
+        )
+        .unwrap_err();
+        match &err {
+            AnthropicToolError::InvalidToolInput { tool, path, .. } => {
+                assert_eq!(tool, "get_weather");
+                assert_eq!(path, "location.city");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(err.to_string().contains("`location.city`"));
+
+        let err = parse_input::<Input>("get_weather", &serde_json::json!({})).unwrap_err();
+        assert!(err.to_string().contains("missing field `location`"));
+    }
+
+    #[test]
+    fn test_tool_definition() {
+        let mut tool = Tool::new("search");
+        tool.add_string_property("query", Some("Search query"), true);
+        let tools: Vec<ToolDefinition> = serde_json::from_value(serde_json::json!([
+            tool.to_value(),
+            {"type": "web_search_20250305", "name": "web_search", "max_uses": 2},
+            {"type": "mcp_toolset", "mcp_server_name": "github"},
+            {"type": "memory_20250818", "name": "memory"},
+            {"type": "bash_20250124"}
+        ]))
+        .unwrap();
+
+        assert!(matches!(&tools[0], ToolDefinition::Custom(t) if t.name == "search"));
+        assert!(matches!(&tools[1], ToolDefinition::WebSearch(t) if t.max_uses == Some(2)));
+        assert!(matches!(&tools[2], ToolDefinition::Mcp(_)));
+        assert!(matches!(&tools[3], ToolDefinition::Raw(_)));
+        // Missing `name`: kept as is
+        assert!(matches!(&tools[4], ToolDefinition::Raw(_)));
+
+        let names: Vec<Option<&str>> = tools.iter().map(ToolDefinition::name).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("search"),
+                Some("web_search"),
+                Some("github"),
+                Some("memory"),
+                None
+            ]
+        );
+        assert_eq!(tools[3].to_value()["type"], "memory_20250818");
+        assert_eq!(ToolDefinition::from(&tool).to_value(), tool.to_value());
+    }
+
+    #[test]
+    fn test_nested_properties() {
+        let mut tool = Tool::new("create_order");
+        tool.add_object_property("customer", Some("Who ordered"), true, |customer| {
+            customer.string("name", Some("Full name"), true).object(
+                "address",
+                None,
+                false,
+                |address| {
+                    address.string("city", None, true);
+                },
+            );
+        })
+        .add_array_of_objects_property("items", None, true, |item| {
+            item.string("sku", None, true)
+                .integer("quantity", None, true)
+                .enum_type("size", None, vec!["s", "m", "l"], false);
+        });
+
+        assert_eq!(
+            tool.to_value()["input_schema"],
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "customer": {
+                        "type": "object",
+                        "description": "Who ordered",
+                        "properties": {
+                            "address": {
+                                "type": "object",
+                                "properties": {"city": {"type": "string"}},
+                                "required": ["city"]
+                            },
+                            "name": {"type": "string", "description": "Full name"}
+                        },
+                        "required": ["name"]
+                    },
+                    "items": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "quantity": {"type": "integer"},
+                                "size": {"type": "string", "enum": ["s", "m", "l"]},
+                                "sku": {"type": "string"}
+                            },
+                            "required": ["sku", "quantity"]
+                        }
+                    }
+                },
+                "required": ["customer", "items"]
+            })
+        );
+        assert!(tool.validate().is_ok());
+        assert!(
+            tool.validate_input(&serde_json::json!({
+                "customer": {"name": "Ann", "address": {}},
+                "items": []
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_property_constraints() {
+        let mut quantity = PropertyDef::integer(Some("Quantity".to_string()));
+        quantity.minimum(1.0).maximum(10.0);
+        let mut code = PropertyDef::string(None);
+        code.pattern("^[A-Z]{3}$");
+        let mut date = PropertyDef::string(None);
+        date.format("date");
+        let mut tags = PropertyDef::array(None, PropertyDef::string(None));
+        tags.min_items(1).max_items(3);
+
+        let mut tool = Tool::new("order");
+        tool.add_object_property("order", None, true, |order| {
+            order
+                .property("quantity", quantity, true)
+                .property("code", code, false)
+                .property("date", date, false)
+                .property("tags", tags, false);
+        });
+        let json = tool.to_value();
+        let order = &json["input_schema"]["properties"]["order"]["properties"];
+        assert_eq!(
+            order["quantity"],
+            serde_json::json!({
+                "type": "integer", "description": "Quantity", "minimum": 1.0, "maximum": 10.0
+            })
+        );
+        assert_eq!(order["code"]["pattern"], "^[A-Z]{3}$");
+        assert_eq!(order["date"]["format"], "date");
+        assert_eq!(order["tags"]["minItems"], 1);
+        assert_eq!(order["tags"]["maxItems"], 3);
+
+        let parsed: PropertyDef = serde_json::from_value(order["tags"].clone()).unwrap();
+        assert_eq!(parsed.max_items, Some(3));
+        assert!(parsed.extra.is_empty());
+
+        let input = |quantity: u32, tags: Vec<&str>| serde_json::json!({"order": {"quantity": quantity, "tags": tags}});
+        assert!(tool.validate_input(&input(5, vec!["a"])).is_ok());
+        let err = tool.validate_input(&input(11, vec!["a"])).unwrap_err();
+        assert!(err.to_string().contains("order.quantity"));
+        let err = tool.validate_input(&input(5, vec![])).unwrap_err();
+        assert!(err.to_string().contains("order.tags"));
+    }
+
+    #[test]
+    fn test_json_file() {
+        let dir = std::env::temp_dir().join(format!("anthropic-tools-tool-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("get_weather.json");
+
+        let mut tool = Tool::new("get_weather");
+        tool.description("Get the weather").add_string_property(
+            "location",
+            Some("City name"),
+            true,
+        );
+        tool.to_json_file(&file).unwrap();
+        let loaded = Tool::from_json_file(&file).unwrap();
+        assert_eq!(loaded.to_value(), tool.to_value());
+
+        std::fs::write(
+            &file,
+            r#"{"name": "get_weather", "input_schema": {"type": "object", "required": 3}}"#,
+        )
+        .unwrap();
+        let err = Tool::from_json_file(&file).unwrap_err().to_string();
+        assert!(
+            err.contains("get_weather.json: invalid tool definition at `input_schema.required`")
+        );
+
+        std::fs::write(
+            &file,
+            r#"{"name": "get weather", "input_schema": {"type": "object"}}"#,
+        )
+        .unwrap();
+        let err = Tool::from_json_file(&file).unwrap_err().to_string();
+        assert!(err.contains("get_weather.json: tool `get weather`: name must be"));
+
+        #[cfg(feature = "yaml")]
+        {
+            let file = dir.join("get_weather.yaml");
+            tool.to_yaml_file(&file).unwrap();
+            let loaded = Tool::from_yaml_file(&file).unwrap();
+            assert_eq!(loaded.to_value(), tool.to_value());
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut tool = Tool::new("get_weather");
+        tool.add_string_property("location", Some("City name"), true);
+        assert!(tool.validate().is_ok());
+
+        assert!(Tool::new("get weather").validate().is_err());
+        assert!(Tool::new("").validate().is_err());
+        assert!(Tool::new("a".repeat(64)).validate().is_ok());
+        assert!(Tool::new("a".repeat(65)).validate().is_err());
+
+        // Adding a required property twice
+        tool.add_string_property("location", Some("City"), true);
+        let err = tool.validate().unwrap_err();
+        assert!(err.to_string().contains("`location` is listed twice"));
+
+        let mut tool = Tool::new("search");
+        tool.input_schema.required = Some(vec!["query".to_string()]);
+        let err = tool.validate().unwrap_err();
+        assert!(err.to_string().contains("`query` is not defined"));
+
+        let mut item = PropertyDef::object(None, HashMap::new());
+        item.required = Some(vec!["id".to_string()]);
+        let mut tool = Tool::new("tag");
+        tool.add_array_property("tags", None, item, false);
+        let err = tool.validate().unwrap_err();
+        assert!(err.to_string().contains("input_schema.tags[]"));
+    }
+
+    #[test]
+    fn test_validate_input() {
+        let mut tool = Tool::new("get_weather");
+        tool.add_string_property("location", Some("City name"), true)
+            .add_enum_property("unit", Some("Unit"), vec!["celsius", "fahrenheit"], false)
+            .add_array_property("days", None, PropertyDef::integer(None), false);
+        let check = |tool: &Tool, input: serde_json::Value| match tool.validate_input(&input) {
+            Ok(()) => None,
+            Err(AnthropicToolError::InvalidToolInput { path, message, .. }) => {
+                Some(format!("{}: {}", path, message))
+            }
+            Err(err) => panic!("unexpected error: {}", err),
+        };
+
+        assert_eq!(
+            check(
+                &tool,
+                serde_json::json!({"location": "Tokyo", "unit": "celsius", "days": [1, 2]})
+            ),
+            None
+        );
+        assert_eq!(
+            check(&tool, serde_json::json!({"unit": "celsius"})).unwrap(),
+            ".: missing field `location`"
+        );
+        assert_eq!(
+            check(&tool, serde_json::json!({"location": 3})).unwrap(),
+            "location: expected string, found 3"
+        );
+        assert_eq!(
+            check(
+                &tool,
+                serde_json::json!({"location": "Tokyo", "unit": "kelvin"})
+            )
+            .unwrap(),
+            "unit: \"kelvin\" is not one of celsius, fahrenheit"
+        );
+        assert_eq!(
+            check(
+                &tool,
+                serde_json::json!({"location": "Tokyo", "days": [1, 2.5]})
+            )
+            .unwrap(),
+            "days[1]: expected integer, found 2.5"
+        );
+        assert_eq!(
+            check(&tool, serde_json::json!("Tokyo")).unwrap(),
+            ".: expected object, found \"Tokyo\""
+        );
+
+        tool.input_schema.additional_properties = Some(false);
+        assert_eq!(
+            check(
+                &tool,
+                serde_json::json!({"location": "Tokyo", "city": "Tokyo"})
+            )
+            .unwrap(),
+            "city: unknown field"
+        );
+    }
 }
@@ -0,0 +1,223 @@
+//! Pluggable HTTP transport.
+//!
+//! [`Messages`](crate::messages::request::Messages) builds requests and parses
+//! responses, but the actual HTTP exchange goes through an [`HttpTransport`].
+//! The default [`ReqwestTransport`] uses `reqwest`; implement the trait to
+//! plug in mock transports for tests, another HTTP stack, or a transport that
+//! signs requests.
+//!
+//! - [`HttpTransport`] - Sends an [`HttpRequest`] and returns an [`HttpResponse`]
+//! - [`BodyStream`] - Response body delivered in chunks (used for streaming)
+//! - [`ReqwestTransport`] - Default transport backed by `reqwest`
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::future::{BoxFuture, boxed};
+//! use anthropic_tools::common::transport::{HttpRequest, HttpResponse, HttpTransport};
+//! use anthropic_tools::messages::request::Messages;
+//! use anthropic_tools::Result;
+//!
+//! /// Answers every request with the same canned response
+//! #[derive(Debug)]
+//! struct CannedTransport(String);
+//!
+//! impl HttpTransport for CannedTransport {
+//!     fn send(&self, _request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+//!         let body = self.0.clone();
+//!         boxed(async move { Ok(HttpResponse::from_bytes(200, body)) })
+//!     }
+//! }
+//!
+//! let mut client = Messages::with_api_key("test_key");
+//! client.transport(CannedTransport(r#"{"id":"msg_1"}"#.to_string()));
+//! ```
+
+use crate::common::errors::Result;
+use crate::common::future::{BoxFuture, MaybeSend, boxed};
+use std::fmt;
+use std::sync::Arc;
+
+/// Sends HTTP requests on behalf of the API clients
+pub trait HttpTransport: fmt::Debug + Send + Sync {
+    /// Send a request and return the response once its headers have arrived
+    ///
+    /// Error statuses are returned as normal responses; the caller maps them
+    /// to API errors.
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>>;
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        (**self).send(request)
+    }
+}
+
+/// Response body delivered in chunks
+pub trait BodyStream: MaybeSend {
+    /// Get the next chunk of the body, or `None` at the end
+    fn next_chunk(&mut self) -> BoxFuture<'_, Result<Option<Vec<u8>>>>;
+}
+
+/// HTTP request produced by a client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    /// Headers in the order they are sent
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Get a header value by name (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// HTTP response returned by a transport
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Box<dyn BodyStream>,
+}
+
+impl HttpResponse {
+    /// Create a response with a body that is already in memory
+    pub fn from_bytes<B: Into<Vec<u8>>>(status: u16, body: B) -> Self {
+        HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: Box::new(FullBody(Some(body.into()))),
+        }
+    }
+
+    /// Check whether the status is 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Get a header value by name (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Read the whole body
+    pub async fn bytes(mut self) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.body.next_chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    /// Read the whole body as text (invalid UTF-8 is replaced)
+    pub async fn text(self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.bytes().await?).into_owned())
+    }
+}
+
+impl fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Body held entirely in memory, yielded as a single chunk
+struct FullBody(Option<Vec<u8>>);
+
+impl BodyStream for FullBody {
+    fn next_chunk(&mut self) -> BoxFuture<'_, Result<Option<Vec<u8>>>> {
+        let chunk = self.0.take();
+        boxed(async move { Ok(chunk) })
+    }
+}
+
+/// Default transport backed by a `reqwest` client
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: request::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport from an existing `reqwest` client
+    pub fn new(client: request::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        boxed(async move {
+            let method = request::Method::from_bytes(request.method.as_bytes()).map_err(|_| {
+                crate::common::errors::AnthropicToolError::InvalidParameter(format!(
+                    "invalid HTTP method: {}",
+                    request.method
+                ))
+            })?;
+            let mut builder = self.client.request(method, &request.url);
+            for (name, value) in &request.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            let response = builder.body(request.body).send().await?;
+
+            let headers = response
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                    (name.to_string(), value)
+                })
+                .collect();
+            Ok(HttpResponse {
+                status: response.status().as_u16(),
+                headers,
+                body: Box::new(ReqwestBody(response)),
+            })
+        })
+    }
+}
+
+/// Body of a `reqwest` response, read chunk by chunk
+struct ReqwestBody(request::Response);
+
+impl BodyStream for ReqwestBody {
+    fn next_chunk(&mut self) -> BoxFuture<'_, Result<Option<Vec<u8>>>> {
+        boxed(async move { Ok(self.0.chunk().await?.map(|chunk| chunk.to_vec())) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_response_from_bytes() {
+        let response = HttpResponse::from_bytes(201, "hello");
+        assert!(response.is_success());
+        assert_eq!(response.text().await.unwrap(), "hello");
+        assert!(!HttpResponse::from_bytes(429, "").is_success());
+    }
+
+    #[test]
+    fn test_request_header_lookup() {
+        let request = HttpRequest {
+            method: "POST".to_string(),
+            url: "https://example.com".to_string(),
+            headers: vec![("x-api-key".to_string(), "secret".to_string())],
+            body: Vec::new(),
+        };
+        assert_eq!(request.header("X-Api-Key"), Some("secret"));
+        assert_eq!(request.header("missing"), None);
+    }
+}
@@ -6,6 +6,10 @@
 //! - Output tokens generated
 //! - Cache creation and read tokens (for prompt caching)
 //!
+//! It also provides [`UsageReport`], the per-request summary passed to
+//! callbacks registered with
+//! [`Messages::on_usage`](crate::messages::request::Messages::on_usage).
+//!
 //! # Example
 //!
 //! ```rust
@@ -31,10 +35,13 @@
 //! assert_eq!(usage.cached_tokens(), 0); // No cache tokens in this example
 //! ```
 
+use crate::messages::request::prompts::PromptVersion;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Token usage information from Anthropic API response
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Usage {
     /// The number of input tokens used
     pub input_tokens: usize,
@@ -49,6 +56,18 @@ pub struct Usage {
     /// The number of input tokens read from the cache
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_read_input_tokens: Option<usize>,
+
+    /// Requests made by server tools (e.g. web searches)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_tool_use: Option<ServerToolUse>,
+}
+
+/// Requests made by server tools for a response
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerToolUse {
+    /// The number of web searches
+    pub web_search_requests: usize,
 }
 
 impl Usage {
@@ -59,6 +78,7 @@ impl Usage {
             output_tokens,
             cache_creation_input_tokens: None,
             cache_read_input_tokens: None,
+            server_tool_use: None,
         }
     }
 
@@ -67,10 +87,91 @@ impl Usage {
         self.input_tokens + self.output_tokens
     }
 
+    /// Add the usage of another request
+    pub fn accumulate(&mut self, other: &Usage) -> &mut Self {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        for (total, more) in [
+            (
+                &mut self.cache_creation_input_tokens,
+                other.cache_creation_input_tokens,
+            ),
+            (
+                &mut self.cache_read_input_tokens,
+                other.cache_read_input_tokens,
+            ),
+        ] {
+            if let Some(more) = more {
+                *total = Some(total.unwrap_or(0) + more);
+            }
+        }
+        if let Some(more) = other.server_tool_use {
+            let total = self.server_tool_use.get_or_insert_default();
+            total.web_search_requests += more.web_search_requests;
+        }
+        self
+    }
+
     /// Get total cached tokens
     pub fn cached_tokens(&self) -> usize {
         self.cache_creation_input_tokens.unwrap_or(0) + self.cache_read_input_tokens.unwrap_or(0)
     }
+
+    /// Get all prompt tokens (uncached input plus cache creation and reads)
+    pub fn prompt_tokens(&self) -> usize {
+        self.input_tokens + self.cached_tokens()
+    }
+
+    /// Get the fraction of prompt tokens served from the cache (0.0 to 1.0)
+    pub fn cache_hit_ratio(&self) -> f64 {
+        let prompt_tokens = self.prompt_tokens();
+        if prompt_tokens == 0 {
+            return 0.0;
+        }
+        self.cache_read_input_tokens.unwrap_or(0) as f64 / prompt_tokens as f64
+    }
+}
+
+/// Per-request usage summary for autoscalers and cost controllers
+#[derive(Debug, Clone)]
+pub struct UsageReport {
+    /// Model that served the request
+    pub model: String,
+
+    /// Token usage reported by the API
+    pub usage: Usage,
+
+    /// Wall-clock latency of the request
+    pub latency: Duration,
+
+    /// System prompt version selected for the conversation, if any
+    pub prompt_version: Option<PromptVersion>,
+}
+
+impl UsageReport {
+    /// Get the number of input tokens read from the cache
+    pub fn cache_read_tokens(&self) -> usize {
+        self.usage.cache_read_input_tokens.unwrap_or(0)
+    }
+
+    /// Get the number of input tokens written to the cache
+    pub fn cache_creation_tokens(&self) -> usize {
+        self.usage.cache_creation_input_tokens.unwrap_or(0)
+    }
+
+    /// Get the fraction of prompt tokens served from the cache
+    pub fn cache_hit_ratio(&self) -> f64 {
+        self.usage.cache_hit_ratio()
+    }
+
+    /// Get output tokens generated per second of latency
+    pub fn output_tokens_per_second(&self) -> f64 {
+        let seconds = self.latency.as_secs_f64();
+        if seconds == 0.0 {
+            return 0.0;
+        }
+        self.usage.output_tokens as f64 / seconds
+    }
 }
 
 #[cfg(test)]
@@ -102,6 +203,29 @@ mod tests {
         assert_eq!(usage.cached_tokens(), 30);
     }
 
+    #[test]
+    fn test_cache_hit_ratio() {
+        let mut usage = Usage::new(10, 5);
+        assert_eq!(usage.cache_hit_ratio(), 0.0);
+
+        usage.cache_creation_input_tokens = Some(10);
+        usage.cache_read_input_tokens = Some(80);
+        assert_eq!(usage.prompt_tokens(), 100);
+        assert!((usage.cache_hit_ratio() - 0.8).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_usage_report() {
+        let report = UsageReport {
+            model: "claude-sonnet-4-20250514".to_string(),
+            usage: Usage::new(100, 50),
+            latency: Duration::from_secs(2),
+            prompt_version: None,
+        };
+        assert_eq!(report.cache_read_tokens(), 0);
+        assert_eq!(report.output_tokens_per_second(), 25.0);
+    }
+
     #[test]
     fn test_usage_serialize() {
         let usage = Usage::new(100, 50);
@@ -112,4 +236,19 @@ mod tests {
         assert!(!json.contains("cache_creation_input_tokens"));
         assert!(!json.contains("cache_read_input_tokens"));
     }
+
+    #[test]
+    fn test_server_tool_use() {
+        let json = r#"{
+            "input_tokens": 100,
+            "output_tokens": 50,
+            "server_tool_use": {"web_search_requests": 2}
+        }"#;
+        let mut usage: Usage = serde_json::from_str(json).unwrap();
+        assert_eq!(usage.server_tool_use.unwrap().web_search_requests, 2);
+
+        usage.accumulate(&usage.clone());
+        assert_eq!(usage.server_tool_use.unwrap().web_search_requests, 4);
+        assert!(Usage::new(1, 1).server_tool_use.is_none());
+    }
 }
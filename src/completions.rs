@@ -0,0 +1,467 @@
+//! Legacy Text Completions API.
+//!
+//! Client for `/v1/complete`, kept for tooling that still sends raw
+//! `"\n\nHuman: ...\n\nAssistant:"` prompts. New code should use
+//! [`Messages`](crate::messages::request::Messages).
+//!
+//! - [`Completions`] - Client with builder methods, [`post`](Completions::post) and [`post_stream`](Completions::post_stream)
+//! - [`CompletionBody`] - Request body (`prompt`, `max_tokens_to_sample`, ...)
+//! - [`CompletionResponse`] - Generated completion
+//! - [`CompletionStream`] - Streamed completion events
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::completions::Completions;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let mut client = Completions::new();
+//! client
+//!     .model("claude-2.1")
+//!     .max_tokens_to_sample(256)
+//!     .human("Write a haiku about Rust");
+//!
+//! let response = client.post().await?;
+//! println!("{}", response.completion);
+//!
+//! let mut stream = client.post_stream().await?;
+//! while let Some(event) = stream.next().await {
+//!     print!("{}", event?.completion);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::{AnthropicToolError, ErrorResponse, Result};
+use crate::common::transport::{BodyStream, HttpResponse};
+use crate::messages::request::Messages;
+use crate::messages::request::body::Metadata;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Path of the completions endpoint below the base URL
+pub(crate) const COMPLETIONS_PATH: &str = "/v1/complete";
+
+/// Marker that starts a human turn in a prompt
+pub const HUMAN_PROMPT: &str = "\n\nHuman:";
+
+/// Marker that starts the assistant turn in a prompt
+pub const AI_PROMPT: &str = "\n\nAssistant:";
+
+/// Request body for the Text Completions API
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CompletionBody {
+    pub model: String,
+    pub prompt: String,
+    pub max_tokens_to_sample: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+impl CompletionBody {
+    /// Create a request body with model and max_tokens_to_sample
+    pub fn new<T: AsRef<str>>(model: T, max_tokens_to_sample: usize) -> Self {
+        CompletionBody {
+            model: model.as_ref().to_string(),
+            max_tokens_to_sample,
+            ..Default::default()
+        }
+    }
+
+    /// Validate the request body
+    ///
+    /// The prompt must start with [`HUMAN_PROMPT`] and end with [`AI_PROMPT`].
+    pub fn validate(&self) -> Result<()> {
+        if self.model.is_empty() {
+            return Err(AnthropicToolError::MissingRequiredField(
+                "model".to_string(),
+            ));
+        }
+        if self.prompt.is_empty() {
+            return Err(AnthropicToolError::MissingRequiredField(
+                "prompt".to_string(),
+            ));
+        }
+        if !self.prompt.starts_with(HUMAN_PROMPT) {
+            return Err(AnthropicToolError::InvalidParameter(
+                "prompt must start with \"\\n\\nHuman:\"".to_string(),
+            ));
+        }
+        if !self.prompt.trim_end().ends_with(AI_PROMPT) {
+            return Err(AnthropicToolError::InvalidParameter(
+                "prompt must end with \"\\n\\nAssistant:\"".to_string(),
+            ));
+        }
+        if self.max_tokens_to_sample == 0 {
+            return Err(AnthropicToolError::InvalidParameter(
+                "max_tokens_to_sample must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(user_id) = self.metadata.as_ref().and_then(|m| m.user_id.as_ref()) {
+            user_id.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Response of the Text Completions API
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CompletionResponse {
+    /// Unique identifier
+    pub id: String,
+
+    /// Object type (always "completion")
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// Generated text
+    pub completion: String,
+
+    /// Why generation stopped (`stop_sequence` or `max_tokens`)
+    pub stop_reason: Option<String>,
+
+    /// Stop sequence that ended generation, if any
+    #[serde(default)]
+    pub stop: Option<String>,
+
+    /// Model that generated the completion
+    pub model: String,
+}
+
+/// Chunks of a streamed completion
+///
+/// Each item is a [`CompletionResponse`] holding the next piece of text in
+/// `completion`; the last one carries the `stop_reason`. `error` events are
+/// surfaced as `Err` items, after which the stream ends.
+pub struct CompletionStream {
+    source: Box<dyn BodyStream>,
+    buffer: Vec<u8>,
+    pending: VecDeque<Result<CompletionResponse>>,
+    done: bool,
+}
+
+impl CompletionStream {
+    /// Stream chunks from SSE text
+    pub fn from_sse_text<T: AsRef<str>>(text: T) -> Self {
+        Self::from_body(HttpResponse::from_bytes(200, text.as_ref()).body)
+    }
+
+    fn from_body(source: Box<dyn BodyStream>) -> Self {
+        CompletionStream {
+            source,
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Get the next chunk, or `None` once the stream has ended
+    pub async fn next(&mut self) -> Option<Result<CompletionResponse>> {
+        loop {
+            if let Some(chunk) = self.pending.pop_front() {
+                if chunk.is_err() {
+                    self.pending.clear();
+                    self.done = true;
+                }
+                return Some(chunk);
+            }
+            if self.done {
+                return None;
+            }
+            match self.source.next_chunk().await {
+                Ok(Some(bytes)) => {
+                    self.buffer.extend_from_slice(&bytes);
+                    self.drain_lines(false);
+                }
+                Ok(None) => {
+                    self.done = true;
+                    self.drain_lines(true);
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+
+    /// Read the whole stream into the final response, with the text concatenated
+    pub async fn collect(mut self) -> Result<CompletionResponse> {
+        let mut response: Option<CompletionResponse> = None;
+        while let Some(chunk) = self.next().await {
+            let chunk = chunk?;
+            match &mut response {
+                Some(response) => {
+                    response.completion.push_str(&chunk.completion);
+                    if chunk.stop_reason.is_some() {
+                        response.stop_reason = chunk.stop_reason;
+                        response.stop = chunk.stop;
+                    }
+                }
+                None => response = Some(chunk),
+            }
+        }
+        response.ok_or_else(|| {
+            AnthropicToolError::InvalidParameter("stream ended without a completion".to_string())
+        })
+    }
+
+    /// Parse complete lines from the buffer (and the remainder at end of stream)
+    fn drain_lines(&mut self, end_of_stream: bool) {
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            self.parse_line(&line);
+        }
+        if end_of_stream && !self.buffer.is_empty() {
+            let line = std::mem::take(&mut self.buffer);
+            self.parse_line(&line);
+        }
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let Some(data) = line.trim_end_matches(['\r', '\n']).strip_prefix("data: ") else {
+            return;
+        };
+        let chunk = serde_json::from_str::<serde_json::Value>(data).and_then(|event| {
+            match event.get("type").and_then(|t| t.as_str()) {
+                Some("completion") => serde_json::from_value(event).map(Ok).map(Some),
+                Some("error") => serde_json::from_value::<ErrorResponse>(event)
+                    .map(|error| Some(Err(error.into_error()))),
+                _ => Ok(None),
+            }
+        });
+        match chunk {
+            Ok(Some(chunk)) => self.pending.push_back(chunk),
+            Ok(None) => {}
+            Err(err) => self.pending.push_back(Err(err.into())),
+        }
+    }
+}
+
+impl fmt::Debug for CompletionStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompletionStream")
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Client for the legacy Text Completions API
+///
+/// Uses the API key, base URL, transport, and other connection settings of a
+/// [`Messages`] client.
+#[derive(Debug, Clone)]
+pub struct Completions {
+    client: Messages,
+    body: CompletionBody,
+}
+
+impl Default for Completions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Completions {
+    /// Create a client, loading the API key from `ANTHROPIC_API_KEY`
+    pub fn new() -> Self {
+        Completions::from_client(Messages::new())
+    }
+
+    /// Create a client with an explicit API key
+    pub fn with_api_key<T: AsRef<str>>(api_key: T) -> Self {
+        Completions::from_client(Messages::with_api_key(api_key))
+    }
+
+    /// Use the connection settings of a configured Messages client
+    pub fn from_client(client: Messages) -> Self {
+        Completions {
+            client,
+            body: CompletionBody::default(),
+        }
+    }
+
+    /// Get the underlying Messages client
+    pub fn client(&self) -> &Messages {
+        &self.client
+    }
+
+    /// Get a reference to the request body
+    pub fn body(&self) -> &CompletionBody {
+        &self.body
+    }
+
+    /// Set the model
+    pub fn model<T: AsRef<str>>(&mut self, model: T) -> &mut Self {
+        self.body.model = model.as_ref().to_string();
+        self
+    }
+
+    /// Set the raw prompt, including the Human/Assistant markers
+    pub fn prompt<T: AsRef<str>>(&mut self, prompt: T) -> &mut Self {
+        self.body.prompt = prompt.as_ref().to_string();
+        self
+    }
+
+    /// Set the prompt to a single human turn followed by the assistant marker
+    pub fn human<T: AsRef<str>>(&mut self, text: T) -> &mut Self {
+        self.body.prompt = format!("{} {}{}", HUMAN_PROMPT, text.as_ref(), AI_PROMPT);
+        self
+    }
+
+    /// Set the maximum number of tokens to generate
+    pub fn max_tokens_to_sample(&mut self, max_tokens: usize) -> &mut Self {
+        self.body.max_tokens_to_sample = max_tokens;
+        self
+    }
+
+    /// Set the stop sequences
+    pub fn stop_sequences<S: Into<Vec<String>>>(&mut self, sequences: S) -> &mut Self {
+        self.body.stop_sequences = Some(sequences.into());
+        self
+    }
+
+    /// Set the temperature
+    pub fn temperature(&mut self, temperature: f32) -> &mut Self {
+        self.body.temperature = Some(temperature);
+        self
+    }
+
+    /// Set top_p (nucleus sampling)
+    pub fn top_p(&mut self, top_p: f32) -> &mut Self {
+        self.body.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top_k
+    pub fn top_k(&mut self, top_k: usize) -> &mut Self {
+        self.body.top_k = Some(top_k);
+        self
+    }
+
+    /// Set the request metadata
+    pub fn metadata(&mut self, metadata: Metadata) -> &mut Self {
+        self.body.metadata = Some(metadata);
+        self
+    }
+
+    /// Send the request and return the completion
+    pub async fn post(&self) -> Result<CompletionResponse> {
+        self.body.validate()?;
+        self.client
+            .send_json("POST", COMPLETIONS_PATH, Some(&self.body))
+            .await
+    }
+
+    /// Send the request with streaming enabled and return the chunk stream
+    ///
+    /// `stream: true` is set on the request body automatically.
+    pub async fn post_stream(&self) -> Result<CompletionStream> {
+        let mut body = self.body.clone();
+        body.stream = Some(true);
+        body.validate()?;
+        let response = self
+            .client
+            .open_json_stream(COMPLETIONS_PATH, &body)
+            .await?;
+        Ok(CompletionStream::from_body(response.body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::future::{BoxFuture, boxed};
+    use crate::common::transport::{HttpRequest, HttpTransport};
+    use std::sync::{Arc, Mutex};
+
+    const STREAM: &str = "event: completion\n\
+        data: {\"type\":\"completion\",\"id\":\"compl_01\",\"completion\":\" Hello\",\"stop_reason\":null,\"model\":\"claude-2.1\"}\n\n\
+        event: ping\n\
+        data: {\"type\":\"ping\"}\n\n\
+        event: completion\n\
+        data: {\"type\":\"completion\",\"id\":\"compl_01\",\"completion\":\" world\",\"stop_reason\":\"stop_sequence\",\"stop\":\"\\n\\nHuman:\",\"model\":\"claude-2.1\"}\n\n";
+
+    #[derive(Debug, Default)]
+    struct RecordingTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl HttpTransport for RecordingTransport {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            let body = if String::from_utf8_lossy(&request.body).contains("\"stream\":true") {
+                STREAM.to_string()
+            } else {
+                r#"{"type":"completion","id":"compl_01","completion":" Hello world","stop_reason":"max_tokens","model":"claude-2.1"}"#.to_string()
+            };
+            self.requests.lock().unwrap().push(request);
+            boxed(async move { Ok(HttpResponse::from_bytes(200, body)) })
+        }
+    }
+
+    #[test]
+    fn test_validate_prompt() {
+        let mut body = CompletionBody::new("claude-2.1", 256);
+        assert!(body.validate().is_err());
+        body.prompt = "Hello".to_string();
+        assert!(body.validate().is_err());
+        body.prompt = format!("{} Hello", HUMAN_PROMPT);
+        assert!(body.validate().is_err());
+        body.prompt = format!("{} Hello{}", HUMAN_PROMPT, AI_PROMPT);
+        assert!(body.validate().is_ok());
+        body.max_tokens_to_sample = 0;
+        assert!(body.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_post_and_stream() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mut messages = Messages::with_api_key("test_key");
+        messages.transport(Arc::clone(&transport));
+
+        let mut client = Completions::from_client(messages);
+        client
+            .model("claude-2.1")
+            .max_tokens_to_sample(16)
+            .human("Say hello");
+
+        let response = client.post().await.unwrap();
+        assert_eq!(response.completion, " Hello world");
+        assert_eq!(response.stop_reason.as_deref(), Some("max_tokens"));
+
+        let streamed = client.post_stream().await.unwrap().collect().await.unwrap();
+        assert_eq!(streamed.completion, " Hello world");
+        assert_eq!(streamed.stop.as_deref(), Some("\n\nHuman:"));
+
+        let requests = transport.requests.lock().unwrap();
+        assert!(requests[0].url.ends_with("/v1/complete"));
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["prompt"], "\n\nHuman: Say hello\n\nAssistant:");
+        assert_eq!(body["max_tokens_to_sample"], 16);
+        assert!(body.get("stream").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_event() {
+        let mut stream = CompletionStream::from_sse_text(
+            "event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+        );
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(AnthropicToolError::OverloadedError(_)))
+        ));
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,393 @@
+//! Files API.
+//!
+//! Upload a file once and reference it by ID from any number of requests,
+//! instead of re-encoding it as base64 every time:
+//!
+//! - [`Files`] - Client for the `/v1/files` endpoints (upload, list, get, delete)
+//! - [`FileObject`] - Metadata of an uploaded file
+//!
+//! Uploaded files are referenced from `image` and `document` blocks through a
+//! `file` source; see [`FileObject::to_content_block`].
+//!
+//! The client enables the `files-api` beta flag automatically, and so does
+//! [`Messages`] for requests that reference uploaded files.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::files::Files;
+//! use anthropic_tools::messages::request::Messages;
+//! use anthropic_tools::messages::request::attachment::{Attachment, AttachmentKind};
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let files = Files::new();
+//! let file = files.upload_path("report.pdf").await?;
+//! println!("{} ({} bytes, {})", file.id, file.size_bytes, file.mime_type);
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .user_with_attachment("Summarize this", Attachment::file(&file.id, AttachmentKind::Document))?;
+//!
+//! files.delete(&file.id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::beta::Beta;
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::pagination::{ListParams, Page, encode};
+use crate::messages::request::Messages;
+use crate::messages::request::attachment::media_type_from_extension;
+use crate::messages::request::content::ContentBlock;
+use crate::messages::request::dedup::content_hash;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Path of the files endpoint below the base URL
+pub(crate) const FILES_PATH: &str = "/v1/files";
+
+/// MIME type used when none is given or can be guessed
+const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// Metadata of an uploaded file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FileObject {
+    /// Unique identifier, used as `file_id` in content blocks
+    pub id: String,
+
+    /// Object type (always "file")
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// Original file name
+    pub filename: String,
+
+    /// MIME type of the content
+    pub mime_type: String,
+
+    /// Size in bytes
+    pub size_bytes: u64,
+
+    /// Creation time (RFC 3339)
+    pub created_at: String,
+
+    /// Whether the file can be downloaded
+    #[serde(default)]
+    pub downloadable: bool,
+}
+
+impl FileObject {
+    /// Check whether the file is an image
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+
+    /// Content block referencing this file
+    ///
+    /// Images become `image` blocks; everything else becomes a `document` block.
+    pub fn to_content_block(&self) -> ContentBlock {
+        if self.is_image() {
+            ContentBlock::image_from_file_id(&self.id)
+        } else {
+            ContentBlock::document_from_file_id(&self.id)
+        }
+    }
+
+    /// Build a `container_upload` block copying the file into the code
+    /// execution container
+    pub fn to_container_upload(&self) -> ContentBlock {
+        ContentBlock::container_upload(&self.id)
+    }
+}
+
+/// Confirmation of a deleted file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeletedFile {
+    /// ID of the deleted file
+    pub id: String,
+
+    /// Object type (always "file_deleted")
+    #[serde(rename = "type")]
+    pub type_name: String,
+}
+
+/// Client for the Files API
+///
+/// Uses the API key, base URL, transport, and other connection settings of a
+/// [`Messages`] client.
+#[derive(Debug, Clone)]
+pub struct Files {
+    client: Messages,
+}
+
+impl Default for Files {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Files {
+    /// Create a client, loading the API key from `ANTHROPIC_API_KEY`
+    pub fn new() -> Self {
+        Files::from_client(Messages::new())
+    }
+
+    /// Create a client with an explicit API key
+    pub fn with_api_key<T: AsRef<str>>(api_key: T) -> Self {
+        Files::from_client(Messages::with_api_key(api_key))
+    }
+
+    /// Use the connection settings of a configured Messages client
+    pub fn from_client(mut client: Messages) -> Self {
+        client.beta(Beta::FilesApi);
+        Files { client }
+    }
+
+    /// Get the underlying Messages client
+    pub fn client(&self) -> &Messages {
+        &self.client
+    }
+
+    /// Upload file content
+    pub async fn upload<F: AsRef<str>, M: AsRef<str>>(
+        &self,
+        filename: F,
+        data: Vec<u8>,
+        mime_type: M,
+    ) -> Result<FileObject> {
+        let filename = filename.as_ref();
+        if filename.is_empty() {
+            return Err(AnthropicToolError::MissingRequiredField(
+                "filename".to_string(),
+            ));
+        }
+        let mime_type = mime_type.as_ref();
+        let recorded = serde_json::json!({
+            "filename": filename,
+            "mime_type": mime_type,
+            "sha256": content_hash(&data),
+        });
+        let boundary = format!("anthropic-tools-{}", &content_hash(&data)[..32]);
+        let body = multipart_body(&boundary, filename, mime_type, &data);
+        self.client
+            .send_bytes(
+                "POST",
+                FILES_PATH,
+                body,
+                &format!("multipart/form-data; boundary={}", boundary),
+                recorded,
+            )
+            .await
+    }
+
+    /// List uploaded files, most recent first
+    pub async fn list(&self, params: &ListParams) -> Result<Page<FileObject>> {
+        let path = format!("{}{}", FILES_PATH, params.query());
+        self.client.send_json("GET", &path, None::<&()>).await
+    }
+
+    /// Get the metadata of an uploaded file
+    pub async fn get(&self, file_id: &str) -> Result<FileObject> {
+        self.client
+            .send_json("GET", &file_path(file_id), None::<&()>)
+            .await
+    }
+
+    /// Delete an uploaded file
+    pub async fn delete(&self, file_id: &str) -> Result<DeletedFile> {
+        self.client
+            .send_json("DELETE", &file_path(file_id), None::<&()>)
+            .await
+    }
+
+    /// Upload a file from disk, guessing the MIME type from its extension
+    pub async fn upload_path<P: AsRef<Path>>(&self, path: P) -> Result<FileObject> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)?;
+        let filename = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("upload");
+        let mime_type = path
+            .to_str()
+            .and_then(media_type_from_extension)
+            .unwrap_or_else(|| DEFAULT_MIME_TYPE.to_string());
+        self.upload(filename, data, mime_type).await
+    }
+}
+
+/// Path of a single file
+fn file_path(file_id: &str) -> String {
+    format!("{}/{}", FILES_PATH, encode(file_id))
+}
+
+/// Encode a single file as a `multipart/form-data` body
+fn multipart_body(boundary: &str, filename: &str, mime_type: &str, data: &[u8]) -> Vec<u8> {
+    let filename = filename.replace(['"', '\r', '\n'], "_");
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        boundary, filename, mime_type
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::future::{BoxFuture, boxed};
+    use crate::common::transport::{HttpRequest, HttpResponse, HttpTransport};
+    use std::sync::{Arc, Mutex};
+
+    const FILE_JSON: &str = r#"{"id":"file_011","type":"file","filename":"notes \"v2\".txt","mime_type":"text/plain",
+        "size_bytes":5,"created_at":"2025-01-01T00:00:00Z","downloadable":false}"#;
+
+    /// Answers with queued bodies, then with [`FILE_JSON`]
+    #[derive(Debug, Default)]
+    struct RecordingTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+        responses: Mutex<Vec<String>>,
+    }
+
+    impl HttpTransport for RecordingTransport {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            self.requests.lock().unwrap().push(request);
+            let mut responses = self.responses.lock().unwrap();
+            let body = if responses.is_empty() {
+                FILE_JSON.to_string()
+            } else {
+                responses.remove(0)
+            };
+            boxed(async move { Ok(HttpResponse::from_bytes(200, body)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_multipart() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+
+        let files = Files::from_client(client);
+        let file = files
+            .upload("notes \"v2\".txt", b"hello".to_vec(), "text/plain")
+            .await
+            .unwrap();
+        assert_eq!(file.id, "file_011");
+        assert_eq!(file.size_bytes, 5);
+
+        let requests = transport.requests.lock().unwrap();
+        let request = &requests[0];
+        assert!(request.url.ends_with("/v1/files"));
+        assert!(
+            request
+                .header("anthropic-beta")
+                .unwrap()
+                .contains("files-api")
+        );
+        let content_type = request.header("content-type").unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+
+        let body = String::from_utf8(request.body.clone()).unwrap();
+        assert!(body.starts_with(&format!("--{}\r\n", boundary)));
+        assert!(body.contains("filename=\"notes _v2_.txt\""));
+        assert!(body.contains("Content-Type: text/plain\r\n\r\nhello\r\n"));
+        assert!(body.ends_with(&format!("--{}--\r\n", boundary)));
+    }
+
+    #[tokio::test]
+    async fn test_attachment_uploading() {
+        use crate::messages::request::attachment::Attachment;
+
+        let transport = Arc::new(RecordingTransport::default());
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+        let files = Files::from_client(client);
+
+        // Small attachments are inlined
+        let block = Attachment::bytes(b"hi".to_vec(), "text/plain")
+            .into_content_block_uploading(&files, 4)
+            .await
+            .unwrap();
+        assert!(matches!(
+            block,
+            ContentBlock::Document { ref source, .. } if source.data.is_some()
+        ));
+        assert!(transport.requests.lock().unwrap().is_empty());
+
+        // Large ones are uploaded and referenced by ID
+        let block = Attachment::bytes(b"hello".to_vec(), "text/plain")
+            .into_content_block_uploading(&files, 4)
+            .await
+            .unwrap();
+        assert!(matches!(
+            block,
+            ContentBlock::Document { ref source, .. } if source.file_id.as_deref() == Some("file_011")
+        ));
+        assert_eq!(transport.requests.lock().unwrap().len(), 1);
+
+        // Local files are read and uploaded the same way
+        let path =
+            std::env::temp_dir().join(format!("anthropic-tools-upload-{}.txt", std::process::id()));
+        std::fs::write(&path, b"hello").unwrap();
+        let block = Attachment::path(&path)
+            .into_content_block_uploading(&files, 4)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            block,
+            ContentBlock::Document { ref source, .. } if source.file_id.as_deref() == Some("file_011")
+        ));
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(
+            requests
+                .iter()
+                .all(|request| request.url.ends_with("/v1/files"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_requires_filename() {
+        let files = Files::with_api_key("test_key");
+        assert!(files.upload("", Vec::new(), "text/plain").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_get_delete() {
+        let transport = Arc::new(RecordingTransport::default());
+        transport.responses.lock().unwrap().extend([
+            format!(r#"{{"data":[{}],"has_more":false}}"#, FILE_JSON),
+            FILE_JSON.to_string(),
+            r#"{"id":"file_011","type":"file_deleted"}"#.to_string(),
+        ]);
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+        let files = Files::from_client(client);
+
+        let page = files.list(&ListParams::new()).await.unwrap();
+        assert_eq!(page.data[0].id, "file_011");
+        assert!(page.next_params(None).is_none());
+        let file = files.get("file_011").await.unwrap();
+        assert!(matches!(
+            file.to_content_block(),
+            ContentBlock::Document { ref source, .. } if source.file_id.as_deref() == Some("file_011")
+        ));
+        assert_eq!(
+            files.delete("file_011").await.unwrap().type_name,
+            "file_deleted"
+        );
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert!(requests[0].url.ends_with("/v1/files"));
+        assert!(requests[1].url.ends_with("/v1/files/file_011"));
+        assert_eq!(requests[2].method, "DELETE");
+    }
+}
@@ -0,0 +1,79 @@
+//! Process-wide default client.
+//!
+//! Small tools and examples often need a single client configured once from
+//! the environment. Instead of threading a [`Messages`] value through every
+//! function signature, call [`client()`] to get a fresh copy of the global
+//! default:
+//!
+//! - [`client()`] - Clone of the global default client
+//! - [`set_client()`] - Replace the global default (e.g. in tests)
+//!
+//! The default is created lazily on first use with [`Messages::new()`], which
+//! reads `ANTHROPIC_API_KEY` from the environment.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::global;
+//! use anthropic_tools::prelude::*;
+//!
+//! // Configure shared defaults once at startup
+//! let mut defaults = Messages::with_api_key("test_key");
+//! defaults.model("claude-sonnet-4-20250514").max_tokens(1024);
+//! global::set_client(defaults);
+//!
+//! // Anywhere else in the program
+//! let mut client = global::client();
+//! client.user("Hello!");
+//! assert_eq!(client.body().model, "claude-sonnet-4-20250514");
+//! ```
+
+use crate::messages::request::Messages;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+static GLOBAL_CLIENT: OnceLock<RwLock<Messages>> = OnceLock::new();
+
+/// Get the global client slot, initializing it from the environment on first use
+fn global() -> &'static RwLock<Messages> {
+    GLOBAL_CLIENT.get_or_init(|| RwLock::new(Messages::new()))
+}
+
+/// Get a copy of the global default client
+///
+/// Each call returns an independent clone, so builder calls on the returned
+/// client never affect other callers.
+pub fn client() -> Messages {
+    global()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Replace the global default client
+///
+/// Subsequent calls to [`client()`] return copies of `client`.
+pub fn set_client(client: Messages) {
+    *global().write().unwrap_or_else(PoisonError::into_inner) = client;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_get_client() {
+        let mut defaults = Messages::with_api_key("test_key");
+        defaults.model("claude-sonnet-4-20250514").max_tokens(256);
+        set_client(defaults);
+
+        let mut first = client();
+        first.user("Hello!");
+        let second = client();
+
+        assert_eq!(first.body().model, "claude-sonnet-4-20250514");
+        assert_eq!(first.body().max_tokens, 256);
+        assert_eq!(first.body().messages.len(), 1);
+        // Clones are independent of each other
+        assert!(second.body().messages.is_empty());
+    }
+}
@@ -10,6 +10,20 @@
 //! - Prompt caching support
 //! - Streaming support (planned)
 //!
+//! ## Minimum Supported Rust Version
+//!
+//! The MSRV is declared as `rust-version` in `Cargo.toml`. Async extension
+//! points are plain traits returning
+//! [`BoxFuture`](common::future::BoxFuture), so no `async-trait` dependency or
+//! newer compiler features are needed to implement them.
+//!
+//! ## WebAssembly
+//!
+//! The crate builds for `wasm32-unknown-unknown` using the `fetch` backend of
+//! `reqwest`. Build with `default-features = false` to drop the `image`
+//! decoder; pool settings are ignored and [`BoxFuture`](common::future::BoxFuture)
+//! is not `Send` on that target.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -30,27 +44,56 @@
 //! }
 //! ```
 
+// Lets `::anthropic_tools` paths generated by the derive macros resolve inside this crate
+extern crate self as anthropic_tools;
+
+pub mod admin;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod capabilities;
 pub mod common;
+pub mod completions;
+pub mod files;
+pub mod global;
 pub mod messages;
+pub mod models;
+pub mod tools;
+
+// Dependencies of the code generated by the `derive` macros
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use serde_json;
+}
 
 /// Commonly used types and traits
 pub mod prelude {
+    // Beta flags
+    pub use crate::common::beta::Beta;
+
     // Error types
     pub use crate::common::errors::{AnthropicToolError, Result};
 
+    // Model IDs
+    pub use crate::common::model::Model;
+
     // Usage
-    pub use crate::common::usage::Usage;
+    pub use crate::common::usage::{Usage, UsageReport};
 
     // Tool definitions
-    pub use crate::common::tool::{CacheControl, JsonSchema, PropertyDef, Tool};
+    pub use crate::common::tool::{CacheControl, JsonSchema, PropertyDef, Tool, ToolDefinition};
 
     // Messages API
     pub use crate::messages::request::{
-        body::{Body, Metadata, ToolChoice},
+        Messages,
+        attachment::{Attachment, AttachmentKind, LazyAttachment},
+        body::{Body, MaxTokensPolicy, Metadata, OutputFormat, ToolChoice, UserId},
         content::{ContentBlock, DocumentSource, ImageSource, MediaType},
-        message::{Message, SystemBlock, SystemPrompt},
+        count_tokens::TokenCount,
+        message::{Message, SystemBlock, SystemPrompt, SystemPromptBuilder},
         role::Role,
-        Messages,
+        sampling::{Temperature, TopK, TopP},
+        stop::StopSequences,
     };
 
     // Response types
@@ -58,11 +101,12 @@ pub mod prelude {
 
     // Streaming types
     pub use crate::messages::streaming::{
-        Delta, MessageDelta, StreamAccumulator, StreamEvent,
+        Delta, EventStream, MessageDelta, StreamAccumulator, StreamEvent,
     };
 }
 
 // Re-export main types at crate level
+pub use capabilities::capabilities;
 pub use common::{AnthropicToolError, Result, Tool, Usage};
 pub use messages::request::Messages;
 pub use messages::response::Response;
@@ -0,0 +1,774 @@
+//! Message Batches API.
+//!
+//! Batches process large numbers of Messages requests asynchronously at half
+//! the usual cost. Each request in a batch is a full request [`Body`] tagged
+//! with a caller-chosen `custom_id`, used to match results to requests.
+//!
+//! - [`BatchRequest`] - Requests to submit, built from [`BatchItem`]s
+//! - [`Batches`] - Client for the `/v1/messages/batches` endpoints
+//! - [`MessageBatch`] - Batch status returned by the API
+//! - [`PollOptions`] - Polling interval and backoff for [`Batches::wait_until_done`]
+//! - [`BatchResults`] - Streaming parser for the JSONL results file
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::messages::batches::{BatchRequest, Batches};
+//! use anthropic_tools::messages::request::body::Body;
+//! use anthropic_tools::messages::request::message::Message;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let mut batch = BatchRequest::new();
+//! for (i, question) in ["What is Rust?", "What is Go?"].iter().enumerate() {
+//!     let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+//!     body.messages.push(Message::user(*question));
+//!     batch.add(format!("question-{}", i), body);
+//! }
+//!
+//! let batches = Batches::new();
+//! let created = batches.create(&batch).await?;
+//! println!("{} is {}", created.id, created.processing_status);
+//!
+//! let done = batches.wait_until_done(&created.id, &Default::default()).await?;
+//! println!("{} succeeded", done.request_counts.succeeded);
+//!
+//! let mut results = batches.results(&done).await?;
+//! while let Some(entry) = results.next().await {
+//!     let entry = entry?;
+//!     if let Some(response) = entry.result.message() {
+//!         println!("{}: {}", entry.custom_id, response.get_text());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::{AnthropicToolError, ErrorResponse, Result};
+use crate::common::pagination::{ListParams, Page, encode};
+use crate::common::runtime;
+use crate::common::transport::{BodyStream, HttpResponse};
+use crate::messages::request::Messages;
+use crate::messages::request::body::Body;
+use crate::messages::response::Response;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+use strum::{Display, EnumString};
+use web_time::Instant;
+
+/// Path of the batches endpoint below the base URL
+pub(crate) const BATCHES_PATH: &str = "/v1/messages/batches";
+
+/// Maximum number of requests in one batch
+pub const MAX_BATCH_REQUESTS: usize = 100_000;
+
+/// Maximum length of a custom ID
+pub const MAX_CUSTOM_ID_LEN: usize = 64;
+
+/// One request in a batch
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchItem {
+    /// Caller-chosen ID, unique within the batch
+    pub custom_id: String,
+
+    /// Messages API request parameters
+    pub params: Body,
+}
+
+impl BatchItem {
+    /// Create a batch item
+    pub fn new<T: AsRef<str>>(custom_id: T, params: Body) -> Self {
+        BatchItem {
+            custom_id: custom_id.as_ref().to_string(),
+            params,
+        }
+    }
+
+    /// Validate the custom ID and request parameters
+    pub fn validate(&self) -> Result<()> {
+        if self.custom_id.is_empty()
+            || self.custom_id.len() > MAX_CUSTOM_ID_LEN
+            || !self
+                .custom_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "custom_id '{}' must be 1-{} characters of letters, digits, '_' or '-'",
+                self.custom_id, MAX_CUSTOM_ID_LEN
+            )));
+        }
+        if self.params.stream == Some(true) {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "batch request '{}' cannot use streaming",
+                self.custom_id
+            )));
+        }
+        self.params.validate()
+    }
+}
+
+/// Requests submitted as one batch
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BatchRequest {
+    pub requests: Vec<BatchItem>,
+}
+
+impl BatchRequest {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        BatchRequest::default()
+    }
+
+    /// Add a request with its custom ID
+    pub fn add<T: AsRef<str>>(&mut self, custom_id: T, params: Body) -> &mut Self {
+        self.requests.push(BatchItem::new(custom_id, params));
+        self
+    }
+
+    /// Add a prepared batch item
+    pub fn add_item(&mut self, item: BatchItem) -> &mut Self {
+        self.requests.push(item);
+        self
+    }
+
+    /// Get the number of requests
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Check if the batch has no requests
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Validate every request and check that custom IDs are unique
+    pub fn validate(&self) -> Result<()> {
+        if self.requests.is_empty() {
+            return Err(AnthropicToolError::MissingRequiredField(
+                "requests".to_string(),
+            ));
+        }
+        if self.requests.len() > MAX_BATCH_REQUESTS {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "a batch holds at most {} requests, got {}",
+                MAX_BATCH_REQUESTS,
+                self.requests.len()
+            )));
+        }
+        let mut seen = HashSet::new();
+        for item in &self.requests {
+            item.validate()?;
+            if !seen.insert(item.custom_id.as_str()) {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "duplicate custom_id '{}'",
+                    item.custom_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Processing status of a batch
+#[derive(Serialize, Deserialize, Debug, Clone, Display, EnumString, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProcessingStatus {
+    /// Requests are still being processed
+    InProgress,
+
+    /// Cancellation was requested and is in progress
+    Canceling,
+
+    /// Every request has finished; results are available
+    Ended,
+}
+
+/// Number of requests in each state
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestCounts {
+    pub processing: usize,
+    pub succeeded: usize,
+    pub errored: usize,
+    pub canceled: usize,
+    pub expired: usize,
+}
+
+impl RequestCounts {
+    /// Get the total number of requests
+    pub fn total(&self) -> usize {
+        self.processing + self.succeeded + self.errored + self.canceled + self.expired
+    }
+}
+
+/// Message batch as returned by the API
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MessageBatch {
+    /// Unique identifier of the batch
+    pub id: String,
+
+    /// Object type (always "message_batch")
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// Processing status
+    pub processing_status: ProcessingStatus,
+
+    /// Request counts by state
+    pub request_counts: RequestCounts,
+
+    /// Creation time (RFC 3339)
+    pub created_at: String,
+
+    /// Time after which unfinished requests expire (RFC 3339)
+    pub expires_at: String,
+
+    /// Time processing ended (RFC 3339)
+    #[serde(default)]
+    pub ended_at: Option<String>,
+
+    /// Time cancellation was requested (RFC 3339)
+    #[serde(default)]
+    pub cancel_initiated_at: Option<String>,
+
+    /// Time the batch was archived (RFC 3339)
+    #[serde(default)]
+    pub archived_at: Option<String>,
+
+    /// URL of the results file, once processing has ended
+    #[serde(default)]
+    pub results_url: Option<String>,
+}
+
+impl MessageBatch {
+    /// Check whether processing has ended
+    pub fn is_ended(&self) -> bool {
+        self.processing_status == ProcessingStatus::Ended
+    }
+}
+
+/// Outcome of one request in a batch
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchResult {
+    /// The request succeeded
+    Succeeded { message: Response },
+
+    /// The request failed
+    Errored { error: ErrorResponse },
+
+    /// The batch was canceled before the request was processed
+    Canceled,
+
+    /// The batch expired before the request was processed
+    Expired,
+}
+
+impl BatchResult {
+    /// Get the response of a succeeded request
+    pub fn message(&self) -> Option<&Response> {
+        match self {
+            BatchResult::Succeeded { message } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Check whether the request succeeded
+    pub fn is_succeeded(&self) -> bool {
+        matches!(self, BatchResult::Succeeded { .. })
+    }
+}
+
+/// One line of a batch results file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BatchResultEntry {
+    /// Custom ID of the request
+    pub custom_id: String,
+
+    /// Outcome of the request
+    pub result: BatchResult,
+}
+
+/// Streaming parser for a JSONL batch results file
+///
+/// Entries are parsed as chunks arrive, so results files of any size are
+/// processed in constant memory. A malformed line is yielded as an `Err`
+/// without ending the stream.
+pub struct BatchResults {
+    source: Box<dyn BodyStream>,
+    buffer: Vec<u8>,
+    pending: VecDeque<Result<BatchResultEntry>>,
+    done: bool,
+}
+
+impl BatchResults {
+    /// Parse entries from the body of an HTTP response
+    pub fn from_response(response: HttpResponse) -> Self {
+        BatchResults {
+            source: response.body,
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Parse entries from JSONL text (e.g. a saved results file)
+    pub fn from_jsonl_text<T: AsRef<str>>(text: T) -> Self {
+        Self::from_response(HttpResponse::from_bytes(200, text.as_ref()))
+    }
+
+    /// Get the next entry, or `None` at the end of the file
+    pub async fn next(&mut self) -> Option<Result<BatchResultEntry>> {
+        loop {
+            if let Some(entry) = self.pending.pop_front() {
+                return Some(entry);
+            }
+            if self.done {
+                return None;
+            }
+            match self.source.next_chunk().await {
+                Ok(Some(chunk)) => {
+                    self.buffer.extend_from_slice(&chunk);
+                    self.drain_lines(false);
+                }
+                Ok(None) => {
+                    self.done = true;
+                    self.drain_lines(true);
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+
+    /// Read every entry into a vector
+    pub async fn collect(mut self) -> Result<Vec<BatchResultEntry>> {
+        let mut entries = Vec::new();
+        while let Some(entry) = self.next().await {
+            entries.push(entry?);
+        }
+        Ok(entries)
+    }
+
+    /// Parse complete lines from the buffer (and the remainder at the end)
+    fn drain_lines(&mut self, end_of_stream: bool) {
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            self.parse_line(&line);
+        }
+        if end_of_stream && !self.buffer.is_empty() {
+            let line = std::mem::take(&mut self.buffer);
+            self.parse_line(&line);
+        }
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return;
+        }
+        self.pending
+            .push_back(serde_json::from_slice(line).map_err(Into::into));
+    }
+}
+
+impl fmt::Debug for BatchResults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchResults")
+            .field("buffered", &self.buffer.len())
+            .field("pending", &self.pending.len())
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+/// Polling schedule for [`Batches::wait_until_done`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollOptions {
+    /// Delay before the second status request
+    pub interval: Duration,
+
+    /// Factor the delay grows by after each request (1.0 polls at a fixed interval)
+    pub backoff: f64,
+
+    /// Upper bound of the delay
+    pub max_interval: Duration,
+
+    /// Give up after this long (`None` waits until the batch ends)
+    pub timeout: Option<Duration>,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        PollOptions {
+            interval: Duration::from_secs(10),
+            backoff: 1.5,
+            max_interval: Duration::from_secs(300),
+            timeout: None,
+        }
+    }
+}
+
+impl PollOptions {
+    /// Set the initial delay
+    pub fn interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the backoff factor (values below 1.0 are treated as 1.0)
+    pub fn backoff(&mut self, backoff: f64) -> &mut Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the upper bound of the delay
+    pub fn max_interval(&mut self, max_interval: Duration) -> &mut Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Set the overall time limit
+    pub fn timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Delay following the given one
+    fn next_interval(&self, current: Duration) -> Duration {
+        current
+            .mul_f64(self.backoff.max(1.0))
+            .min(self.max_interval)
+    }
+}
+
+/// Client for the Message Batches API
+///
+/// Uses the API key, base URL, transport, and other connection settings of a
+/// [`Messages`] client.
+#[derive(Debug, Clone, Default)]
+pub struct Batches {
+    client: Messages,
+}
+
+impl Batches {
+    /// Create a client, loading the API key from `ANTHROPIC_API_KEY`
+    pub fn new() -> Self {
+        Batches::from_client(Messages::new())
+    }
+
+    /// Create a client with an explicit API key
+    pub fn with_api_key<T: AsRef<str>>(api_key: T) -> Self {
+        Batches::from_client(Messages::with_api_key(api_key))
+    }
+
+    /// Use the connection settings of a configured Messages client
+    pub fn from_client(client: Messages) -> Self {
+        Batches { client }
+    }
+
+    /// Get the underlying Messages client
+    pub fn client(&self) -> &Messages {
+        &self.client
+    }
+
+    /// Submit a batch of requests
+    pub async fn create(&self, batch: &BatchRequest) -> Result<MessageBatch> {
+        batch.validate()?;
+        self.client
+            .send_json("POST", BATCHES_PATH, Some(batch))
+            .await
+    }
+
+    /// Get the current status of a batch
+    pub async fn get(&self, batch_id: &str) -> Result<MessageBatch> {
+        self.client
+            .send_json("GET", &batch_path(batch_id, ""), None::<&()>)
+            .await
+    }
+
+    /// List batches, most recent first
+    pub async fn list(&self, params: &ListParams) -> Result<Page<MessageBatch>> {
+        let path = format!("{}{}", BATCHES_PATH, params.query());
+        self.client.send_json("GET", &path, None::<&()>).await
+    }
+
+    /// Request cancellation of a batch
+    ///
+    /// The batch moves to `canceling` until requests already being processed finish.
+    pub async fn cancel(&self, batch_id: &str) -> Result<MessageBatch> {
+        self.client
+            .send_json("POST", &batch_path(batch_id, "/cancel"), None::<&()>)
+            .await
+    }
+
+    /// Stream the results of an ended batch from its `results_url`
+    pub async fn results(&self, batch: &MessageBatch) -> Result<BatchResults> {
+        let url = batch.results_url.as_deref().ok_or_else(|| {
+            AnthropicToolError::InvalidParameter(format!(
+                "batch {} has no results yet ({})",
+                batch.id, batch.processing_status
+            ))
+        })?;
+        let response = self.client.open_download(url).await?;
+        Ok(BatchResults::from_response(response))
+    }
+
+    /// Stream the results of an ended batch by ID
+    ///
+    /// Uses the configured base URL, which suits gateways that do not expose
+    /// the host in `results_url`.
+    pub async fn results_by_id(&self, batch_id: &str) -> Result<BatchResults> {
+        let response = self
+            .client
+            .open_download(&batch_path(batch_id, "/results"))
+            .await?;
+        Ok(BatchResults::from_response(response))
+    }
+
+    /// Poll a batch until processing has ended and return its final status
+    pub async fn wait_until_done(
+        &self,
+        batch_id: &str,
+        options: &PollOptions,
+    ) -> Result<MessageBatch> {
+        let started = Instant::now();
+        let mut interval = options.interval;
+        loop {
+            let batch = self.get(batch_id).await?;
+            if batch.is_ended() {
+                return Ok(batch);
+            }
+
+            let mut delay = interval;
+            if let Some(timeout) = options.timeout {
+                let remaining = timeout.saturating_sub(started.elapsed());
+                if remaining.is_zero() {
+                    return Err(AnthropicToolError::InvalidParameter(format!(
+                        "batch {} still {} after {:?}",
+                        batch_id, batch.processing_status, timeout
+                    )));
+                }
+                delay = delay.min(remaining);
+            }
+            runtime::sleep(delay).await;
+            interval = options.next_interval(interval);
+        }
+    }
+}
+
+/// Path of a single batch, with an optional action suffix
+fn batch_path(batch_id: &str, suffix: &str) -> String {
+    format!("{}/{}{}", BATCHES_PATH, encode(batch_id), suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::future::{BoxFuture, boxed};
+    use crate::common::transport::{HttpRequest, HttpResponse, HttpTransport};
+    use crate::messages::request::message::Message;
+    use std::sync::{Arc, Mutex};
+
+    /// Answers with queued bodies, then with an in-progress batch
+    #[derive(Debug, Default)]
+    struct RecordingTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+        responses: Mutex<Vec<String>>,
+    }
+
+    impl HttpTransport for RecordingTransport {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            self.requests.lock().unwrap().push(request);
+            let mut responses = self.responses.lock().unwrap();
+            let body = if responses.is_empty() {
+                batch_json("in_progress")
+            } else {
+                responses.remove(0)
+            };
+            boxed(async move { Ok(HttpResponse::from_bytes(200, body)) })
+        }
+    }
+
+    fn batch_json(status: &str) -> String {
+        serde_json::json!({
+            "id": "msgbatch_01", "type": "message_batch", "processing_status": status,
+            "request_counts": {"processing": 2, "succeeded": 0, "errored": 0, "canceled": 0, "expired": 0},
+            "created_at": "2025-01-01T00:00:00Z", "expires_at": "2025-01-02T00:00:00Z",
+            "ended_at": null, "cancel_initiated_at": null, "archived_at": null, "results_url": null
+        })
+        .to_string()
+    }
+
+    fn body(text: &str) -> Body {
+        let mut body = Body::new("claude-sonnet-4-20250514", 256);
+        body.messages.push(Message::user(text));
+        body
+    }
+
+    #[test]
+    fn test_batch_validation() {
+        let mut batch = BatchRequest::new();
+        assert!(batch.validate().is_err());
+
+        batch.add("q-1", body("One")).add("q_2", body("Two"));
+        assert!(batch.validate().is_ok());
+
+        batch.add("q-1", body("Again"));
+        assert!(batch.validate().is_err());
+
+        let mut invalid = BatchRequest::new();
+        invalid.add("has space", body("One"));
+        assert!(invalid.validate().is_err());
+
+        let mut streaming = body("One");
+        streaming.stream = Some(true);
+        assert!(BatchItem::new("q-1", streaming).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_batch() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+
+        let mut batch = BatchRequest::new();
+        batch.add("q-1", body("One")).add("q-2", body("Two"));
+        let created = Batches::from_client(client).create(&batch).await.unwrap();
+        assert_eq!(created.id, "msgbatch_01");
+        assert_eq!(created.processing_status, ProcessingStatus::InProgress);
+        assert_eq!(created.request_counts.total(), 2);
+
+        let requests = transport.requests.lock().unwrap();
+        assert!(requests[0].url.ends_with("/v1/messages/batches"));
+        let sent: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(sent["requests"][1]["custom_id"], "q-2");
+        assert_eq!(sent["requests"][1]["params"]["max_tokens"], 256);
+    }
+
+    #[tokio::test]
+    async fn test_get_list_cancel() {
+        let transport = Arc::new(RecordingTransport::default());
+        transport.responses.lock().unwrap().extend([
+            batch_json("in_progress"),
+            format!(
+                r#"{{"data":[{}],"has_more":true,"first_id":"msgbatch_01","last_id":"msgbatch_01"}}"#,
+                batch_json("ended")
+            ),
+            batch_json("canceling"),
+        ]);
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+        let batches = Batches::from_client(client);
+
+        assert!(!batches.get("msgbatch_01").await.unwrap().is_ended());
+        let mut params = ListParams::new();
+        params.limit(1);
+        let page = batches.list(&params).await.unwrap();
+        assert!(page.data[0].is_ended());
+        assert_eq!(
+            page.next_params(Some(1)).unwrap().query(),
+            "?limit=1&after_id=msgbatch_01"
+        );
+        let canceled = batches.cancel("msgbatch_01").await.unwrap();
+        assert_eq!(canceled.processing_status, ProcessingStatus::Canceling);
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert!(
+            requests[0]
+                .url
+                .ends_with("/v1/messages/batches/msgbatch_01")
+        );
+        assert!(requests[1].url.ends_with("/v1/messages/batches?limit=1"));
+        assert_eq!(requests[2].method, "POST");
+        assert!(
+            requests[2]
+                .url
+                .ends_with("/v1/messages/batches/msgbatch_01/cancel")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_done() {
+        let transport = Arc::new(RecordingTransport::default());
+        transport.responses.lock().unwrap().extend([
+            batch_json("in_progress"),
+            batch_json("canceling"),
+            batch_json("ended"),
+        ]);
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+        let batches = Batches::from_client(client);
+
+        let mut options = PollOptions::default();
+        options
+            .interval(Duration::from_millis(1))
+            .backoff(2.0)
+            .max_interval(Duration::from_millis(3));
+        assert_eq!(
+            options.next_interval(Duration::from_millis(2)),
+            Duration::from_millis(3)
+        );
+        let done = batches
+            .wait_until_done("msgbatch_01", &options)
+            .await
+            .unwrap();
+        assert!(done.is_ended());
+        assert_eq!(transport.requests.lock().unwrap().len(), 3);
+
+        options.timeout(Some(Duration::from_millis(5)));
+        assert!(
+            batches
+                .wait_until_done("msgbatch_01", &options)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_results_stream() {
+        let jsonl = concat!(
+            r#"{"custom_id":"q-1","result":{"type":"succeeded","message":{"id":"msg_1","type":"message","role":"assistant","content":[{"type":"text","text":"Hi"}],"model":"claude-sonnet-4-20250514","stop_reason":"end_turn","usage":{"input_tokens":3,"output_tokens":1}}}}"#,
+            "\n",
+            r#"{"custom_id":"q-2","result":{"type":"errored","error":{"type":"error","error":{"type":"invalid_request_error","message":"Bad"}}}}"#,
+            "\n\nnot json\n",
+            r#"{"custom_id":"q-3","result":{"type":"expired"}}"#,
+        );
+
+        let transport = Arc::new(RecordingTransport::default());
+        transport.responses.lock().unwrap().push(jsonl.to_string());
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+        let batches = Batches::from_client(client);
+
+        let mut results = batches.results_by_id("msgbatch_01").await.unwrap();
+        let first = results.next().await.unwrap().unwrap();
+        assert_eq!(first.custom_id, "q-1");
+        assert_eq!(first.result.message().unwrap().get_text(), "Hi");
+        match results.next().await.unwrap().unwrap().result {
+            BatchResult::Errored { error } => assert_eq!(error.error.message, "Bad"),
+            other => panic!("Expected Errored, got {:?}", other),
+        }
+        assert!(results.next().await.unwrap().is_err());
+        let last = results.next().await.unwrap().unwrap();
+        assert!(matches!(last.result, BatchResult::Expired));
+        assert!(results.next().await.is_none());
+
+        let pending: MessageBatch = serde_json::from_str(&batch_json("in_progress")).unwrap();
+        assert!(batches.results(&pending).await.is_err());
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests[0].method, "GET");
+        assert!(
+            requests[0]
+                .url
+                .ends_with("/v1/messages/batches/msgbatch_01/results")
+        );
+        assert!(requests[0].header("x-api-key").is_some());
+    }
+}
@@ -2,9 +2,11 @@
 //!
 //! This module provides the main interface for interacting with the Anthropic Messages API:
 //!
+//! - [`batches`] - Message Batches API for asynchronous bulk requests
 //! - [`request`] - Request types and the [`Messages`](request::Messages) client
 //! - [`response`] - Response types including [`Response`](response::Response)
 //! - [`streaming`] - SSE streaming support
+//! - `otel` - OpenTelemetry GenAI spans (feature `opentelemetry`)
 //!
 //! # Basic Usage
 //!
@@ -52,6 +54,9 @@
 //! }
 //! ```
 
+pub mod batches;
+#[cfg(feature = "opentelemetry")]
+pub mod otel;
 pub mod request;
 pub mod response;
 pub mod streaming;
@@ -0,0 +1,219 @@
+//! OpenTelemetry GenAI semantic-convention spans for Messages API calls.
+//!
+//! Available with the `opentelemetry` cargo feature. Every call made by
+//! [`Messages::post`](crate::messages::request::Messages::post) creates a client
+//! span named `chat {model}` on the global tracer provider, carrying the
+//! standard `gen_ai.*` attributes:
+//!
+//! - `gen_ai.system`, `gen_ai.operation.name`
+//! - `gen_ai.request.model`, `gen_ai.request.max_tokens`, `gen_ai.request.temperature`,
+//!   `gen_ai.request.top_p`, `gen_ai.request.top_k`, `gen_ai.request.stop_sequences`
+//! - `gen_ai.response.id`, `gen_ai.response.model`, `gen_ai.response.finish_reasons`
+//! - `gen_ai.usage.input_tokens`, `gen_ai.usage.output_tokens`
+//! - `error.type` when the call fails
+//!
+//! Configure an OpenTelemetry SDK tracer provider in your application and the
+//! spans are exported to any GenAI-aware observability backend.
+
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::messages::request::body::Body;
+use crate::messages::response::Response;
+use opentelemetry::trace::{Span, SpanKind, Status, Tracer};
+use opentelemetry::{Array, KeyValue, StringValue, Value, global};
+
+/// Instrumentation scope name used for the tracer
+pub const TRACER_NAME: &str = "anthropic-tools";
+
+/// Value of the `gen_ai.system` attribute
+const GEN_AI_SYSTEM: &str = "anthropic";
+
+/// Value of the `gen_ai.operation.name` attribute
+const GEN_AI_OPERATION: &str = "chat";
+
+/// Span covering a single Messages API call
+pub(crate) struct RequestSpan {
+    span: global::BoxedSpan,
+}
+
+impl RequestSpan {
+    /// Start a client span for the request on the global tracer
+    pub(crate) fn start(body: &Body) -> Self {
+        let tracer = global::tracer(TRACER_NAME);
+        let span = tracer
+            .span_builder(format!("{} {}", GEN_AI_OPERATION, body.model))
+            .with_kind(SpanKind::Client)
+            .with_attributes(request_attributes(body))
+            .start(&tracer);
+        RequestSpan { span }
+    }
+
+    /// Record the outcome of the request and end the span
+    pub(crate) fn finish(mut self, result: &Result<Response>) {
+        match result {
+            Ok(response) => {
+                for attribute in response_attributes(response) {
+                    self.span.set_attribute(attribute);
+                }
+            }
+            Err(error) => {
+                self.span.set_attribute(error_attribute(error));
+                self.span.set_status(Status::error(error.to_string()));
+            }
+        }
+        self.span.end();
+    }
+}
+
+/// Build the `gen_ai.request.*` attributes for a request body
+pub fn request_attributes(body: &Body) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new("gen_ai.system", GEN_AI_SYSTEM),
+        KeyValue::new("gen_ai.operation.name", GEN_AI_OPERATION),
+        KeyValue::new("gen_ai.request.model", body.model.clone()),
+        KeyValue::new("gen_ai.request.max_tokens", body.max_tokens as i64),
+    ];
+    if let Some(temperature) = body.temperature {
+        attributes.push(KeyValue::new(
+            "gen_ai.request.temperature",
+            temperature.value() as f64,
+        ));
+    }
+    if let Some(top_p) = body.top_p {
+        attributes.push(KeyValue::new("gen_ai.request.top_p", top_p.value() as f64));
+    }
+    if let Some(top_k) = body.top_k {
+        attributes.push(KeyValue::new("gen_ai.request.top_k", top_k.value() as i64));
+    }
+    if let Some(stop_sequences) = &body.stop_sequences {
+        attributes.push(KeyValue::new(
+            "gen_ai.request.stop_sequences",
+            string_array(stop_sequences.iter().cloned()),
+        ));
+    }
+    attributes
+}
+
+/// Build the `gen_ai.response.*` and `gen_ai.usage.*` attributes for a response
+pub fn response_attributes(response: &Response) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new("gen_ai.response.id", response.id.clone()),
+        KeyValue::new("gen_ai.response.model", response.model.clone()),
+        KeyValue::new(
+            "gen_ai.usage.input_tokens",
+            response.usage.input_tokens as i64,
+        ),
+        KeyValue::new(
+            "gen_ai.usage.output_tokens",
+            response.usage.output_tokens as i64,
+        ),
+    ];
+    if let Some(stop_reason) = &response.stop_reason {
+        attributes.push(KeyValue::new(
+            "gen_ai.response.finish_reasons",
+            string_array([stop_reason_value(stop_reason)]),
+        ));
+    }
+    attributes
+}
+
+/// Build the `error.type` attribute for a failed request
+pub fn error_attribute(error: &AnthropicToolError) -> KeyValue {
+    KeyValue::new("error.type", error.error_type().to_string())
+}
+
+/// Serialize a stop reason the way the API reports it (e.g. `end_turn`)
+fn stop_reason_value<T: serde::Serialize>(stop_reason: &T) -> String {
+    serde_json::to_value(stop_reason)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Convert strings into an OpenTelemetry string array value
+fn string_array<I: IntoIterator<Item = String>>(values: I) -> Value {
+    Value::Array(Array::String(
+        values.into_iter().map(StringValue::from).collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Usage;
+    use crate::messages::request::content::ContentBlock;
+    use crate::messages::request::role::Role;
+    use crate::messages::request::sampling::Temperature;
+    use crate::messages::response::StopReason;
+
+    fn find<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a Value> {
+        attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| &kv.value)
+    }
+
+    #[test]
+    fn test_request_attributes() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 512);
+        body.temperature = Some(Temperature::new(0.5).unwrap());
+        body.stop_sequences = Some(vec!["END".to_string()]);
+
+        let attributes = request_attributes(&body);
+        assert_eq!(
+            find(&attributes, "gen_ai.system"),
+            Some(&Value::from("anthropic"))
+        );
+        assert_eq!(
+            find(&attributes, "gen_ai.request.model"),
+            Some(&Value::from("claude-sonnet-4-20250514"))
+        );
+        assert_eq!(
+            find(&attributes, "gen_ai.request.max_tokens"),
+            Some(&Value::I64(512))
+        );
+        assert_eq!(
+            find(&attributes, "gen_ai.request.temperature"),
+            Some(&Value::F64(0.5))
+        );
+        assert!(find(&attributes, "gen_ai.request.top_p").is_none());
+        assert!(find(&attributes, "gen_ai.request.stop_sequences").is_some());
+    }
+
+    #[test]
+    fn test_response_attributes() {
+        let response = Response {
+            id: "msg_123".to_string(),
+            type_name: "message".to_string(),
+            role: Role::Assistant,
+            content: vec![ContentBlock::text("Hi")],
+            model: "claude-sonnet-4-20250514".to_string(),
+            stop_reason: Some(StopReason::EndTurn),
+            stop_sequence: None,
+            usage: Usage::new(10, 5),
+            container: None,
+            context_management: None,
+        };
+
+        let attributes = response_attributes(&response);
+        assert_eq!(
+            find(&attributes, "gen_ai.response.id"),
+            Some(&Value::from("msg_123"))
+        );
+        assert_eq!(
+            find(&attributes, "gen_ai.usage.input_tokens"),
+            Some(&Value::I64(10))
+        );
+        assert_eq!(
+            find(&attributes, "gen_ai.response.finish_reasons"),
+            Some(&string_array(["end_turn".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_error_attribute() {
+        let error = AnthropicToolError::OverloadedError("busy".to_string());
+        let attribute = error_attribute(&error);
+        assert_eq!(attribute.key.as_str(), "error.type");
+        assert_eq!(attribute.value, Value::from("overloaded_error"));
+    }
+}
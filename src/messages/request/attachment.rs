@@ -0,0 +1,505 @@
+//! Binary attachments for messages.
+//!
+//! This module provides the [`Attachment`] type, a single entry point for
+//! arbitrary user uploads (images, PDFs, plain-text files):
+//!
+//! - [`Attachment`] - File path, in-memory bytes, URL, or uploaded file reference
+//! - [`AttachmentKind`] - Content block family an attachment is routed to
+//! - [`LazyAttachment`] - Attachment loaded only when the request is sent
+//!
+//! Attachments are converted into the matching [`ContentBlock`] automatically:
+//! images become `image` blocks, PDFs and text files become `document` blocks.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::attachment::Attachment;
+//! use anthropic_tools::messages::request::content::ContentBlock;
+//!
+//! let attachment = Attachment::url("https://example.com/report.pdf");
+//! let block = attachment.into_content_block().unwrap();
+//! assert!(matches!(block, ContentBlock::Document { .. }));
+//!
+//! let attachment = Attachment::bytes(b"plain notes".to_vec(), "text/plain");
+//! let block = attachment.into_content_block().unwrap();
+//! assert!(matches!(block, ContentBlock::Document { .. }));
+//! ```
+//!
+//! # Size Limits
+//!
+//! Inline attachments are base64-encoded into the request body. Anything larger
+//! than [`DEFAULT_INLINE_LIMIT`] (or the limit passed to
+//! [`Attachment::into_content_block_with_limit`]) is rejected there.
+//! [`Attachment::into_content_block_uploading`] uploads such attachments
+//! through the Files API instead and references them by `file_id`.
+//!
+//! # Deferred Loading
+//!
+//! A [`LazyAttachment`] wraps a closure (or async loader) that produces the
+//! attachment. Nothing is read or encoded until [`Messages::post`] is called, so
+//! building many candidate prompts stays cheap:
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::Messages;
+//! use anthropic_tools::messages::request::attachment::{Attachment, LazyAttachment};
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .user_with_lazy_attachment(
+//!         "Summarize this report",
+//!         LazyAttachment::new(|| Ok(Attachment::path("report.pdf"))),
+//!     );
+//! ```
+//!
+//! [`Messages::post`]: crate::messages::request::Messages::post
+
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::future::{BoxFuture, MaybeSend, MaybeSync};
+use crate::common::runtime;
+use crate::files::Files;
+use crate::messages::request::content::{ContentBlock, DocumentSource, ImageSource, MediaType};
+use base64::prelude::*;
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Default maximum size (in bytes) of an attachment that is inlined as base64
+pub const DEFAULT_INLINE_LIMIT: usize = 5 * 1024 * 1024;
+
+/// Content block family an attachment is routed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentKind {
+    /// Routed to an `image` content block
+    Image,
+    /// Routed to a `document` content block
+    Document,
+}
+
+/// Binary attachment (image, PDF, or text file) from any source
+#[derive(Debug, Clone)]
+pub enum Attachment {
+    /// Local file, read when converted into a content block
+    Path(PathBuf),
+
+    /// In-memory data with an explicit MIME type
+    Bytes { data: Vec<u8>, media_type: String },
+
+    /// Remote file referenced by URL
+    Url(String),
+
+    /// File previously uploaded through the Files API
+    File {
+        file_id: String,
+        kind: AttachmentKind,
+    },
+}
+
+impl Attachment {
+    /// Create an attachment from a local file path
+    pub fn path<P: AsRef<Path>>(path: P) -> Self {
+        Attachment::Path(path.as_ref().to_path_buf())
+    }
+
+    /// Create an attachment from in-memory bytes and a MIME type
+    pub fn bytes<T: AsRef<str>>(data: Vec<u8>, media_type: T) -> Self {
+        Attachment::Bytes {
+            data,
+            media_type: media_type.as_ref().to_string(),
+        }
+    }
+
+    /// Create an attachment from a URL
+    pub fn url<T: AsRef<str>>(url: T) -> Self {
+        Attachment::Url(url.as_ref().to_string())
+    }
+
+    /// Create an attachment referencing an uploaded file
+    pub fn file<T: AsRef<str>>(file_id: T, kind: AttachmentKind) -> Self {
+        Attachment::File {
+            file_id: file_id.as_ref().to_string(),
+            kind,
+        }
+    }
+
+    /// Get the MIME type of the attachment, if it can be determined
+    pub fn media_type(&self) -> Option<String> {
+        match self {
+            Attachment::Path(path) => media_type_from_extension(path.to_str()?),
+            Attachment::Bytes { media_type, .. } => Some(media_type.clone()),
+            Attachment::Url(url) => media_type_from_extension(url),
+            Attachment::File { .. } => None,
+        }
+    }
+
+    /// Get the content block family this attachment is routed to
+    pub fn kind(&self) -> Result<AttachmentKind> {
+        if let Attachment::File { kind, .. } = self {
+            return Ok(*kind);
+        }
+        match self.media_type() {
+            Some(media_type) => kind_from_media_type(&media_type),
+            // URLs without a recognizable extension are treated as images
+            None if matches!(self, Attachment::Url(_)) => Ok(AttachmentKind::Image),
+            None => Err(AnthropicToolError::InvalidParameter(
+                "cannot determine attachment media type".to_string(),
+            )),
+        }
+    }
+
+    /// Get the size of the attachment data in bytes (local sources only)
+    pub fn size(&self) -> Result<Option<u64>> {
+        match self {
+            Attachment::Path(path) => Ok(Some(std::fs::metadata(path)?.len())),
+            Attachment::Bytes { data, .. } => Ok(Some(data.len() as u64)),
+            Attachment::Url(_) | Attachment::File { .. } => Ok(None),
+        }
+    }
+
+    /// Check whether the attachment is too large to be inlined as base64
+    pub fn should_upload(&self, inline_limit: usize) -> Result<bool> {
+        Ok(self.size()?.is_some_and(|size| size > inline_limit as u64))
+    }
+
+    /// Convert into a content block using [`DEFAULT_INLINE_LIMIT`]
+    pub fn into_content_block(self) -> Result<ContentBlock> {
+        self.into_content_block_with_limit(DEFAULT_INLINE_LIMIT)
+    }
+
+    /// Convert into a content block, rejecting inline data above `inline_limit` bytes
+    ///
+    /// Use [`into_content_block_uploading`](Self::into_content_block_uploading)
+    /// to upload large attachments instead.
+    pub fn into_content_block_with_limit(self, inline_limit: usize) -> Result<ContentBlock> {
+        if self.should_upload(inline_limit)? {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "attachment exceeds the inline limit of {} bytes; upload it via the Files API",
+                inline_limit
+            )));
+        }
+
+        let kind = self.kind()?;
+        let media_type = self.media_type();
+        let block = match self {
+            Attachment::Path(path) => {
+                inline_block(kind, media_type.unwrap_or_default(), std::fs::read(path)?)?
+            }
+            Attachment::Bytes { data, media_type } => inline_block(kind, media_type, data)?,
+            Attachment::Url(url) => match kind {
+                AttachmentKind::Image => ContentBlock::image_from_url(url),
+                AttachmentKind::Document => ContentBlock::document_from_url(url),
+            },
+            Attachment::File { file_id, kind } => match kind {
+                AttachmentKind::Image => ContentBlock::Image {
+                    source: ImageSource::from_file_id(file_id),
+                    cache_control: None,
+                },
+                AttachmentKind::Document => ContentBlock::Document {
+                    source: DocumentSource::from_file_id(file_id),
+                    cache_control: None,
+                    title: None,
+                    context: None,
+                    citations: None,
+                },
+            },
+        };
+        Ok(block)
+    }
+
+    /// Convert into a content block, uploading data above `inline_limit` bytes
+    ///
+    /// Local attachments larger than the limit are uploaded with `files` and
+    /// referenced by `file_id`; everything else is converted like
+    /// [`into_content_block`](Self::into_content_block). Files are read with
+    /// [`runtime::read_file`], so the executor is not blocked. Uploaded files
+    /// stay in the Files API until deleted.
+    pub async fn into_content_block_uploading(
+        self,
+        files: &Files,
+        inline_limit: usize,
+    ) -> Result<ContentBlock> {
+        let kind = self.kind()?;
+        let media_type = self.media_type().unwrap_or_default();
+        let (filename, data) = match self {
+            Attachment::Path(path) => {
+                let filename = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("attachment")
+                    .to_string();
+                (filename, runtime::read_file(&path).await?)
+            }
+            Attachment::Bytes { data, .. } => ("attachment".to_string(), data),
+            // Only local data has a size
+            other => return other.into_content_block_with_limit(inline_limit),
+        };
+        if data.len() <= inline_limit {
+            return inline_block(kind, media_type, data);
+        }
+        let file = files.upload(filename, data, media_type).await?;
+        Attachment::file(file.id, kind).into_content_block()
+    }
+}
+
+/// Future returned by a [`LazyAttachment`] loader
+pub type AttachmentFuture = BoxFuture<'static, Result<Attachment>>;
+
+/// Loader shared by clones of a [`LazyAttachment`]
+#[cfg(not(target_arch = "wasm32"))]
+type Loader = dyn Fn() -> AttachmentFuture + Send + Sync;
+
+/// Loader shared by clones of a [`LazyAttachment`]
+#[cfg(target_arch = "wasm32")]
+type Loader = dyn Fn() -> AttachmentFuture;
+
+/// Attachment whose data is loaded and encoded only when the request is sent
+///
+/// Loaders must be `Send + Sync` (and their futures `Send`) on native
+/// targets only; on `wasm32` they may hold JS values.
+#[derive(Clone)]
+pub struct LazyAttachment {
+    loader: Arc<Loader>,
+}
+
+impl LazyAttachment {
+    /// Create a lazy attachment from a synchronous loader
+    pub fn new<F>(loader: F) -> Self
+    where
+        F: Fn() -> Result<Attachment> + MaybeSend + MaybeSync + 'static,
+    {
+        LazyAttachment {
+            loader: Arc::new(move || {
+                let attachment = loader();
+                Box::pin(async move { attachment })
+            }),
+        }
+    }
+
+    /// Create a lazy attachment from an async loader
+    pub fn from_async<F, Fut>(loader: F) -> Self
+    where
+        F: Fn() -> Fut + MaybeSend + MaybeSync + 'static,
+        Fut: Future<Output = Result<Attachment>> + MaybeSend + 'static,
+    {
+        LazyAttachment {
+            loader: Arc::new(move || Box::pin(loader())),
+        }
+    }
+
+    /// Run the loader and convert the attachment into a content block
+    pub async fn load(&self) -> Result<ContentBlock> {
+        (self.loader)().await?.into_content_block()
+    }
+}
+
+impl fmt::Debug for LazyAttachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyAttachment").finish_non_exhaustive()
+    }
+}
+
+impl From<Attachment> for LazyAttachment {
+    fn from(attachment: Attachment) -> Self {
+        LazyAttachment::new(move || Ok(attachment.clone()))
+    }
+}
+
+impl TryFrom<Attachment> for ContentBlock {
+    type Error = AnthropicToolError;
+
+    fn try_from(attachment: Attachment) -> Result<Self> {
+        attachment.into_content_block()
+    }
+}
+
+/// Build a base64 content block from raw attachment data
+fn inline_block(kind: AttachmentKind, media_type: String, data: Vec<u8>) -> Result<ContentBlock> {
+    let block = match kind {
+        AttachmentKind::Image => {
+            let media_type = MediaType::from_str(&media_type).map_err(|_| {
+                AnthropicToolError::InvalidParameter(format!(
+                    "unsupported image media type: {}",
+                    media_type
+                ))
+            })?;
+            if let Some(detected) = MediaType::detect(&data)
+                && detected != media_type
+            {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "image data is {} but was labeled {}",
+                    detected, media_type
+                )));
+            }
+            ContentBlock::image_from_base64(media_type, BASE64_STANDARD.encode(data))
+        }
+        AttachmentKind::Document if media_type == "text/plain" => {
+            let text = String::from_utf8(data).map_err(|_| {
+                AnthropicToolError::InvalidParameter(
+                    "text attachment is not valid UTF-8".to_string(),
+                )
+            })?;
+            ContentBlock::document_from_text(text)
+        }
+        AttachmentKind::Document => ContentBlock::Document {
+            source: DocumentSource::from_base64(BASE64_STANDARD.encode(data)),
+            cache_control: None,
+            title: None,
+            context: None,
+            citations: None,
+        },
+    };
+    Ok(block)
+}
+
+/// Guess a MIME type from a path or URL extension
+pub(crate) fn media_type_from_extension(path: &str) -> Option<String> {
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let ext = Path::new(path).extension()?.to_str()?.to_lowercase();
+    let media_type = match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "txt" | "md" => "text/plain",
+        _ => return None,
+    };
+    Some(media_type.to_string())
+}
+
+/// Map a MIME type to the content block family that accepts it
+fn kind_from_media_type(media_type: &str) -> Result<AttachmentKind> {
+    match media_type {
+        "image/png" | "image/jpeg" | "image/gif" | "image/webp" => Ok(AttachmentKind::Image),
+        "application/pdf" | "text/plain" => Ok(AttachmentKind::Document),
+        _ => Err(AnthropicToolError::InvalidParameter(format!(
+            "unsupported attachment media type: {}",
+            media_type
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_routing() {
+        let block = Attachment::url("https://example.com/doc.pdf?v=1")
+            .into_content_block()
+            .unwrap();
+        assert!(matches!(block, ContentBlock::Document { .. }));
+
+        let block = Attachment::url("https://example.com/photo.jpg")
+            .into_content_block()
+            .unwrap();
+        assert!(matches!(block, ContentBlock::Image { .. }));
+    }
+
+    #[test]
+    fn test_bytes_image() {
+        let block = Attachment::bytes(vec![1, 2, 3], "image/png")
+            .into_content_block()
+            .unwrap();
+        let json = serde_json::to_string(&block).unwrap();
+        assert!(json.contains("\"type\":\"image\""));
+        assert!(json.contains("\"media_type\":\"image/png\""));
+        assert!(json.contains("\"data\":\"AQID\""));
+    }
+
+    #[test]
+    fn test_bytes_text_document() {
+        let block = Attachment::bytes(b"hello".to_vec(), "text/plain")
+            .into_content_block()
+            .unwrap();
+        let json = serde_json::to_string(&block).unwrap();
+        assert!(json.contains("\"type\":\"document\""));
+        assert!(json.contains("\"media_type\":\"text/plain\""));
+        assert!(json.contains("\"data\":\"hello\""));
+    }
+
+    #[test]
+    fn test_file_id_source() {
+        let block = Attachment::file("file_abc", AttachmentKind::Document)
+            .into_content_block()
+            .unwrap();
+        let json = serde_json::to_string(&block).unwrap();
+        assert!(json.contains("\"type\":\"file\""));
+        assert!(json.contains("\"file_id\":\"file_abc\""));
+    }
+
+    #[test]
+    fn test_inline_limit() {
+        let attachment = Attachment::bytes(vec![0; 16], "application/pdf");
+        assert!(attachment.should_upload(8).unwrap());
+        assert!(!attachment.should_upload(16).unwrap());
+        assert!(attachment.into_content_block_with_limit(8).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lazy_attachment_sync() {
+        let lazy = LazyAttachment::new(|| Ok(Attachment::url("https://example.com/a.png")));
+        let block = lazy.load().await.unwrap();
+        assert!(matches!(block, ContentBlock::Image { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_lazy_attachment_async() {
+        let lazy = LazyAttachment::from_async(|| async {
+            Ok(Attachment::bytes(b"notes".to_vec(), "text/plain"))
+        });
+        let block = lazy.load().await.unwrap();
+        assert!(matches!(block, ContentBlock::Document { .. }));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_lazy_attachment_is_send_and_sync_on_native() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        let lazy = LazyAttachment::from_async(|| async { Ok(Attachment::url("a.png")) });
+        assert_send_sync(&lazy);
+    }
+
+    #[tokio::test]
+    async fn test_messages_resolve_lazy_attachments() {
+        use crate::messages::request::Messages;
+
+        let mut client = Messages::with_api_key("test_key");
+        client
+            .user_with_lazy_attachment(
+                "Describe this",
+                Attachment::url("https://example.com/a.png").into(),
+            )
+            .add_lazy_attachment(Attachment::url("https://example.com/b.pdf").into());
+
+        // Nothing is loaded until the body is resolved
+        assert_eq!(client.body().messages[0].content.len(), 1);
+
+        let body = client.resolve_body().await.unwrap();
+        let content = &body.messages[0].content;
+        assert_eq!(content.len(), 3);
+        assert!(matches!(content[0], ContentBlock::Image { .. }));
+        assert!(matches!(content[1], ContentBlock::Text { .. }));
+        assert!(matches!(content[2], ContentBlock::Document { .. }));
+    }
+
+    #[test]
+    fn test_mislabeled_image() {
+        let jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0];
+        let err = Attachment::bytes(jpeg, "image/png")
+            .into_content_block()
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("image data is image/jpeg but was labeled image/png")
+        );
+    }
+
+    #[test]
+    fn test_unsupported_media_type() {
+        let attachment = Attachment::bytes(vec![0], "application/zip");
+        assert!(attachment.kind().is_err());
+    }
+}
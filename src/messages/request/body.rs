@@ -6,6 +6,8 @@
 //! - [`Body`] - Complete request body with all parameters
 //! - [`ToolChoice`] - Configuration for tool selection behavior
 //! - [`Metadata`] - Optional request metadata
+//! - [`UserId`] - Opaque end-user identifier for `metadata.user_id`
+//! - [`OutputFormat`] - JSON schema the response must follow
 //!
 //! # Request Parameters
 //!
@@ -16,11 +18,13 @@
 //!
 //! Optional:
 //! - `system` - System prompt
-//! - `temperature` - Sampling temperature (0.0-1.0)
-//! - `top_p`, `top_k` - Sampling parameters
+//! - `temperature` - Sampling temperature (0.0-1.0, see [`Temperature`])
+//! - `top_p`, `top_k` - Sampling parameters (see [`TopP`], [`TopK`])
 //! - `stop_sequences` - Custom stop sequences
 //! - `tools` - Available tools for function calling
 //! - `stream` - Enable streaming responses
+//! - `output_format` - Structured output schema (beta)
+//! - `context_management` - Automatic clearing of old context (beta)
 //!
 //! # Example
 //!
@@ -31,10 +35,70 @@
 //! assert_eq!(body.model, "claude-sonnet-4-20250514");
 //! assert_eq!(body.max_tokens, 1024);
 //! ```
+//!
+//! # User IDs
+//!
+//! `metadata.user_id` should be an opaque identifier, never an email address
+//! or other personal data. [`UserId::hashed`] derives one with salted SHA-256:
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::body::{Metadata, UserId};
+//!
+//! let metadata = Metadata::hashed_user_id("alice@example.com", "app-salt");
+//! let user_id = metadata.user_id.unwrap();
+//! assert_eq!(user_id.as_str().len(), 64);
+//!
+//! assert!(UserId::new("alice@example.com").is_err());
+//! assert!(UserId::new("user-42").is_ok());
+//! ```
+//!
+//! # Saved Requests
+//!
+//! [`Body::to_json_file`] and [`Body::from_json_file`] store a request as
+//! sent to the API, so prompts and parameters can be kept under version
+//! control and replayed with
+//! [`Messages::from_body`](crate::messages::request::Messages::from_body).
 
+use crate::common::beta::Beta;
 use crate::common::errors::{AnthropicToolError, Result};
-use crate::messages::request::{mcp::McpServer, message::Message, message::SystemPrompt};
+use crate::common::model::Model;
+use crate::common::tool::{JsonSchema, ToolDefinition, ToolInput};
+use crate::messages::request::content::{CacheControl, ContentBlock};
+use crate::messages::request::context_management::ContextManagement;
+use crate::messages::request::image_check::validate_images;
+use crate::messages::request::mcp::McpServer;
+use crate::messages::request::message::{self, Message, SystemPrompt};
+use crate::messages::request::role::Role;
+use crate::messages::request::sampling::{Temperature, TopK, TopP};
+use crate::messages::request::stop::StopSequences;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+/// Maximum length of a user ID
+pub const MAX_USER_ID_LEN: usize = 256;
+
+/// Wire names of the fields modeled by [`Body`], which `extra` must not repeat
+const BODY_FIELDS: &[&str] = &[
+    "model",
+    "messages",
+    "max_tokens",
+    "system",
+    "temperature",
+    "top_p",
+    "top_k",
+    "stop_sequences",
+    "stream",
+    "tools",
+    "tool_choice",
+    "metadata",
+    "container",
+    "mcp_servers",
+    "output_format",
+    "context_management",
+];
 
 /// Request body for the Messages API
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -42,7 +106,8 @@ pub struct Body {
     /// The model to use (e.g., "claude-sonnet-4-20250514")
     pub model: String,
 
-    /// Input messages for the conversation
+    /// Input messages for the conversation (annotations are not serialized)
+    #[serde(serialize_with = "message::serialize_for_api")]
     pub messages: Vec<Message>,
 
     /// Maximum number of tokens to generate (required)
@@ -54,15 +119,15 @@ pub struct Body {
 
     /// Sampling temperature (0.0 to 1.0)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub temperature: Option<f32>,
+    pub temperature: Option<Temperature>,
 
     /// Top-p sampling parameter
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub top_p: Option<f32>,
+    pub top_p: Option<TopP>,
 
     /// Top-k sampling parameter
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub top_k: Option<u32>,
+    pub top_k: Option<TopK>,
 
     /// Custom stop sequences
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -74,7 +139,7 @@ pub struct Body {
 
     /// Tools available to the model
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tools: Option<Vec<serde_json::Value>>,
+    pub tools: Option<Vec<ToolDefinition>>,
 
     /// Tool choice configuration
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -91,15 +156,118 @@ pub struct Body {
     /// MCP servers configuration (beta)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mcp_servers: Option<Vec<McpServer>>,
+
+    /// Structured output format (beta)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+
+    /// Context management configuration (beta)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_management: Option<ContextManagement>,
+
+    /// Parameters this crate does not model yet, sent as top-level fields
+    #[serde(flatten, default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// What to do when `max_tokens` exceeds the output limit of the model
+///
+/// Limits come from [`Model::max_output_tokens`]; models missing from the
+/// table are never checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MaxTokensPolicy {
+    /// Send `max_tokens` as is
+    #[default]
+    Allow,
+    /// Fail with `InvalidParameter` before sending
+    Reject,
+    /// Lower `max_tokens` to the limit
+    Clamp,
+}
+
+/// Format of the response text (structured outputs)
+///
+/// With a JSON schema, the text of the response is JSON matching it; parse it
+/// with [`Response::parse_output`](crate::messages::response::Response::parse_output).
+/// Objects in the schema are closed with `additionalProperties: false` unless
+/// they say otherwise, as the API requires.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutputFormat {
+    #[serde(rename = "type")]
+    pub type_name: String, // "json_schema"
+
+    pub schema: serde_json::Value,
+}
+
+impl OutputFormat {
+    /// Require JSON matching a schema
+    ///
+    /// Never panics: a `JsonSchema` holds only strings, string-keyed maps,
+    /// and JSON values, which always serialize.
+    pub fn json_schema(schema: &JsonSchema) -> Self {
+        let schema = serde_json::to_value(schema).expect("JsonSchema serializes to JSON");
+        OutputFormat::from_value(schema)
+    }
+
+    /// Require JSON matching a schema given as JSON
+    pub fn from_value(mut schema: serde_json::Value) -> Self {
+        close_objects(&mut schema);
+        OutputFormat {
+            type_name: "json_schema".to_string(),
+            schema,
+        }
+    }
+
+    /// Require JSON matching the schema of a [`ToolInput`] type
+    pub fn from_input<T: ToolInput>() -> Self {
+        OutputFormat::json_schema(&T::input_schema())
+    }
+
+    /// Require JSON matching the schema of a `schemars::JsonSchema` type
+    #[cfg(feature = "schemars")]
+    pub fn from_schema<T: schemars::JsonSchema>() -> Result<Self> {
+        let schema = crate::common::tool::schema_for::<T>()?;
+        Ok(OutputFormat::from_value(schema))
+    }
+}
+
+/// Add `additionalProperties: false` to every object schema that lacks it
+fn close_objects(schema: &mut serde_json::Value) {
+    let Some(object) = schema.as_object_mut() else {
+        if let Some(items) = schema.as_array_mut() {
+            items.iter_mut().for_each(close_objects);
+        }
+        return;
+    };
+    // `JsonSchema` serializes the keyword in snake case
+    if let Some(value) = object.remove("additional_properties") {
+        object.insert("additionalProperties".to_string(), value);
+    }
+    if object.get("type").and_then(serde_json::Value::as_str) == Some("object")
+        && !object.contains_key("additionalProperties")
+    {
+        object.insert("additionalProperties".to_string(), false.into());
+    }
+    for (key, value) in object.iter_mut() {
+        if !matches!(key.as_str(), "enum" | "const" | "default" | "examples") {
+            close_objects(value);
+        }
+    }
 }
 
 /// Tool choice configuration
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// With `disable_parallel_tool_use`, `Auto` and `Any` let the model call at
+/// most one tool per response. The flag is omitted from the JSON when unset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum ToolChoice {
     /// Let the model decide whether to use tools
     #[serde(rename = "auto")]
-    Auto,
+    Auto {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
 
     /// Force the model to use a specific tool
     #[serde(rename = "tool")]
@@ -107,19 +275,156 @@ pub enum ToolChoice {
 
     /// Force the model to use any tool
     #[serde(rename = "any")]
-    Any,
+    Any {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
 
     /// Disable tool use
     #[serde(rename = "none")]
     None,
 }
 
+impl ToolChoice {
+    /// Let the model decide whether to use tools
+    pub fn auto() -> Self {
+        ToolChoice::Auto {
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Force the model to use any tool
+    pub fn any() -> Self {
+        ToolChoice::Any {
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Force the model to use a specific tool
+    pub fn tool<S: AsRef<str>>(name: S) -> Self {
+        ToolChoice::Tool {
+            name: name.as_ref().to_string(),
+        }
+    }
+
+    /// Allow at most one tool call per response (`Auto` and `Any` only)
+    pub fn disable_parallel_tool_use(self) -> Self {
+        match self {
+            ToolChoice::Auto { .. } => ToolChoice::Auto {
+                disable_parallel_tool_use: Some(true),
+            },
+            ToolChoice::Any { .. } => ToolChoice::Any {
+                disable_parallel_tool_use: Some(true),
+            },
+            other => other,
+        }
+    }
+}
+
 /// Request metadata
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Metadata {
-    /// User ID for tracking
+    /// Opaque end-user identifier for abuse detection
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
+}
+
+impl Metadata {
+    /// Create metadata with a checked user ID
+    pub fn user_id<T: AsRef<str>>(user_id: T) -> Result<Self> {
+        Ok(Metadata {
+            user_id: Some(UserId::new(user_id)?),
+        })
+    }
+
+    /// Create metadata with a user ID hashed from a raw identifier
+    pub fn hashed_user_id<T: AsRef<str>, S: AsRef<str>>(raw_id: T, salt: S) -> Self {
+        Metadata {
+            user_id: Some(UserId::hashed(raw_id, salt)),
+        }
+    }
+}
+
+/// Opaque end-user identifier sent as `metadata.user_id`
+///
+/// Allowed characters are ASCII letters, digits, `-`, `_`, `.`, and `:`, up to
+/// [`MAX_USER_ID_LEN`] characters, so emails, names, and phone numbers are
+/// rejected. Use [`UserId::hashed`] to derive an ID from such values.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct UserId(String);
+
+impl UserId {
+    /// Create a user ID, rejecting values that are not opaque identifiers
+    pub fn new<T: AsRef<str>>(user_id: T) -> Result<Self> {
+        let user_id = UserId(user_id.as_ref().to_string());
+        user_id.validate()?;
+        Ok(user_id)
+    }
+
+    /// Create a user ID from the salted SHA-256 hash of a raw identifier
+    ///
+    /// The result is 64 lowercase hex characters. Use a per-application
+    /// secret salt so the hash cannot be reversed with a dictionary.
+    pub fn hashed<T: AsRef<str>, S: AsRef<str>>(raw_id: T, salt: S) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(salt.as_ref().as_bytes());
+        hasher.update(raw_id.as_ref().as_bytes());
+        let hex = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        UserId(hex)
+    }
+
+    /// Create a user ID without checking it (checked by [`Body::validate`])
+    pub(crate) fn unchecked<T: AsRef<str>>(user_id: T) -> Self {
+        UserId(user_id.as_ref().to_string())
+    }
+
+    /// Check the length and character set
+    pub fn validate(&self) -> Result<()> {
+        if self.0.is_empty() || self.0.len() > MAX_USER_ID_LEN {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "user_id must be 1 to {} characters",
+                MAX_USER_ID_LEN
+            )));
+        }
+        if self.0.contains('@') {
+            return Err(AnthropicToolError::InvalidParameter(
+                "user_id looks like an email address; use UserId::hashed instead".to_string(),
+            ));
+        }
+        if let Some(c) = self
+            .0
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')))
+        {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "user_id contains invalid character {:?}",
+                c
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the ID as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<UserId> for String {
+    fn from(user_id: UserId) -> Self {
+        user_id.0
+    }
 }
 
 impl Default for Body {
@@ -139,6 +444,9 @@ impl Default for Body {
             metadata: None,
             container: None,
             mcp_servers: None,
+            output_format: None,
+            context_management: None,
+            extra: serde_json::Map::new(),
         }
     }
 }
@@ -153,6 +461,178 @@ impl Body {
         }
     }
 
+    /// Check whether any message block, including blocks nested in tool
+    /// results, satisfies `predicate`
+    fn any_block(&self, predicate: impl Fn(&ContentBlock) -> bool + Copy) -> bool {
+        fn visit(block: &ContentBlock, predicate: impl Fn(&ContentBlock) -> bool + Copy) -> bool {
+            predicate(block)
+                || matches!(block, ContentBlock::ToolResult { content: Some(content), .. }
+                    if content.iter().any(|block| visit(block, predicate)))
+        }
+        self.messages
+            .iter()
+            .flat_map(|message| &message.content)
+            .any(|block| visit(block, predicate))
+    }
+
+    /// Check whether any block references a file uploaded with the Files API
+    ///
+    /// Covers `file` image and document sources, `container_upload` blocks,
+    /// and blocks nested in tool results.
+    pub fn references_files(&self) -> bool {
+        self.any_block(|block| match block {
+            ContentBlock::Image { source, .. } => source.type_name == "file",
+            ContentBlock::Document { source, .. } => source.type_name == "file",
+            ContentBlock::ContainerUpload { .. } => true,
+            _ => false,
+        })
+    }
+
+    /// Check whether any tool, system block, or content block is cached for 1 hour
+    pub fn uses_1h_cache(&self) -> bool {
+        let tools = self
+            .tools
+            .iter()
+            .flatten()
+            .any(|tool| tool.cache_control().is_some_and(|cache| cache.is_1h()));
+        let system = match &self.system {
+            Some(SystemPrompt::Blocks(blocks)) => blocks.iter().any(|block| {
+                block
+                    .cache_control
+                    .as_ref()
+                    .is_some_and(CacheControl::is_1h)
+            }),
+            _ => false,
+        };
+        tools
+            || system
+            || self.any_block(|block| block.get_cache_control().is_some_and(CacheControl::is_1h))
+    }
+
+    /// Beta flags the body needs, e.g. `files-api` when it references files
+    pub fn required_betas(&self) -> Vec<Beta> {
+        let mut betas = Vec::new();
+        if self.references_files() {
+            betas.push(Beta::FilesApi);
+        }
+        if self.uses_1h_cache() {
+            betas.push(Beta::ExtendedCacheTtl);
+        }
+        if self.output_format.is_some() {
+            betas.push(Beta::StructuredOutputs);
+        }
+        if self.context_management.is_some() {
+            betas.push(Beta::ContextManagement);
+        }
+        betas
+    }
+
+    /// Get the output limit of the body's model, if known
+    ///
+    /// `extended_output` tells whether the `output-128k` beta flag is enabled.
+    pub fn max_output_tokens(&self, extended_output: bool) -> Option<usize> {
+        Model::from(self.model.as_str()).max_output_tokens(extended_output)
+    }
+
+    /// Reject or clamp `max_tokens` above the model's output limit
+    pub fn enforce_max_tokens(
+        &mut self,
+        policy: MaxTokensPolicy,
+        extended_output: bool,
+    ) -> Result<()> {
+        let Some(limit) = self.max_output_tokens(extended_output) else {
+            return Ok(());
+        };
+        if self.max_tokens <= limit {
+            return Ok(());
+        }
+        match policy {
+            MaxTokensPolicy::Allow => Ok(()),
+            MaxTokensPolicy::Clamp => {
+                self.max_tokens = limit;
+                Ok(())
+            }
+            MaxTokensPolicy::Reject => {
+                let hint = if !extended_output
+                    && Model::from(self.model.as_str()).max_output_tokens(true) > Some(limit)
+                {
+                    " (enable the output-128k beta flag for more)"
+                } else {
+                    ""
+                };
+                Err(AnthropicToolError::InvalidParameter(format!(
+                    "max_tokens {} exceeds the output limit of {} ({} tokens){}",
+                    self.max_tokens, self.model, limit, hint
+                )))
+            }
+        }
+    }
+
+    /// Read a request body from a JSON file
+    ///
+    /// The file holds the body as sent to the API. Errors name the file and
+    /// the offending field. The body is not validated, so a file may hold a
+    /// template to which messages are added later.
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let mut deserializer = serde_json::Deserializer::from_str(&text);
+        serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+            AnthropicToolError::InvalidParameter(format!(
+                "{}: invalid request body at `{}`: {}",
+                path.display(),
+                err.path(),
+                err.inner()
+            ))
+        })
+    }
+
+    /// Write the request body to a JSON file (pretty-printed)
+    ///
+    /// Message annotations are local-only and not written.
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Get the text the assistant's reply is prefilled with, if any
+    ///
+    /// This is the trailing text of a final assistant message; the model
+    /// continues from it.
+    pub fn prefill(&self) -> Option<&str> {
+        let message = self.messages.last()?;
+        if message.role != Role::Assistant {
+            return None;
+        }
+        match message.content.last()? {
+            ContentBlock::Text { text, .. } => Some(text),
+            _ => None,
+        }
+    }
+
+    /// List settings the API accepts but advises against
+    ///
+    /// Currently this flags `temperature` and `top_p` set together; only one
+    /// of them should be changed from its default.
+    pub fn lints(&self) -> Vec<String> {
+        let mut lints = Vec::new();
+        if self.temperature.is_some() && self.top_p.is_some() {
+            lints.push("temperature and top_p are both set; set only one of them".to_string());
+        }
+        lints
+    }
+
+    /// Validate the request body, also rejecting everything [`lints`](Self::lints) reports
+    pub fn validate_strict(&self) -> Result<()> {
+        self.validate()?;
+        match self.lints().into_iter().next() {
+            Some(lint) => Err(AnthropicToolError::InvalidParameter(lint)),
+            None => Ok(()),
+        }
+    }
+
     /// Validate the request body
     pub fn validate(&self) -> Result<()> {
         if self.model.is_empty() {
@@ -173,21 +653,60 @@ impl Body {
             ));
         }
 
-        // Validate temperature if set
-        if let Some(temp) = self.temperature {
-            if !(0.0..=1.0).contains(&temp) {
-                return Err(AnthropicToolError::InvalidParameter(
-                    "temperature must be between 0.0 and 1.0".to_string(),
-                ));
+        if let Some(user_id) = self.metadata.as_ref().and_then(|m| m.user_id.as_ref()) {
+            user_id.validate()?;
+        }
+
+        if let Some(sequences) = &self.stop_sequences {
+            StopSequences::validate(sequences)?;
+        }
+
+        if let Some(prefill) = self.prefill()
+            && prefill.ends_with(char::is_whitespace)
+        {
+            return Err(AnthropicToolError::InvalidParameter(
+                "assistant prefill must not end with whitespace".to_string(),
+            ));
+        }
+
+        validate_images(&self.messages)?;
+
+        if let Some(key) = self
+            .extra
+            .keys()
+            .find(|key| BODY_FIELDS.contains(&key.as_str()))
+        {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "extra parameter `{}` duplicates a body field; use its builder instead",
+                key
+            )));
+        }
+
+        if let Some(context_management) = &self.context_management {
+            context_management.validate()?;
+        }
+
+        let mut server_names = HashSet::new();
+        for server in self.mcp_servers.iter().flatten() {
+            server.validate()?;
+            if !server_names.insert(server.name.as_str()) {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "mcp server `{}` is defined twice",
+                    server.name
+                )));
             }
         }
 
-        // Validate top_p if set
-        if let Some(top_p) = self.top_p {
-            if !(0.0..=1.0).contains(&top_p) {
-                return Err(AnthropicToolError::InvalidParameter(
-                    "top_p must be between 0.0 and 1.0".to_string(),
-                ));
+        let mut tool_names = HashSet::new();
+        for tool in self.tools.iter().flatten() {
+            tool.validate()?;
+            if let Some(name) = tool.name()
+                && !tool_names.insert(name)
+            {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "tool `{}` is defined twice",
+                    name
+                )));
             }
         }
 
@@ -198,6 +717,10 @@ impl Body {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::tool::{PropertyDef, Tool};
+    use crate::messages::request::content::{ContentBlock, MediaType};
+    use crate::messages::request::role::Role;
+    use std::collections::HashMap;
 
     #[test]
     fn test_body_new() {
@@ -220,11 +743,364 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_user_id_validation() {
+        assert!(UserId::new("user-42").is_ok());
+        assert!(UserId::new("tenant:7.user_9").is_ok());
+        assert!(UserId::new("").is_err());
+        assert!(UserId::new("alice@example.com").is_err());
+        assert!(UserId::new("Alice Smith").is_err());
+        assert!(UserId::new("+1 555 0100").is_err());
+        assert!(UserId::new("a".repeat(MAX_USER_ID_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_hashed_user_id() {
+        let a = UserId::hashed("alice@example.com", "salt");
+        assert_eq!(a, UserId::hashed("alice@example.com", "salt"));
+        assert_ne!(a, UserId::hashed("alice@example.com", "other-salt"));
+        assert_eq!(a.as_str().len(), 64);
+        assert!(a.validate().is_ok());
+
+        // SHA-256("saltabc")
+        assert_eq!(
+            UserId::hashed("abc", "salt").as_str(),
+            "3681099918be28c95b81e27e7e5c2e4c6a6dea566d2d10e7f49139ebb779eb6f"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_user_id() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(Message::user("Hello"));
+        body.metadata = Some(Metadata {
+            user_id: Some(UserId::unchecked("bob@example.com")),
+        });
+        assert!(body.validate().is_err());
+
+        body.metadata = Some(Metadata::hashed_user_id("bob@example.com", "salt"));
+        assert!(body.validate().is_ok());
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["metadata"]["user_id"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_validate_tools() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(Message::user("Hello"));
+        body.tools = Some(vec![Tool::new("search").into()]);
+        assert!(body.validate().is_ok());
+
+        body.tools = Some(vec![Tool::new("search!").into()]);
+        assert!(body.validate().is_err());
+
+        body.tools = Some(vec![Tool::new("search").into(), Tool::new("search").into()]);
+        let err = body.validate().unwrap_err();
+        assert!(err.to_string().contains("defined twice"));
+    }
+
+    #[test]
+    fn test_validate_stop_sequences() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(Message::user("Hello"));
+        body.stop_sequences = Some(vec!["END".to_string(), "</answer>".to_string()]);
+        assert!(body.validate().is_ok());
+
+        let error = |sequences: Vec<String>| {
+            let mut body = body.clone();
+            body.stop_sequences = Some(sequences);
+            match body.validate() {
+                Err(AnthropicToolError::InvalidParameter(message)) => message,
+                other => panic!("expected InvalidParameter, got {:?}", other),
+            }
+        };
+        assert_eq!(
+            error(vec!["\n ".to_string()]),
+            "stop sequence \"\\n \" must contain non-whitespace characters"
+        );
+        assert_eq!(
+            error(vec!["END".to_string(), "END".to_string()]),
+            "duplicate stop sequence \"END\""
+        );
+        assert_eq!(
+            error(
+                (0..=StopSequences::MAX_COUNT)
+                    .map(|i| i.to_string())
+                    .collect()
+            ),
+            "at most 16 stop sequences are allowed, got 17"
+        );
+    }
+
+    #[test]
+    fn test_json_file() {
+        let dir = std::env::temp_dir().join(format!("anthropic-tools-body-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("request.json");
+
+        let mut body = Body::new("claude-sonnet-4-5", 512);
+        body.system = Some(SystemPrompt::with_cache("You are terse."));
+        body.messages.push(Message::user("Hello"));
+        body.temperature = Some(Temperature::new(0.2).unwrap());
+        body.tools = Some(vec![Tool::new("search").into()]);
+        body.extra
+            .insert("service_tier".to_string(), serde_json::json!("auto"));
+        body.to_json_file(&file).unwrap();
+        let loaded = Body::from_json_file(&file).unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&body).unwrap()
+        );
+
+        std::fs::write(
+            &file,
+            r#"{"model": "m", "messages": [], "max_tokens": "many"}"#,
+        )
+        .unwrap();
+        let err = Body::from_json_file(&file).unwrap_err().to_string();
+        assert!(err.contains("request.json: invalid request body at `max_tokens`"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prefill() {
+        let mut body = Body::new("model", 100);
+        body.messages.push(Message::user("Name a color"));
+        assert_eq!(body.prefill(), None);
+
+        body.messages.push(Message::assistant("The color is"));
+        assert_eq!(body.prefill(), Some("The color is"));
+        assert!(body.validate().is_ok());
+
+        body.messages.pop();
+        body.messages.push(Message::assistant("The color is "));
+        match body.validate() {
+            Err(AnthropicToolError::InvalidParameter(message)) => {
+                assert!(message.contains("whitespace"))
+            }
+            other => panic!("expected InvalidParameter, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_strict() {
+        let mut body = Body::new("model", 100);
+        body.messages.push(Message::user("Hello"));
+        body.temperature = Some(Temperature::new(0.5).unwrap());
+        assert!(body.lints().is_empty());
+        body.validate_strict().unwrap();
+
+        body.top_p = Some(TopP::new(0.9).unwrap());
+        assert_eq!(body.lints().len(), 1);
+        body.validate().unwrap();
+        match body.validate_strict() {
+            Err(AnthropicToolError::InvalidParameter(message)) => {
+                assert!(message.contains("temperature and top_p"))
+            }
+            other => panic!("expected InvalidParameter, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_enforce_max_tokens() {
+        let mut body = Body::new("claude-3-5-haiku-20241022", 10_000);
+        body.enforce_max_tokens(MaxTokensPolicy::Allow, false)
+            .unwrap();
+        assert_eq!(body.max_tokens, 10_000);
+        body.enforce_max_tokens(MaxTokensPolicy::Clamp, false)
+            .unwrap();
+        assert_eq!(body.max_tokens, 8_192);
+        body.enforce_max_tokens(MaxTokensPolicy::Reject, false)
+            .unwrap();
+
+        let mut body = Body::new("claude-3-7-sonnet-20250219", 100_000);
+        assert!(
+            body.enforce_max_tokens(MaxTokensPolicy::Reject, true)
+                .is_ok()
+        );
+        match body.enforce_max_tokens(MaxTokensPolicy::Reject, false) {
+            Err(AnthropicToolError::InvalidParameter(message)) => assert_eq!(
+                message,
+                "max_tokens 100000 exceeds the output limit of claude-3-7-sonnet-20250219 \
+                 (64000 tokens) (enable the output-128k beta flag for more)"
+            ),
+            other => panic!("expected InvalidParameter, got {:?}", other),
+        }
+
+        // Unknown models are not checked
+        let mut body = Body::new("claude-next", 1_000_000);
+        body.enforce_max_tokens(MaxTokensPolicy::Reject, false)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_output_format() {
+        let mut schema = JsonSchema::object();
+        schema.properties = Some(HashMap::from([
+            ("name".to_string(), PropertyDef::string(None)),
+            (
+                "address".to_string(),
+                PropertyDef::object_with(None, |address| {
+                    address.string("city", Some("City name"), true);
+                }),
+            ),
+        ]));
+        schema.required = Some(vec!["name".to_string()]);
+        let format = OutputFormat::json_schema(&schema);
+        let json = serde_json::to_value(&format).unwrap();
+        assert_eq!(json["type"], "json_schema");
+        assert_eq!(json["schema"]["additionalProperties"], false);
+        assert_eq!(
+            json["schema"]["properties"]["address"]["additionalProperties"],
+            false
+        );
+        assert!(
+            json["schema"]["properties"]["name"]
+                .get("additionalProperties")
+                .is_none()
+        );
+
+        // An explicit setting is kept, under its wire name
+        schema.additional_properties = Some(true);
+        let format = OutputFormat::json_schema(&schema);
+        assert_eq!(format.schema["additionalProperties"], true);
+        assert!(format.schema.get("additional_properties").is_none());
+
+        let mut body = Body::new("claude-sonnet-4-5", 1024);
+        assert!(body.required_betas().is_empty());
+        body.output_format = Some(format);
+        assert_eq!(body.required_betas(), vec![Beta::StructuredOutputs]);
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_output_format_from_schema() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Contact {
+            name: String,
+            email: Option<String>,
+        }
+
+        let format = OutputFormat::from_schema::<Contact>().unwrap();
+        assert_eq!(format.schema["type"], "object");
+        assert_eq!(format.schema["additionalProperties"], false);
+        assert_eq!(format.schema["properties"]["email"]["type"], "string");
+        assert!(format.schema.get("title").is_none());
+    }
+
+    #[test]
+    fn test_extra_params() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(Message::user("Hello"));
+        body.extra
+            .insert("service_tier".to_string(), serde_json::json!("auto"));
+        assert!(body.validate().is_ok());
+
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["service_tier"], "auto");
+        assert_eq!(json["model"], "claude-sonnet-4-20250514");
+        let parsed: Body = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.extra["service_tier"], "auto");
+        assert!(!parsed.extra.contains_key("model"));
+
+        // Every modeled field is known
+        body.system = Some(SystemPrompt::text("Be brief"));
+        body.temperature = Some(Temperature::new(0.5).unwrap());
+        body.top_p = Some(TopP::new(0.9).unwrap());
+        body.top_k = Some(TopK::new(5).unwrap());
+        body.stop_sequences = Some(vec!["END".to_string()]);
+        body.stream = Some(true);
+        body.tools = Some(vec![Tool::new("search").into()]);
+        body.tool_choice = Some(ToolChoice::auto());
+        body.metadata = Some(Metadata::default());
+        body.container = Some("container_1".to_string());
+        body.mcp_servers = Some(Vec::new());
+        body.output_format = Some(OutputFormat::from_value(serde_json::json!({})));
+        body.context_management = Some(ContextManagement::new());
+        body.extra.clear();
+        let json = serde_json::to_value(&body).unwrap();
+        let mut keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut fields = BODY_FIELDS.to_vec();
+        keys.sort();
+        fields.sort();
+        assert_eq!(keys, fields);
+
+        body.extra
+            .insert("max_tokens".to_string(), serde_json::json!(10));
+        assert!(matches!(
+            body.validate(),
+            Err(AnthropicToolError::InvalidParameter(message)) if message.contains("`max_tokens`")
+        ));
+    }
+
+    #[test]
+    fn test_validate_images() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(Message::new(
+            Role::User,
+            vec![ContentBlock::image_from_bytes(
+                MediaType::Gif,
+                b"\xFF\xD8\xFF\xE0",
+            )],
+        ));
+        match body.validate() {
+            Err(AnthropicToolError::InvalidImages(violations)) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(
+                    violations[0].to_string(),
+                    "messages[0].content[0]: data is image/jpeg but the media type is image/gif"
+                );
+            }
+            other => panic!("expected InvalidImages, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_mcp_servers() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(Message::user("Hello"));
+        let server = McpServer::url("docs", "https://mcp.example.com");
+        body.mcp_servers = Some(vec![server.clone()]);
+        assert!(body.validate().is_ok());
+
+        body.mcp_servers = Some(vec![server.clone(), server]);
+        let err = body.validate().unwrap_err();
+        assert!(err.to_string().contains("defined twice"));
+
+        body.mcp_servers = Some(vec![McpServer::url("docs", "ftp://mcp.example.com")]);
+        assert!(body.validate().is_err());
+    }
+
+    #[test]
+    fn test_annotations_not_sent() {
+        let mut message = Message::user("Hello!");
+        message.annotate("moderation", "approved");
+
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(message);
+
+        let json = serde_json::to_string(&body).unwrap();
+        assert!(!json.contains("annotations"));
+        assert!(!json.contains("approved"));
+        assert!(
+            serde_json::to_string(&body.messages[0])
+                .unwrap()
+                .contains("approved")
+        );
+    }
+
     #[test]
     fn test_tool_choice_serialize() {
-        let auto = ToolChoice::Auto;
+        let auto = ToolChoice::auto();
         let json = serde_json::to_string(&auto).unwrap();
-        assert!(json.contains("\"type\":\"auto\""));
+        assert_eq!(json, r#"{"type":"auto"}"#);
 
         let tool = ToolChoice::Tool {
             name: "search".to_string(),
@@ -234,6 +1110,36 @@ mod tests {
         assert!(json.contains("\"name\":\"search\""));
     }
 
+    #[test]
+    fn test_disable_parallel_tool_use() {
+        let json = serde_json::to_value(ToolChoice::any().disable_parallel_tool_use()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "any", "disable_parallel_tool_use": true})
+        );
+        assert_eq!(
+            ToolChoice::tool("search").disable_parallel_tool_use(),
+            ToolChoice::tool("search")
+        );
+
+        let parsed: ToolChoice = serde_json::from_str(r#"{"type":"auto"}"#).unwrap();
+        assert_eq!(parsed, ToolChoice::auto());
+        let parsed: ToolChoice =
+            serde_json::from_str(r#"{"type":"auto","disable_parallel_tool_use":true}"#).unwrap();
+        assert_eq!(parsed, ToolChoice::auto().disable_parallel_tool_use());
+    }
+
+    #[test]
+    fn test_body_deserialize_rejects_invalid_sampling() {
+        let json = r#"{"model":"m","messages":[],"max_tokens":10,"temperature":1.5}"#;
+        assert!(serde_json::from_str::<Body>(json).is_err());
+
+        let json = r#"{"model":"m","messages":[],"max_tokens":10,"temperature":0.5,"top_k":5}"#;
+        let body: Body = serde_json::from_str(json).unwrap();
+        assert_eq!(body.temperature.map(|t| t.value()), Some(0.5));
+        assert_eq!(body.top_k.map(|k| k.value()), Some(5));
+    }
+
     #[test]
     fn test_body_serialize() {
         let body = Body::new("claude-sonnet-4-20250514", 1024);
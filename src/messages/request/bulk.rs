@@ -0,0 +1,322 @@
+//! Concurrent submission of many requests.
+//!
+//! [`Messages::post_many`](super::Messages::post_many) sends a list of request
+//! bodies with a cap on the number in flight, waits out rate limits reported
+//! by the API, and retries transient failures. Results come back in input
+//! order, one per body, so a failed item does not affect the others.
+//!
+//! This sits between single calls and the Message Batches API: results arrive
+//! within seconds, at regular (not batch) pricing.
+//!
+//! Rate limits are read from the response headers:
+//!
+//! - `retry-after` - Seconds to wait before the next request
+//! - `anthropic-ratelimit-{requests,tokens,input-tokens,output-tokens}-remaining`
+//!   and the matching `-reset` time - When a budget is exhausted, all workers
+//!   pause until it resets
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::messages::request::bulk::BulkOptions;
+//!
+//! # async fn example() -> Result<()> {
+//! let bodies: Vec<Body> = ["red", "green", "blue"]
+//!     .iter()
+//!     .map(|color| {
+//!         let mut body = Body::new("claude-sonnet-4-20250514", 256);
+//!         body.messages.push(Message::user(format!("Name a fruit that is {}", color)));
+//!         body
+//!     })
+//!     .collect();
+//!
+//! let client = Messages::new();
+//! let mut options = BulkOptions::new();
+//! options.concurrency(8).max_retries(5);
+//!
+//! for (index, result) in client.post_many_with(bodies, &options).await.into_iter().enumerate() {
+//!     match result {
+//!         Ok(response) => println!("{}: {}", index, response.get_text()),
+//!         Err(err) => println!("{}: failed: {}", index, err),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::AnthropicToolError;
+use crate::common::runtime::sleep;
+use std::sync::Mutex;
+use std::time::Duration;
+use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Rate limit budgets reported in `anthropic-ratelimit-*` headers
+const RATE_LIMIT_BUDGETS: [&str; 4] = ["requests", "tokens", "input-tokens", "output-tokens"];
+
+/// Options for [`Messages::post_many_with`](super::Messages::post_many_with)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkOptions {
+    /// Maximum number of requests in flight
+    pub concurrency: usize,
+
+    /// Retries per request after a transient failure
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled for each further retry
+    pub initial_backoff: Duration,
+
+    /// Upper bound for the retry delay
+    pub max_backoff: Duration,
+}
+
+impl Default for BulkOptions {
+    fn default() -> Self {
+        BulkOptions {
+            concurrency: 4,
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl BulkOptions {
+    /// Create options with the defaults (4 in flight, 3 retries, 1s to 60s backoff)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of requests in flight (at least 1)
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the number of retries per request
+    pub fn max_retries(&mut self, max_retries: u32) -> &mut Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn initial_backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the upper bound for the retry delay
+    pub fn max_backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Delay before the given retry (1-based)
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Check whether a failed request is worth retrying
+///
+/// Of the unmapped API errors, only `api_error` (an internal server error) is
+/// transient; other types would fail the same way again.
+pub(crate) fn is_retryable(err: &AnthropicToolError) -> bool {
+    match err {
+        AnthropicToolError::RateLimitError(_)
+        | AnthropicToolError::OverloadedError(_)
+        | AnthropicToolError::RequestError(_) => true,
+        AnthropicToolError::ApiError { error_type, .. } => error_type == "api_error",
+        _ => false,
+    }
+}
+
+/// Pause shared by all requests of a bulk submission
+///
+/// Updated from the headers of every response; requests wait for it before
+/// being sent.
+#[derive(Debug, Default)]
+pub(crate) struct RateGate {
+    paused_until: Mutex<Option<Instant>>,
+}
+
+impl RateGate {
+    /// Update the pause from response headers
+    pub(crate) fn observe(&self, headers: &[(String, String)]) {
+        if let Some(wait) = pause_from_headers(headers) {
+            let until = Instant::now() + wait;
+            let mut paused_until = self
+                .paused_until
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if paused_until.is_none_or(|current| current < until) {
+                *paused_until = Some(until);
+            }
+        }
+    }
+
+    /// Wait until the pause (if any) is over
+    pub(crate) async fn wait(&self) {
+        let remaining = self
+            .paused_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .and_then(|until| until.checked_duration_since(Instant::now()));
+        if let Some(remaining) = remaining {
+            sleep(remaining).await;
+        }
+    }
+}
+
+/// How long to pause according to rate limit headers
+fn pause_from_headers(headers: &[(String, String)]) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    };
+    if let Some(seconds) = header("retry-after").and_then(|value| value.parse::<f64>().ok())
+        && seconds.is_finite()
+        && seconds >= 0.0
+    {
+        return Some(Duration::from_secs_f64(seconds));
+    }
+    RATE_LIMIT_BUDGETS
+        .iter()
+        .filter(|budget| header(&format!("anthropic-ratelimit-{}-remaining", budget)) == Some("0"))
+        .filter_map(|budget| header(&format!("anthropic-ratelimit-{}-reset", budget)))
+        .filter_map(parse_rfc3339)
+        .filter_map(|reset| reset.duration_since(SystemTime::now()).ok())
+        .max()
+}
+
+/// Parse an RFC 3339 timestamp (`2025-01-01T00:00:00Z`, `...T00:00:00.5+09:00`)
+fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    let (date, time) = value.split_once(['T', 't'])?;
+    let mut date_parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+
+    let (clock, offset) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        (clock, 0)
+    } else {
+        let split = time.rfind(['+', '-'])?;
+        let (clock, offset) = time.split_at(split);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = offset[1..].split_once(':')?;
+        (
+            clock,
+            sign * (hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60),
+        )
+    };
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour = clock_parts.next()?.parse::<i64>().ok()?;
+    let minute = clock_parts.next()?.parse::<i64>().ok()?;
+    let seconds = clock_parts.next()?.parse::<f64>().ok()?;
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let whole = days * 86_400 + hour * 3600 + minute * 60 - offset;
+    let secs = whole as f64 + seconds;
+    (secs >= 0.0).then(|| UNIX_EPOCH + Duration::from_secs_f64(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc3339() {
+        let epoch = |value| {
+            parse_rfc3339(value)
+                .unwrap()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+        };
+        assert_eq!(epoch("1970-01-01T00:00:00Z"), Duration::ZERO);
+        assert_eq!(
+            epoch("2025-03-01T12:30:15Z"),
+            Duration::from_secs(1_740_832_215)
+        );
+        assert_eq!(
+            epoch("2025-03-01T21:30:15.5+09:00"),
+            Duration::from_secs_f64(1_740_832_215.5)
+        );
+        assert!(parse_rfc3339("yesterday").is_none());
+    }
+
+    #[test]
+    fn test_pause_from_headers() {
+        let headers = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            pause_from_headers(&headers(&[("Retry-After", "7")])),
+            Some(Duration::from_secs(7))
+        );
+        assert!(
+            pause_from_headers(&headers(&[
+                ("anthropic-ratelimit-requests-remaining", "12"),
+                ("anthropic-ratelimit-requests-reset", "2999-01-01T00:00:00Z"),
+            ]))
+            .is_none()
+        );
+        assert!(
+            pause_from_headers(&headers(&[
+                ("anthropic-ratelimit-tokens-remaining", "0"),
+                ("anthropic-ratelimit-tokens-reset", "2999-01-01T00:00:00Z"),
+            ]))
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let api_error = |error_type: &str| AnthropicToolError::ApiError {
+            error_type: error_type.to_string(),
+            message: "failed".to_string(),
+            request_id: None,
+        };
+        assert!(is_retryable(&api_error("api_error")));
+        assert!(!is_retryable(&api_error("request_too_large")));
+        assert!(!is_retryable(&api_error("billing_error")));
+        assert!(is_retryable(&AnthropicToolError::OverloadedError(
+            "busy".to_string()
+        )));
+        assert!(!is_retryable(&AnthropicToolError::InvalidRequestError(
+            "bad".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let mut options = BulkOptions::new();
+        options
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350));
+        assert_eq!(options.backoff(1), Duration::from_millis(100));
+        assert_eq!(options.backoff(2), Duration::from_millis(200));
+        assert_eq!(options.backoff(3), Duration::from_millis(350));
+        assert_eq!(options.backoff(40), Duration::from_millis(350));
+    }
+}
@@ -0,0 +1,256 @@
+//! Citations of documents and search results.
+//!
+//! Enabling citations on a document block makes the model attach a
+//! [`Citation`] to each part of the answer that is based on it. The
+//! citations are found in the `citations` field of the response text blocks.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::messages::request::content::ContentBlock;
+//! use anthropic_tools::messages::request::message::Message;
+//! use anthropic_tools::messages::request::role::Role;
+//!
+//! # async fn example() -> Result<()> {
+//! let document = ContentBlock::document_from_url("https://example.com/report.pdf")
+//!     .with_title("Annual Report")
+//!     .with_citations();
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .add_message(Message::new(
+//!         Role::User,
+//!         vec![document, ContentBlock::text("How did revenue develop?")],
+//!     ));
+//!
+//! let response = client.post().await?;
+//! for citation in response.citations() {
+//!     println!("{}", citation);
+//! }
+//! // Or the whole answer with [n] markers and a list of sources
+//! println!("{}", response.get_text_with_citations());
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Citation settings of a document block
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CitationsConfig {
+    pub enabled: bool,
+}
+
+impl CitationsConfig {
+    /// Let the model cite the document
+    pub fn enabled() -> Self {
+        CitationsConfig { enabled: true }
+    }
+}
+
+/// Location of cited text, attached to a response text block
+///
+/// End indices are exclusive. Indices of documents count the document blocks
+/// of the request, starting at 0.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Citation {
+    /// Character range of a plain text document
+    CharLocation {
+        cited_text: String,
+        document_index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        document_title: Option<String>,
+        start_char_index: usize,
+        end_char_index: usize,
+    },
+
+    /// Page range of a PDF document (pages start at 1)
+    PageLocation {
+        cited_text: String,
+        document_index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        document_title: Option<String>,
+        start_page_number: usize,
+        end_page_number: usize,
+    },
+
+    /// Block range of a custom content document
+    ContentBlockLocation {
+        cited_text: String,
+        document_index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        document_title: Option<String>,
+        start_block_index: usize,
+        end_block_index: usize,
+    },
+
+    /// Block range of a `search_result` block supplied by the caller
+    SearchResultLocation {
+        cited_text: String,
+        source: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Index of the search result among those of the request
+        search_result_index: usize,
+        start_block_index: usize,
+        end_block_index: usize,
+    },
+
+    /// Result of the web search tool
+    WebSearchResultLocation {
+        cited_text: String,
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Reference to the cited passage, to be passed back verbatim
+        encrypted_index: String,
+    },
+}
+
+impl Citation {
+    /// Get the quoted text
+    pub fn cited_text(&self) -> &str {
+        match self {
+            Citation::CharLocation { cited_text, .. }
+            | Citation::PageLocation { cited_text, .. }
+            | Citation::ContentBlockLocation { cited_text, .. }
+            | Citation::SearchResultLocation { cited_text, .. }
+            | Citation::WebSearchResultLocation { cited_text, .. } => cited_text,
+        }
+    }
+
+    /// Get the index of the cited document, if a document is cited
+    pub fn document_index(&self) -> Option<usize> {
+        match self {
+            Citation::CharLocation { document_index, .. }
+            | Citation::PageLocation { document_index, .. }
+            | Citation::ContentBlockLocation { document_index, .. } => Some(*document_index),
+            Citation::SearchResultLocation { .. } | Citation::WebSearchResultLocation { .. } => {
+                None
+            }
+        }
+    }
+
+    /// Describe where the quote comes from, e.g. `Annual Report, p. 3`
+    ///
+    /// Documents without a title are named by their index.
+    pub fn source(&self) -> String {
+        let document = |title: &Option<String>, index: &usize| {
+            title
+                .clone()
+                .unwrap_or_else(|| format!("document {}", index))
+        };
+        let range = |unit: &str, units: &str, start: usize, end: usize| {
+            if end <= start + 1 {
+                format!("{} {}", unit, start)
+            } else {
+                format!("{} {}-{}", units, start, end - 1)
+            }
+        };
+        match self {
+            Citation::CharLocation {
+                document_title,
+                document_index,
+                start_char_index,
+                end_char_index,
+                ..
+            } => format!(
+                "{}, {}",
+                document(document_title, document_index),
+                range("char", "chars", *start_char_index, *end_char_index)
+            ),
+            Citation::PageLocation {
+                document_title,
+                document_index,
+                start_page_number,
+                end_page_number,
+                ..
+            } => format!(
+                "{}, {}",
+                document(document_title, document_index),
+                range("p.", "pp.", *start_page_number, *end_page_number)
+            ),
+            Citation::ContentBlockLocation {
+                document_title,
+                document_index,
+                start_block_index,
+                end_block_index,
+                ..
+            } => format!(
+                "{}, {}",
+                document(document_title, document_index),
+                range("block", "blocks", *start_block_index, *end_block_index)
+            ),
+            Citation::SearchResultLocation {
+                source,
+                title: Some(title),
+                ..
+            } => format!("{} ({})", title, source),
+            Citation::SearchResultLocation { source, .. } => source.clone(),
+            Citation::WebSearchResultLocation { url, title, .. } => match title {
+                Some(title) => format!("{} ({})", title, url),
+                None => url.clone(),
+            },
+        }
+    }
+}
+
+/// Render as `"quote" — source`
+impl fmt::Display for Citation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" — {}", self.cited_text().trim(), self.source())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_citation_deserialize_and_render() {
+        let citations: Vec<Citation> = serde_json::from_value(serde_json::json!([
+            {"type": "page_location", "cited_text": "Revenue grew 12%.", "document_index": 0,
+             "document_title": "Annual Report", "start_page_number": 3, "end_page_number": 4},
+            {"type": "char_location", "cited_text": "The sky is blue.", "document_index": 1,
+             "start_char_index": 0, "end_char_index": 16},
+            {"type": "content_block_location", "cited_text": "Step one.", "document_index": 2,
+             "document_title": "Guide", "start_block_index": 1, "end_block_index": 3},
+            {"type": "web_search_result_location", "cited_text": "Rust 1.88 released",
+             "url": "https://blog.rust-lang.org", "title": "Rust Blog",
+             "encrypted_index": "Eo8BCioIAhgB"},
+            {"type": "search_result_location", "cited_text": "Use kill_on_drop.",
+             "source": "https://docs.rs/tokio", "title": "tokio::process",
+             "search_result_index": 0, "start_block_index": 0, "end_block_index": 1}
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            citations[0].to_string(),
+            "\"Revenue grew 12%.\" — Annual Report, p. 3"
+        );
+        assert_eq!(citations[1].source(), "document 1, chars 0-15");
+        assert_eq!(citations[2].source(), "Guide, blocks 1-2");
+        assert_eq!(
+            citations[3].source(),
+            "Rust Blog (https://blog.rust-lang.org)"
+        );
+        assert_eq!(citations[2].document_index(), Some(2));
+        assert_eq!(citations[3].document_index(), None);
+        assert_eq!(citations[3].cited_text(), "Rust 1.88 released");
+        assert_eq!(
+            citations[4].source(),
+            "tokio::process (https://docs.rs/tokio)"
+        );
+        assert_eq!(citations[4].document_index(), None);
+
+        // Passed back unchanged
+        let json = serde_json::to_value(&citations[1]).unwrap();
+        assert_eq!(json["type"], "char_location");
+        assert!(json.get("document_title").is_none());
+    }
+}
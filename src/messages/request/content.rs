@@ -3,10 +3,13 @@
 //! This module provides content block types for constructing messages:
 //!
 //! - [`ContentBlock`] - Main enum for all content types
-//! - [`ImageSource`] - Image data (base64 or URL)
-//! - [`DocumentSource`] - PDF document data
+//! - [`ImageSource`] - Image data (base64, URL, or file ID)
+//! - [`DocumentSource`] - Document data (PDF, plain text, or custom content)
 //! - [`MediaType`] - Supported image formats
-//! - [`CacheControl`] - Prompt caching configuration
+//! - [`CacheControl`] - Prompt caching configuration (same type as
+//!   [`common::tool::CacheControl`](crate::common::tool::CacheControl))
+//!
+//! Citation settings and locations are in [`citation`](super::citation).
 //!
 //! # Text Content
 //!
@@ -15,6 +18,10 @@
 //!
 //! let text = ContentBlock::text("Hello, world!");
 //! let cached = ContentBlock::text_with_cache("Cached content");
+//!
+//! // Images, documents, tool results, ... are cached the same way
+//! let document = ContentBlock::document_from_url("https://example.com/a.pdf").with_cache();
+//! assert!(document.has_cache_control());
 //! ```
 //!
 //! # Image Content
@@ -25,8 +32,11 @@
 //! // From URL
 //! let image = ContentBlock::image_from_url("https://example.com/image.png");
 //!
-//! // From local file (requires image feature)
-//! // let image = ContentBlock::image_from_path(MediaType::Png, "path/to/image.png");
+//! // From local file (requires the `image` feature); the format is detected
+//! // from the file contents
+//! // let image = ContentBlock::image_from_path("path/to/image.png")?;
+//!
+//! assert_eq!(MediaType::detect(b"\x89PNG\r\n\x1a\n..."), Some(MediaType::Png));
 //! ```
 //!
 //! # Tool Use
@@ -42,14 +52,17 @@
 //! let result = ContentBlock::tool_result_text("tool_123", "Search results...");
 //! ```
 
+use super::citation::{Citation, CitationsConfig};
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::server_tool::{CodeExecutionToolResultContent, WebSearchToolResultContent};
+pub use crate::common::tool::CacheControl;
 use base64::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::path::PathBuf;
 use strum::{Display, EnumString};
 
 /// Media types supported by Anthropic API
-#[derive(Serialize, Deserialize, Debug, Clone, Display, EnumString, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
 pub enum MediaType {
     #[strum(serialize = "image/png")]
     #[serde(rename = "image/png")]
@@ -65,11 +78,51 @@ pub enum MediaType {
     Webp,
 }
 
+impl MediaType {
+    /// Detect the format of image data from its magic bytes
+    ///
+    /// Returns `None` for data that is not a supported format.
+    pub fn detect(data: &[u8]) -> Option<MediaType> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(MediaType::Png)
+        } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(MediaType::Jpeg)
+        } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            Some(MediaType::Gif)
+        } else if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+            Some(MediaType::Webp)
+        } else {
+            None
+        }
+    }
+
+    /// Get the format of a file extension such as `"jpg"` (case-insensitive)
+    pub fn from_extension(ext: &str) -> Option<MediaType> {
+        match ext.to_ascii_lowercase().as_str() {
+            "png" => Some(MediaType::Png),
+            "jpg" | "jpeg" => Some(MediaType::Jpeg),
+            "gif" => Some(MediaType::Gif),
+            "webp" => Some(MediaType::Webp),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "image")]
+    fn image_format(&self) -> image::ImageFormat {
+        match self {
+            MediaType::Png => image::ImageFormat::Png,
+            MediaType::Jpeg => image::ImageFormat::Jpeg,
+            MediaType::Gif => image::ImageFormat::Gif,
+            MediaType::Webp => image::ImageFormat::WebP,
+        }
+    }
+}
+
 /// Source for image content (base64 or URL)
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ImageSource {
     #[serde(rename = "type")]
-    pub type_name: String, // "base64" or "url"
+    pub type_name: String, // "base64", "url", or "file"
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub media_type: Option<String>,
@@ -79,68 +132,170 @@ pub struct ImageSource {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>, // URL for url type
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>, // Files API id for file type
 }
 
 impl ImageSource {
     /// Create image source from local file path
-    pub fn from_path<T: AsRef<str>>(media_type: MediaType, path: T) -> Self {
-        let path = PathBuf::from(path.as_ref());
-        let ext = std::path::Path::new(&path)
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("png");
-
-        let img = image::ImageReader::open(path.as_path())
-            .expect("Failed to open image file")
-            .decode()
-            .expect("Failed to decode image");
-
-        let img_fmt = match ext {
-            "png" => image::ImageFormat::Png,
-            "jpg" | "jpeg" => image::ImageFormat::Jpeg,
-            "gif" => image::ImageFormat::Gif,
-            "webp" => image::ImageFormat::WebP,
-            _ => panic!("Unsupported image format: {}", ext),
+    ///
+    /// The format is detected from the file contents. Files within the API
+    /// limits are sent byte for byte; others are decoded and re-encoded. With
+    /// the `image-convert` feature, formats the API does not accept (BMP,
+    /// TIFF, ...) are converted to PNG or JPEG, and so is HEIC with the `heic`
+    /// feature. Images that are re-encoded are scaled down to fit the API's pixel limit. Fails
+    /// with `ImageError` if the file cannot be read or decoded, is not a
+    /// supported format, or its extension names a different format than its
+    /// contents.
+    #[cfg(feature = "image")]
+    pub fn from_path<T: AsRef<str>>(path: T) -> Result<Self> {
+        let location = path.as_ref().to_string();
+        let image_error = |message: String| AnthropicToolError::ImageError {
+            location: location.clone(),
+            message,
         };
-
-        let mut buf = std::io::Cursor::new(Vec::new());
-        img.write_to(&mut buf, img_fmt)
-            .expect("Failed to write image to buffer");
-        let base64_string = BASE64_STANDARD.encode(buf.into_inner());
-
-        ImageSource {
-            type_name: "base64".to_string(),
-            media_type: Some(media_type.to_string()),
-            data: Some(base64_string),
-            url: None,
+        let path = std::path::Path::new(path.as_ref());
+        let data =
+            std::fs::read(path).map_err(|e| image_error(format!("cannot open file: {}", e)))?;
+        let Some(media_type) = MediaType::detect(&data) else {
+            #[cfg(feature = "image-convert")]
+            return ImageSource::convert(&data, image_error);
+            #[cfg(not(feature = "image-convert"))]
+            return Err(image_error(unsupported_format_message(&data)));
+        };
+        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+        if let Some(expected) = MediaType::from_extension(ext)
+            && expected != media_type
+        {
+            return Err(image_error(format!(
+                "file extension `{}` does not match its contents ({})",
+                ext, media_type
+            )));
         }
+
+        ImageSource::encode(data, Some(media_type), media_type, image_error)
     }
 
     /// Create image source from URL (async fetch and convert to base64)
-    pub async fn from_url_as_base64<T: AsRef<str>>(media_type: MediaType, url: T) -> Self {
+    ///
+    /// A supported image within the API limits is sent as downloaded, with
+    /// its detected media type. Anything else is decoded and re-encoded as
+    /// `media_type`. Fails with `ImageError` if the download fails, the server
+    /// answers with an error status, or the body is not a decodable image.
+    #[cfg(feature = "image")]
+    pub async fn from_url_as_base64<T: AsRef<str>>(media_type: MediaType, url: T) -> Result<Self> {
+        let image_error = |message: String| AnthropicToolError::ImageError {
+            location: url.as_ref().to_string(),
+            message,
+        };
         let response = request::get(url.as_ref())
             .await
-            .expect("Failed to fetch image from URL");
-        let bytes = response.bytes().await.expect("Failed to read image bytes");
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| image_error(format!("cannot fetch image: {}", e)))?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| image_error(format!("cannot read image bytes: {}", e)))?;
+
+        let detected = MediaType::detect(&bytes);
+        ImageSource::encode(bytes.to_vec(), detected, media_type, image_error)
+    }
 
-        let img = image::ImageReader::new(std::io::Cursor::new(bytes))
-            .with_guessed_format()
-            .expect("Failed to guess image format")
-            .decode()
-            .expect("Failed to decode image");
+    /// Create image source from raw bytes of any format, detecting it
+    ///
+    /// Supported formats within the API limits are sent as is; anything else
+    /// the `image` crate can decode (BMP, TIFF, ...) is converted to PNG, or
+    /// to JPEG when the PNG would be too large. HEIC photos are converted the
+    /// same way with the `heic` feature.
+    #[cfg(feature = "image-convert")]
+    pub fn from_any_bytes<B: AsRef<[u8]>>(data: B) -> Result<Self> {
+        let data = data.as_ref();
+        let image_error = |message: String| AnthropicToolError::ImageError {
+            location: "in-memory bytes".to_string(),
+            message,
+        };
+        match MediaType::detect(data) {
+            Some(media_type) => {
+                ImageSource::encode(data.to_vec(), Some(media_type), media_type, image_error)
+            }
+            None => ImageSource::convert(data, image_error),
+        }
+    }
 
-        let img_fmt = image::ImageFormat::Png;
-        let mut buf = std::io::Cursor::new(Vec::new());
-        img.write_to(&mut buf, img_fmt)
-            .expect("Failed to write image to buffer");
-        let base64_string = BASE64_STANDARD.encode(buf.into_inner());
+    /// Convert an image the API does not accept to PNG, or JPEG if too large
+    ///
+    /// Images larger than [`MAX_IMAGE_DIMENSION`](super::image_check::MAX_IMAGE_DIMENSION)
+    /// on either side are scaled down first, keeping their aspect ratio.
+    #[cfg(feature = "image-convert")]
+    fn convert(data: &[u8], image_error: impl Fn(String) -> AnthropicToolError) -> Result<Self> {
+        use super::image_check::MAX_IMAGE_BYTES;
 
-        ImageSource {
+        let img = match image::load_from_memory(data) {
+            Ok(img) => img,
+            #[cfg(feature = "heic")]
+            Err(_) if is_heic(data) => decode_heic(data)
+                .map_err(|e| image_error(format!("cannot decode HEIC image: {}", e)))?,
+            Err(_) => return Err(image_error(unsupported_format_message(data))),
+        };
+        let img = clamp_dimensions(img);
+        let mut png = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut png, image::ImageFormat::Png)
+            .map_err(|e| image_error(format!("cannot encode image: {}", e)))?;
+        let png = png.into_inner();
+        if png.len().div_ceil(3) * 4 <= MAX_IMAGE_BYTES {
+            return Ok(ImageSource::from_bytes(MediaType::Png, png));
+        }
+        // JPEG has no alpha channel
+        let mut jpeg = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgb8(img.to_rgb8())
+            .write_to(&mut jpeg, image::ImageFormat::Jpeg)
+            .map_err(|e| image_error(format!("cannot encode image: {}", e)))?;
+        Ok(ImageSource::from_bytes(MediaType::Jpeg, jpeg.into_inner()))
+    }
+
+    /// Base64-encode image data, re-encoding it only when necessary
+    ///
+    /// Data of a `detected` format within the API limits is kept as is;
+    /// anything else is decoded and written as `target`.
+    #[cfg(feature = "image")]
+    fn encode(
+        data: Vec<u8>,
+        detected: Option<MediaType>,
+        target: MediaType,
+        image_error: impl Fn(String) -> AnthropicToolError,
+    ) -> Result<Self> {
+        use super::image_check::{MAX_IMAGE_BYTES, MAX_IMAGE_DIMENSION, image_dimensions};
+
+        let encoded_len = data.len().div_ceil(3) * 4;
+        let fits = image_dimensions(&data)
+            .is_some_and(|(width, height)| width.max(height) <= MAX_IMAGE_DIMENSION)
+            && encoded_len <= MAX_IMAGE_BYTES;
+        let (media_type, data) = match detected {
+            Some(media_type) if fits => (media_type, data),
+            _ => {
+                let img = match detected {
+                    Some(format) => {
+                        image::load_from_memory_with_format(&data, format.image_format())
+                    }
+                    None => image::load_from_memory(&data),
+                }
+                .map_err(|e| image_error(format!("cannot decode image: {}", e)))?;
+                let img = clamp_dimensions(img);
+                let mut buf = std::io::Cursor::new(Vec::new());
+                img.write_to(&mut buf, target.image_format())
+                    .map_err(|e| image_error(format!("cannot encode image: {}", e)))?;
+                (target, buf.into_inner())
+            }
+        };
+
+        Ok(ImageSource {
             type_name: "base64".to_string(),
             media_type: Some(media_type.to_string()),
-            data: Some(base64_string),
+            data: Some(BASE64_STANDARD.encode(data)),
             url: None,
-        }
+            file_id: None,
+        })
     }
 
     /// Create image source from URL (direct URL reference)
@@ -150,6 +305,7 @@ impl ImageSource {
             media_type: None,
             data: None,
             url: Some(url.as_ref().to_string()),
+            file_id: None,
         }
     }
 
@@ -160,33 +316,130 @@ impl ImageSource {
             media_type: Some(media_type.to_string()),
             data: Some(data.as_ref().to_string()),
             url: None,
+            file_id: None,
+        }
+    }
+
+    /// Create image source from raw image bytes, e.g. a download or a render
+    ///
+    /// Accepts `Vec<u8>`, `&[u8]`, `bytes::Bytes`, or anything else that
+    /// dereferences to bytes; the data is sent as is. With the
+    /// `image-convert` feature, data in a format the API does not accept
+    /// (BMP, TIFF, ...) is converted like [`ImageSource::from_any_bytes`]
+    /// does, ignoring `media_type`.
+    pub fn from_bytes<B: AsRef<[u8]>>(media_type: MediaType, data: B) -> Self {
+        #[cfg(feature = "image-convert")]
+        if MediaType::detect(data.as_ref()).is_none()
+            && let Ok(source) = ImageSource::from_any_bytes(data.as_ref())
+        {
+            return source;
+        }
+        ImageSource::from_base64(media_type, BASE64_STANDARD.encode(data))
+    }
+
+    /// Create image source from a Files API file ID
+    pub fn from_file_id<T: AsRef<str>>(file_id: T) -> Self {
+        ImageSource {
+            type_name: "file".to_string(),
+            media_type: None,
+            data: None,
+            url: None,
+            file_id: Some(file_id.as_ref().to_string()),
         }
     }
 }
 
-/// Cache control for prompt caching
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CacheControl {
-    #[serde(rename = "type")]
-    pub type_name: String, // "ephemeral"
+/// Scale an image down to fit the API's per-side pixel limit
+#[cfg(feature = "image")]
+fn clamp_dimensions(img: image::DynamicImage) -> image::DynamicImage {
+    use super::image_check::MAX_IMAGE_DIMENSION;
+
+    if img.width().max(img.height()) <= MAX_IMAGE_DIMENSION {
+        return img;
+    }
+    img.resize(
+        MAX_IMAGE_DIMENSION,
+        MAX_IMAGE_DIMENSION,
+        image::imageops::FilterType::Triangle,
+    )
 }
 
-impl CacheControl {
-    pub fn ephemeral() -> Self {
-        CacheControl {
-            type_name: "ephemeral".to_string(),
-        }
+/// Whether data starts with an ISO-BMFF `ftyp` box of a HEIC/HEIF brand
+#[cfg(feature = "image")]
+fn is_heic(data: &[u8]) -> bool {
+    data.get(4..8) == Some(b"ftyp")
+        && data.get(8..12).is_some_and(|brand| {
+            let brand: &[u8; 4] = brand
+                .try_into()
+                .expect("data.get(8..12) always yields 4 bytes");
+            [b"heic", b"heix", b"hevc", b"mif1", b"msf1"].contains(&brand)
+        })
+}
+
+/// Decode the primary image of a HEIC file with libheif
+#[cfg(feature = "heic")]
+fn decode_heic(data: &[u8]) -> std::result::Result<image::DynamicImage, String> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    let lib_heif = LibHeif::new();
+    let context = HeifContext::read_from_bytes(data).map_err(|e| e.to_string())?;
+    let handle = context.primary_image_handle().map_err(|e| e.to_string())?;
+    let (chroma, channels) = if handle.has_alpha_channel() {
+        (RgbChroma::Rgba, 4)
+    } else {
+        (RgbChroma::Rgb, 3)
+    };
+    let decoded = lib_heif
+        .decode(&handle, ColorSpace::Rgb(chroma), None)
+        .map_err(|e| e.to_string())?;
+    let planes = decoded.planes();
+    let plane = planes
+        .interleaved
+        .ok_or("decoded image has no interleaved plane")?;
+    // Rows may be padded past the pixel data
+    let row_len = plane.width as usize * channels;
+    let mut pixels = Vec::with_capacity(row_len * plane.height as usize);
+    for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+        pixels.extend_from_slice(&row[..row_len]);
+    }
+    let img = if channels == 4 {
+        image::RgbaImage::from_raw(plane.width, plane.height, pixels)
+            .map(image::DynamicImage::ImageRgba8)
+    } else {
+        image::RgbImage::from_raw(plane.width, plane.height, pixels)
+            .map(image::DynamicImage::ImageRgb8)
+    };
+    img.ok_or_else(|| "decoded image is smaller than its dimensions".to_string())
+}
+
+/// Explain why image data cannot be sent, naming HEIC photos explicitly
+///
+/// HEIC photos are only decoded with the `heic` feature, which links the
+/// libheif C library.
+#[cfg(feature = "image")]
+fn unsupported_format_message(data: &[u8]) -> String {
+    if is_heic(data) {
+        "HEIC images are not supported; enable the `heic` feature or convert them to JPEG before sending".to_string()
+    } else {
+        "unsupported image format (expected png, jpeg, gif, or webp)".to_string()
     }
 }
 
 /// Content block types for Anthropic API
+///
+/// Blocks of types this crate does not know yet are kept as
+/// [`ContentBlock::Other`], so new API features do not break responses.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
+#[non_exhaustive]
 pub enum ContentBlock {
     /// Text content block
     #[serde(rename = "text")]
     Text {
         text: String,
+        /// Sources of the text, set by the API when citations are enabled
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        citations: Option<Vec<Citation>>,
         #[serde(skip_serializing_if = "Option::is_none")]
         cache_control: Option<CacheControl>,
     },
@@ -215,6 +468,8 @@ pub enum ContentBlock {
         content: Option<Vec<ContentBlock>>,
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
 
     /// Thinking content block (extended thinking)
@@ -225,29 +480,144 @@ pub enum ContentBlock {
         signature: Option<String>,
     },
 
+    /// Call of a server tool such as web search, run by the API itself
+    #[serde(rename = "server_tool_use")]
+    ServerToolUse {
+        id: String,
+        name: String,
+        input: Value,
+    },
+
+    /// Results of a web search run by the API
+    #[serde(rename = "web_search_tool_result")]
+    WebSearchToolResult {
+        tool_use_id: String,
+        content: WebSearchToolResultContent,
+    },
+
+    /// Output of code run by the code execution tool
+    #[serde(rename = "code_execution_tool_result")]
+    CodeExecutionToolResult {
+        tool_use_id: String,
+        content: CodeExecutionToolResultContent,
+    },
+
+    /// Thinking flagged by the safety systems, encrypted (extended thinking)
+    ///
+    /// Must be sent back unchanged with the rest of the assistant turn.
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
+
+    /// Search result supplied by the caller, e.g. from their own retrieval
+    ///
+    /// Can be sent at the top level of a user message or inside a tool
+    /// result; the model cites it with
+    /// [`Citation::SearchResultLocation`].
+    #[serde(rename = "search_result")]
+    SearchResult {
+        /// URL or other identifier of the result
+        source: String,
+        title: String,
+        /// Text blocks of the result
+        content: Vec<ContentBlock>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        citations: Option<CitationsConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+
+    /// Uploaded file to copy into the code execution container
+    ///
+    /// Requires the `files-api` and `code-execution` beta flags; see
+    /// [`Messages::user_with_container_uploads`](super::Messages::user_with_container_uploads).
+    #[serde(rename = "container_upload")]
+    ContainerUpload {
+        /// Files API id of the file
+        file_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+
     /// Document content block (PDF support)
     #[serde(rename = "document")]
     Document {
         source: DocumentSource,
         #[serde(skip_serializing_if = "Option::is_none")]
         cache_control: Option<CacheControl>,
+        /// Title shown to the model and returned in citations
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Information about the document that is not cited
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        context: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        citations: Option<CitationsConfig>,
     },
+
+    /// Block of a type unknown to this crate, as raw JSON
+    ///
+    /// Serialized unchanged, so it can be sent back with the conversation.
+    #[serde(untagged)]
+    Other(#[serde(deserialize_with = "deserialize_unknown_block")] Value),
 }
 
-/// Document source for PDF content
+/// Accept only blocks whose `type` is not one of [`ContentBlock::TYPES`]
+///
+/// Malformed blocks of known types still fail instead of becoming `Other`.
+fn deserialize_unknown_block<'de, D>(deserializer: D) -> std::result::Result<Value, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Value::deserialize(deserializer)?;
+    match value.get("type").and_then(Value::as_str) {
+        Some(block_type) if !ContentBlock::TYPES.contains(&block_type) => Ok(value),
+        Some(block_type) => Err(serde::de::Error::custom(format!(
+            "invalid {} content block",
+            block_type
+        ))),
+        None => Err(serde::de::Error::missing_field("type")),
+    }
+}
+
+/// Passage returned by a retrieval system, see [`ContentBlock::search_results`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passage {
+    pub source: String,
+    pub title: String,
+    pub text: String,
+}
+
+impl Passage {
+    /// Create a passage of the document at `source`
+    pub fn new<S: AsRef<str>, T: AsRef<str>, U: AsRef<str>>(source: S, title: T, text: U) -> Self {
+        Passage {
+            source: source.as_ref().to_string(),
+            title: title.as_ref().to_string(),
+            text: text.as_ref().to_string(),
+        }
+    }
+}
+
+/// Document source: a PDF, plain text, or custom content blocks
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DocumentSource {
     #[serde(rename = "type")]
-    pub type_name: String, // "base64" or "url"
+    pub type_name: String, // "base64", "url", "file", "text", or "content"
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_type: Option<String>, // "application/pdf"
+    pub media_type: Option<String>, // "application/pdf" or "text/plain"
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<String>, // base64 data
+    pub data: Option<String>, // base64 data, or the text for text type
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>, // URL for url type
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>, // Files API id for file type
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<Vec<ContentBlock>>, // Blocks for content type
 }
 
 impl DocumentSource {
@@ -258,6 +628,8 @@ impl DocumentSource {
             media_type: Some("application/pdf".to_string()),
             data: Some(data.as_ref().to_string()),
             url: None,
+            file_id: None,
+            content: None,
         }
     }
 
@@ -268,6 +640,54 @@ impl DocumentSource {
             media_type: None,
             data: None,
             url: Some(url.as_ref().to_string()),
+            file_id: None,
+            content: None,
+        }
+    }
+
+    /// Create document source from a Files API file ID
+    pub fn from_file_id<T: AsRef<str>>(file_id: T) -> Self {
+        DocumentSource {
+            type_name: "file".to_string(),
+            media_type: None,
+            data: None,
+            url: None,
+            file_id: Some(file_id.as_ref().to_string()),
+            content: None,
+        }
+    }
+
+    /// Create a PDF document source from raw bytes
+    pub fn from_bytes<B: AsRef<[u8]>>(data: B) -> Self {
+        DocumentSource::from_base64(BASE64_STANDARD.encode(data))
+    }
+
+    /// Create a plain text document source
+    ///
+    /// Citations of text documents point at character ranges.
+    pub fn from_text<T: AsRef<str>>(text: T) -> Self {
+        DocumentSource {
+            type_name: "text".to_string(),
+            media_type: Some("text/plain".to_string()),
+            data: Some(text.as_ref().to_string()),
+            url: None,
+            file_id: None,
+            content: None,
+        }
+    }
+
+    /// Create a document source from pre-chunked text and image blocks
+    ///
+    /// The blocks are not split further, so citations of such documents
+    /// point at whole blocks.
+    pub fn from_content_blocks(blocks: Vec<ContentBlock>) -> Self {
+        DocumentSource {
+            type_name: "content".to_string(),
+            media_type: None,
+            data: None,
+            url: None,
+            file_id: None,
+            content: Some(blocks),
         }
     }
 
@@ -281,6 +701,8 @@ impl DocumentSource {
             media_type: Some("application/pdf".to_string()),
             data: Some(base64_string),
             url: None,
+            file_id: None,
+            content: None,
         })
     }
 }
@@ -290,6 +712,7 @@ impl ContentBlock {
     pub fn text<T: AsRef<str>>(text: T) -> Self {
         ContentBlock::Text {
             text: text.as_ref().to_string(),
+            citations: None,
             cache_control: None,
         }
     }
@@ -298,16 +721,18 @@ impl ContentBlock {
     pub fn text_with_cache<T: AsRef<str>>(text: T) -> Self {
         ContentBlock::Text {
             text: text.as_ref().to_string(),
+            citations: None,
             cache_control: Some(CacheControl::ephemeral()),
         }
     }
 
-    /// Create an image content block from file path
-    pub fn image_from_path<T: AsRef<str>>(media_type: MediaType, path: T) -> Self {
-        ContentBlock::Image {
-            source: ImageSource::from_path(media_type, path),
+    /// Create an image content block from file path, detecting the format
+    #[cfg(feature = "image")]
+    pub fn image_from_path<T: AsRef<str>>(path: T) -> Result<Self> {
+        Ok(ContentBlock::Image {
+            source: ImageSource::from_path(path)?,
             cache_control: None,
-        }
+        })
     }
 
     /// Create an image content block from URL
@@ -326,6 +751,14 @@ impl ContentBlock {
         }
     }
 
+    /// Create an image content block from raw image bytes
+    pub fn image_from_bytes<B: AsRef<[u8]>>(media_type: MediaType, data: B) -> Self {
+        ContentBlock::Image {
+            source: ImageSource::from_bytes(media_type, data),
+            cache_control: None,
+        }
+    }
+
     /// Create a tool use content block
     pub fn tool_use<S: AsRef<str>>(id: S, name: S, input: Value) -> Self {
         ContentBlock::ToolUse {
@@ -341,15 +774,57 @@ impl ContentBlock {
             tool_use_id: tool_use_id.as_ref().to_string(),
             content: Some(vec![ContentBlock::text(text)]),
             is_error: None,
+            cache_control: None,
+        }
+    }
+
+    /// Create a tool result content block with a value serialized as JSON text
+    pub fn tool_result_json<S: AsRef<str>, T: Serialize + ?Sized>(
+        tool_use_id: S,
+        value: &T,
+    ) -> Result<Self> {
+        Ok(ContentBlock::ToolResult {
+            tool_use_id: tool_use_id.as_ref().to_string(),
+            content: Some(vec![ContentBlock::text(serde_json::to_string(value)?)]),
+            is_error: None,
+            cache_control: None,
+        })
+    }
+
+    /// Create a tool result content block with arbitrary content blocks
+    ///
+    /// Tool results may carry text and image blocks, e.g. a screenshot
+    /// alongside a description.
+    pub fn tool_result_with_blocks<S: AsRef<str>>(
+        tool_use_id: S,
+        content: Vec<ContentBlock>,
+    ) -> Self {
+        ContentBlock::ToolResult {
+            tool_use_id: tool_use_id.as_ref().to_string(),
+            content: Some(content),
+            is_error: None,
+            cache_control: None,
         }
     }
 
+    /// Create a tool result content block with a single image
+    pub fn tool_result_image<S: AsRef<str>>(tool_use_id: S, source: ImageSource) -> Self {
+        ContentBlock::tool_result_with_blocks(
+            tool_use_id,
+            vec![ContentBlock::Image {
+                source,
+                cache_control: None,
+            }],
+        )
+    }
+
     /// Create a tool result content block with error
     pub fn tool_result_error<S: AsRef<str>>(tool_use_id: S, error_message: S) -> Self {
         ContentBlock::ToolResult {
             tool_use_id: tool_use_id.as_ref().to_string(),
             content: Some(vec![ContentBlock::text(error_message)]),
             is_error: Some(true),
+            cache_control: None,
         }
     }
 
@@ -358,6 +833,9 @@ impl ContentBlock {
         Ok(ContentBlock::Document {
             source: DocumentSource::from_path(path)?,
             cache_control: None,
+            title: None,
+            context: None,
+            citations: None,
         })
     }
 
@@ -366,13 +844,275 @@ impl ContentBlock {
         ContentBlock::Document {
             source: DocumentSource::from_url(url),
             cache_control: None,
+            title: None,
+            context: None,
+            citations: None,
         }
     }
+
+    /// Create a PDF document content block from raw bytes
+    pub fn document_from_bytes<B: AsRef<[u8]>>(data: B) -> Self {
+        ContentBlock::document(DocumentSource::from_bytes(data))
+    }
+
+    /// Create a plain text document content block
+    pub fn document_from_text<T: AsRef<str>>(text: T) -> Self {
+        ContentBlock::document(DocumentSource::from_text(text))
+    }
+
+    /// Create a document content block from pre-chunked blocks
+    pub fn document_from_content_blocks(blocks: Vec<ContentBlock>) -> Self {
+        ContentBlock::document(DocumentSource::from_content_blocks(blocks))
+    }
+
+    /// Create a document content block from any source
+    pub fn document(source: DocumentSource) -> Self {
+        ContentBlock::Document {
+            source,
+            cache_control: None,
+            title: None,
+            context: None,
+            citations: None,
+        }
+    }
+
+    /// Create an image content block referencing an uploaded file
+    pub fn image_from_file_id<T: AsRef<str>>(file_id: T) -> Self {
+        ContentBlock::Image {
+            source: ImageSource::from_file_id(file_id),
+            cache_control: None,
+        }
+    }
+
+    /// Create a document content block referencing an uploaded file
+    pub fn document_from_file_id<T: AsRef<str>>(file_id: T) -> Self {
+        ContentBlock::Document {
+            source: DocumentSource::from_file_id(file_id),
+            cache_control: None,
+            title: None,
+            context: None,
+            citations: None,
+        }
+    }
+
+    /// Create a block placing an uploaded file in the code execution container
+    pub fn container_upload<T: AsRef<str>>(file_id: T) -> Self {
+        ContentBlock::ContainerUpload {
+            file_id: file_id.as_ref().to_string(),
+            cache_control: None,
+        }
+    }
+
+    /// Create a search result block with one text block per chunk
+    pub fn search_result<S: AsRef<str>, T: AsRef<str>, C: AsRef<str>>(
+        source: S,
+        title: T,
+        chunks: Vec<C>,
+    ) -> Self {
+        ContentBlock::SearchResult {
+            source: source.as_ref().to_string(),
+            title: title.as_ref().to_string(),
+            content: chunks.iter().map(ContentBlock::text).collect(),
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// Convert retrieved passages into search result blocks, with citations
+    ///
+    /// Passages with the same source and title are merged into one block, in
+    /// order of first appearance, so the model sees each document once.
+    pub fn search_results(passages: Vec<Passage>) -> Vec<ContentBlock> {
+        let mut blocks: Vec<ContentBlock> = Vec::new();
+        for passage in passages {
+            let existing = blocks.iter_mut().find_map(|block| match block {
+                ContentBlock::SearchResult {
+                    source,
+                    title,
+                    content,
+                    ..
+                } if *source == passage.source && *title == passage.title => Some(content),
+                _ => None,
+            });
+            match existing {
+                Some(content) => content.push(ContentBlock::text(passage.text)),
+                None => blocks.push(
+                    ContentBlock::search_result(passage.source, passage.title, vec![passage.text])
+                        .with_citations(),
+                ),
+            }
+        }
+        blocks
+    }
+
+    /// Let the model cite the document or search result
+    ///
+    /// Has no effect on other block types.
+    pub fn with_citations(mut self) -> Self {
+        if let ContentBlock::Document { citations, .. }
+        | ContentBlock::SearchResult { citations, .. } = &mut self
+        {
+            *citations = Some(CitationsConfig::enabled());
+        }
+        self
+    }
+
+    /// Set the title of the document
+    ///
+    /// Has no effect on other block types.
+    pub fn with_title<T: AsRef<str>>(mut self, title: T) -> Self {
+        if let ContentBlock::Document { title: slot, .. } = &mut self {
+            *slot = Some(title.as_ref().to_string());
+        }
+        self
+    }
+
+    /// Set context about the document that the model should not cite
+    ///
+    /// Has no effect on other block types.
+    pub fn with_context<T: AsRef<str>>(mut self, context: T) -> Self {
+        if let ContentBlock::Document { context: slot, .. } = &mut self {
+            *slot = Some(context.as_ref().to_string());
+        }
+        self
+    }
+
+    /// Get the citations of a response text block
+    ///
+    /// Empty for other block types and for text without sources.
+    pub fn citations(&self) -> &[Citation] {
+        match self {
+            ContentBlock::Text {
+                citations: Some(citations),
+                ..
+            } => citations,
+            _ => &[],
+        }
+    }
+
+    /// Deserialize the input of a `tool_use` block into a typed struct
+    ///
+    /// See [`parse_input`](crate::common::tool::parse_input) for the errors;
+    /// fails with `InvalidParameter` for other block types.
+    pub fn parse_input<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        match self {
+            ContentBlock::ToolUse { name, input, .. } => {
+                crate::common::tool::parse_input(name, input)
+            }
+            other => Err(AnthropicToolError::InvalidParameter(format!(
+                "expected a tool_use block, got {}",
+                other.block_type()
+            ))),
+        }
+    }
+
+    /// Type names of every block variant, as used on the wire
+    pub const TYPES: &'static [&'static str] = &[
+        "text",
+        "image",
+        "tool_use",
+        "tool_result",
+        "server_tool_use",
+        "web_search_tool_result",
+        "code_execution_tool_result",
+        "thinking",
+        "redacted_thinking",
+        "search_result",
+        "container_upload",
+        "document",
+    ];
+
+    /// Get the wire `type` of the block (e.g. `"text"`, `"thinking"`)
+    pub fn block_type(&self) -> &str {
+        match self {
+            ContentBlock::Text { .. } => "text",
+            ContentBlock::Image { .. } => "image",
+            ContentBlock::ToolUse { .. } => "tool_use",
+            ContentBlock::ToolResult { .. } => "tool_result",
+            ContentBlock::ServerToolUse { .. } => "server_tool_use",
+            ContentBlock::WebSearchToolResult { .. } => "web_search_tool_result",
+            ContentBlock::CodeExecutionToolResult { .. } => "code_execution_tool_result",
+            ContentBlock::Thinking { .. } => "thinking",
+            ContentBlock::RedactedThinking { .. } => "redacted_thinking",
+            ContentBlock::SearchResult { .. } => "search_result",
+            ContentBlock::ContainerUpload { .. } => "container_upload",
+            ContentBlock::Document { .. } => "document",
+            ContentBlock::Other(value) => value["type"].as_str().unwrap_or("unknown"),
+        }
+    }
+
+    /// Mark the block as a prompt cache breakpoint with an `ephemeral` cache
+    ///
+    /// Blocks that cannot be cached, such as thinking, are returned unchanged.
+    pub fn with_cache(self) -> Self {
+        self.cache_control(CacheControl::ephemeral())
+    }
+
+    /// Set the cache control of the block (no-op for blocks that cannot be cached)
+    pub fn cache_control(mut self, cache_control: CacheControl) -> Self {
+        if let Some(slot) = self.cache_control_mut() {
+            *slot = Some(cache_control);
+        }
+        self
+    }
+
+    /// Get mutable access to the cache control, if the block can be cached
+    pub fn cache_control_mut(&mut self) -> Option<&mut Option<CacheControl>> {
+        match self {
+            ContentBlock::Text { cache_control, .. }
+            | ContentBlock::Image { cache_control, .. }
+            | ContentBlock::ToolResult { cache_control, .. }
+            | ContentBlock::SearchResult { cache_control, .. }
+            | ContentBlock::ContainerUpload { cache_control, .. }
+            | ContentBlock::Document { cache_control, .. } => Some(cache_control),
+            _ => None,
+        }
+    }
+
+    /// Get the cache control of the block, if set
+    pub fn get_cache_control(&self) -> Option<&CacheControl> {
+        match self {
+            ContentBlock::Text { cache_control, .. }
+            | ContentBlock::Image { cache_control, .. }
+            | ContentBlock::ToolResult { cache_control, .. }
+            | ContentBlock::SearchResult { cache_control, .. }
+            | ContentBlock::ContainerUpload { cache_control, .. }
+            | ContentBlock::Document { cache_control, .. } => cache_control.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Check whether the block carries a prompt cache breakpoint
+    pub fn has_cache_control(&self) -> bool {
+        matches!(
+            self,
+            ContentBlock::Text {
+                cache_control: Some(_),
+                ..
+            } | ContentBlock::Image {
+                cache_control: Some(_),
+                ..
+            } | ContentBlock::ToolResult {
+                cache_control: Some(_),
+                ..
+            } | ContentBlock::SearchResult {
+                cache_control: Some(_),
+                ..
+            } | ContentBlock::ContainerUpload {
+                cache_control: Some(_),
+                ..
+            } | ContentBlock::Document {
+                cache_control: Some(_),
+                ..
+            }
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::server_tool::CodeExecutionToolResultError;
 
     #[test]
     fn test_text_content_block() {
@@ -390,6 +1130,35 @@ mod tests {
         assert!(json.contains("\"type\":\"ephemeral\""));
     }
 
+    #[test]
+    fn test_with_cache_on_any_block() {
+        let blocks = [
+            ContentBlock::image_from_url("https://example.com/a.png"),
+            ContentBlock::document_from_url("https://example.com/a.pdf"),
+            ContentBlock::tool_result_text("toolu_1", "42"),
+            ContentBlock::search_result("https://example.com", "Example", vec!["chunk"]),
+            ContentBlock::container_upload("file_011"),
+        ];
+        for block in blocks {
+            assert!(!block.has_cache_control());
+            let block = block.with_cache();
+            assert!(block.has_cache_control());
+            let json = serde_json::to_value(&block).unwrap();
+            assert_eq!(
+                json["cache_control"],
+                serde_json::json!({"type": "ephemeral"})
+            );
+        }
+
+        // Not cacheable: unchanged
+        let mut thinking = ContentBlock::RedactedThinking {
+            data: "EmwK".to_string(),
+        }
+        .with_cache();
+        assert!(!thinking.has_cache_control());
+        assert!(thinking.cache_control_mut().is_none());
+    }
+
     #[test]
     fn test_image_from_url() {
         let block = ContentBlock::image_from_url("https://example.com/image.png");
@@ -416,6 +1185,49 @@ mod tests {
         assert!(json.contains("\"tool_use_id\":\"tool_123\""));
     }
 
+    #[test]
+    fn test_tool_result_json() {
+        #[derive(Serialize)]
+        struct Weather {
+            temperature: f64,
+            conditions: Vec<&'static str>,
+        }
+        let weather = Weather {
+            temperature: 21.5,
+            conditions: vec!["sunny"],
+        };
+        let block = ContentBlock::tool_result_json("tool_123", &weather).unwrap();
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["tool_use_id"], "tool_123");
+        assert_eq!(
+            json["content"][0]["text"],
+            r#"{"temperature":21.5,"conditions":["sunny"]}"#
+        );
+    }
+
+    #[test]
+    fn test_tool_result_with_image() {
+        let block = ContentBlock::tool_result_with_blocks(
+            "tool_123",
+            vec![
+                ContentBlock::text("Screenshot of the desktop"),
+                ContentBlock::image_from_base64(MediaType::Png, "iVBORw0KGgo="),
+            ],
+        );
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["content"][0]["type"], "text");
+        assert_eq!(json["content"][1]["type"], "image");
+        assert_eq!(json["content"][1]["source"]["media_type"], "image/png");
+
+        let block = ContentBlock::tool_result_image(
+            "tool_456",
+            ImageSource::from_url("https://example.com/shot.png"),
+        );
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["tool_use_id"], "tool_456");
+        assert_eq!(json["content"][0]["source"]["type"], "url");
+    }
+
     #[test]
     fn test_tool_result_error() {
         let block = ContentBlock::tool_result_error("tool_123", "Error occurred");
@@ -423,6 +1235,45 @@ mod tests {
         assert!(json.contains("\"is_error\":true"));
     }
 
+    #[test]
+    fn test_block_type_matches_serde_tag() {
+        let blocks = vec![
+            ContentBlock::text("hi"),
+            ContentBlock::image_from_url("https://example.com/a.png"),
+            ContentBlock::tool_use("id", "name", serde_json::json!({})),
+            ContentBlock::tool_result_text("id", "ok"),
+            ContentBlock::Thinking {
+                thinking: "hmm".to_string(),
+                signature: None,
+            },
+            ContentBlock::ServerToolUse {
+                id: "srvtoolu_1".to_string(),
+                name: "web_search".to_string(),
+                input: serde_json::json!({}),
+            },
+            ContentBlock::WebSearchToolResult {
+                tool_use_id: "srvtoolu_1".to_string(),
+                content: WebSearchToolResultContent::Results(Vec::new()),
+            },
+            ContentBlock::CodeExecutionToolResult {
+                tool_use_id: "srvtoolu_2".to_string(),
+                content: CodeExecutionToolResultContent::Error(CodeExecutionToolResultError {
+                    type_name: "code_execution_tool_result_error".to_string(),
+                    error_code: "unavailable".to_string(),
+                }),
+            },
+            ContentBlock::RedactedThinking {
+                data: "EmwKAhgBEgy".to_string(),
+            },
+            ContentBlock::container_upload("file_011"),
+            ContentBlock::document_from_url("https://example.com/a.pdf"),
+        ];
+        for block in blocks {
+            let value = serde_json::to_value(&block).unwrap();
+            assert_eq!(value["type"], block.block_type());
+        }
+    }
+
     #[test]
     fn test_document_from_url() {
         let block = ContentBlock::document_from_url("https://example.com/doc.pdf");
@@ -431,6 +1282,278 @@ mod tests {
         assert!(json.contains("\"url\":\"https://example.com/doc.pdf\""));
     }
 
+    #[test]
+    fn test_document_with_citations() {
+        let block = ContentBlock::document_from_url("https://example.com/doc.pdf")
+            .with_title("Report")
+            .with_context("Published 2024")
+            .with_citations();
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["title"], "Report");
+        assert_eq!(json["context"], "Published 2024");
+        assert_eq!(json["citations"], serde_json::json!({"enabled": true}));
+
+        // Other blocks are unchanged
+        let text = serde_json::to_value(ContentBlock::text("Hi").with_citations()).unwrap();
+        assert_eq!(text, serde_json::json!({"type": "text", "text": "Hi"}));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_image_from_path_passthrough() {
+        use super::super::image_check::{MAX_IMAGE_DIMENSION, image_dimensions};
+
+        let dir = std::env::temp_dir().join(format!(
+            "anthropic-tools-passthrough-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Files within the limits are sent byte for byte, in their own format
+        for (name, media_type) in [("photo.png", "image/png"), ("photo.jpg", "image/jpeg")] {
+            let path = dir.join(name);
+            image::RgbImage::from_pixel(4, 3, image::Rgb([200, 30, 60]))
+                .save(&path)
+                .unwrap();
+            let bytes = std::fs::read(&path).unwrap();
+            let source = ImageSource::from_path(path.to_str().unwrap()).unwrap();
+            assert_eq!(source.media_type.as_deref(), Some(media_type));
+            assert_eq!(source.data.unwrap(), BASE64_STANDARD.encode(&bytes));
+        }
+
+        // Files over the pixel limit are decoded, scaled down, and re-encoded
+        let wide = dir.join("wide.png");
+        image::GrayImage::new(MAX_IMAGE_DIMENSION + 1, 2)
+            .save(&wide)
+            .unwrap();
+        let bytes = std::fs::read(&wide).unwrap();
+        let source = ImageSource::from_path(wide.to_str().unwrap()).unwrap();
+        assert_eq!(source.media_type.as_deref(), Some("image/png"));
+        let data = BASE64_STANDARD.decode(source.data.unwrap()).unwrap();
+        assert_ne!(data, bytes);
+        assert_eq!(image_dimensions(&data).unwrap().0, MAX_IMAGE_DIMENSION);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_image_from_path_errors() {
+        let dir =
+            std::env::temp_dir().join(format!("anthropic-tools-image-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let png = dir.join("pixel.png");
+        image::RgbImage::new(1, 1).save(&png).unwrap();
+        let block = ContentBlock::image_from_path(png.to_str().unwrap()).unwrap();
+        assert_eq!(block.block_type(), "image");
+
+        // Valid files are sent byte for byte
+        let mut bytes = std::fs::read(&png).unwrap();
+        bytes.extend_from_slice(b"trailer");
+        std::fs::write(&png, &bytes).unwrap();
+        let source = ImageSource::from_path(png.to_str().unwrap()).unwrap();
+        assert_eq!(source.data.unwrap(), BASE64_STANDARD.encode(&bytes));
+
+        let missing = dir.join("missing.png");
+        let err = ImageSource::from_path(missing.to_str().unwrap()).unwrap_err();
+        assert_eq!(err.error_type(), "image_error");
+        assert!(err.to_string().contains("missing.png"));
+
+        let corrupt = dir.join("corrupt.jpg");
+        std::fs::write(&corrupt, b"\xFF\xD8\xFF\xE0 truncated").unwrap();
+        let err = ImageSource::from_path(corrupt.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("cannot decode image"));
+
+        let bmp = dir.join("photo.bmp");
+        std::fs::write(&bmp, b"BM\x3a\0\0\0").unwrap();
+        let err = ImageSource::from_path(bmp.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("unsupported image format"));
+
+        let renamed = dir.join("pixel.jpg");
+        std::fs::copy(&png, &renamed).unwrap();
+        let err = ImageSource::from_path(renamed.to_str().unwrap()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`jpg` does not match its contents (image/png)")
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_media_type() {
+        assert_eq!(
+            MediaType::detect(b"\x89PNG\r\n\x1a\n\0\0"),
+            Some(MediaType::Png)
+        );
+        assert_eq!(
+            MediaType::detect(&[0xFF, 0xD8, 0xFF, 0xE1]),
+            Some(MediaType::Jpeg)
+        );
+        assert_eq!(MediaType::detect(b"GIF89a\x01\0"), Some(MediaType::Gif));
+        assert_eq!(
+            MediaType::detect(b"RIFF\x24\0\0\0WEBPVP8 "),
+            Some(MediaType::Webp)
+        );
+        assert_eq!(MediaType::detect(b"RIFF\x24\0\0\0WAVEfmt "), None);
+        assert_eq!(MediaType::detect(b"%PDF-1.7"), None);
+        assert_eq!(MediaType::from_extension("JPG"), Some(MediaType::Jpeg));
+        assert_eq!(MediaType::from_extension("heic"), None);
+    }
+
+    #[cfg(feature = "image-convert")]
+    #[test]
+    fn test_convert_unsupported_formats() {
+        let dir =
+            std::env::temp_dir().join(format!("anthropic-tools-convert-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["scan.bmp", "scan.tiff"] {
+            let path = dir.join(name);
+            image::RgbImage::new(3, 2).save(&path).unwrap();
+            let source = ImageSource::from_path(path.to_str().unwrap()).unwrap();
+            assert_eq!(source.media_type.as_deref(), Some("image/png"));
+            let data = BASE64_STANDARD.decode(source.data.unwrap()).unwrap();
+            assert_eq!(MediaType::detect(&data), Some(MediaType::Png));
+        }
+
+        let bmp = std::fs::read(dir.join("scan.bmp")).unwrap();
+        let source = ImageSource::from_any_bytes(&bmp).unwrap();
+        assert_eq!(source.media_type.as_deref(), Some("image/png"));
+        let gif = b"GIF89a\x01\0\x01\0\0\0\0;";
+        let source = ImageSource::from_any_bytes(gif).unwrap();
+        assert_eq!(source.data.unwrap(), BASE64_STANDARD.encode(gif));
+
+        let heic = b"\0\0\0\x18ftypheic\0\0\0\0mif1heic";
+        let err = ImageSource::from_any_bytes(heic).unwrap_err();
+        #[cfg(not(feature = "heic"))]
+        assert!(err.to_string().contains("HEIC images are not supported"));
+        #[cfg(feature = "heic")]
+        assert!(err.to_string().contains("cannot decode HEIC image"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "heic")]
+    #[test]
+    fn test_convert_heic() {
+        let heic = include_bytes!("../../../tests/fixtures/red-16x8.heic");
+        let source = ImageSource::from_any_bytes(heic).unwrap();
+        assert_eq!(source.media_type.as_deref(), Some("image/png"));
+        let data = BASE64_STANDARD.decode(source.data.unwrap()).unwrap();
+        let img = image::load_from_memory(&data).unwrap().to_rgb8();
+        assert_eq!(img.dimensions(), (16, 8));
+        let [r, g, b] = img.get_pixel(8, 4).0;
+        assert!(
+            r > 200 && g < 50 && b < 50,
+            "expected red, got {:?}",
+            (r, g, b)
+        );
+    }
+
+    #[cfg(feature = "image-convert")]
+    #[test]
+    fn test_from_bytes_converts_and_clamps() {
+        use super::super::image_check::{MAX_IMAGE_DIMENSION, image_dimensions};
+
+        let mut bmp = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(MAX_IMAGE_DIMENSION + 100, 4)
+            .write_to(&mut bmp, image::ImageFormat::Bmp)
+            .unwrap();
+        let source = ImageSource::from_bytes(MediaType::Png, bmp.into_inner());
+        assert_eq!(source.media_type.as_deref(), Some("image/png"));
+        let data = BASE64_STANDARD.decode(source.data.unwrap()).unwrap();
+        let (width, height) = image_dimensions(&data).unwrap();
+        assert_eq!(width, MAX_IMAGE_DIMENSION);
+        assert!(height <= 4);
+
+        // Undecodable data is still sent as given
+        let source = ImageSource::from_bytes(MediaType::Png, b"not an image");
+        assert_eq!(
+            source.data.unwrap(),
+            BASE64_STANDARD.encode(b"not an image")
+        );
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let png: Vec<u8> = vec![0x89, b'P', b'N', b'G'];
+        let block = ContentBlock::image_from_bytes(MediaType::Png, &png);
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["source"]["type"], "base64");
+        assert_eq!(json["source"]["media_type"], "image/png");
+        assert_eq!(json["source"]["data"], BASE64_STANDARD.encode(&png));
+
+        let source = DocumentSource::from_bytes(b"%PDF-1.7".as_slice());
+        assert_eq!(source.media_type.as_deref(), Some("application/pdf"));
+        assert_eq!(source.data.unwrap(), "JVBERi0xLjc=");
+        let block = ContentBlock::document_from_bytes(b"%PDF-1.7");
+        assert_eq!(block.block_type(), "document");
+    }
+
+    #[test]
+    fn test_text_and_content_documents() {
+        let block = ContentBlock::document_from_text("The grass is green.").with_citations();
+        assert_eq!(
+            serde_json::to_value(&block).unwrap(),
+            serde_json::json!({
+                "type": "document",
+                "source": {"type": "text", "media_type": "text/plain", "data": "The grass is green."},
+                "citations": {"enabled": true}
+            })
+        );
+
+        let block = ContentBlock::document_from_content_blocks(vec![
+            ContentBlock::text("First chunk"),
+            ContentBlock::text("Second chunk"),
+        ]);
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(
+            json["source"],
+            serde_json::json!({
+                "type": "content",
+                "content": [
+                    {"type": "text", "text": "First chunk"},
+                    {"type": "text", "text": "Second chunk"}
+                ]
+            })
+        );
+        let parsed: ContentBlock = serde_json::from_value(json).unwrap();
+        match parsed {
+            ContentBlock::Document { source, .. } => assert_eq!(source.content.unwrap().len(), 2),
+            _ => panic!("Expected Document block"),
+        }
+    }
+
+    #[test]
+    fn test_search_results() {
+        let blocks = ContentBlock::search_results(vec![
+            Passage::new(
+                "https://docs.rs/tokio",
+                "tokio::process",
+                "Use kill_on_drop.",
+            ),
+            Passage::new("https://docs.rs/serde", "serde", "Derive Serialize."),
+            Passage::new("https://docs.rs/tokio", "tokio::process", "Pipe stdout."),
+        ]);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&blocks[0]).unwrap(),
+            serde_json::json!({
+                "type": "search_result",
+                "source": "https://docs.rs/tokio",
+                "title": "tokio::process",
+                "content": [
+                    {"type": "text", "text": "Use kill_on_drop."},
+                    {"type": "text", "text": "Pipe stdout."}
+                ],
+                "citations": {"enabled": true}
+            })
+        );
+
+        let block = ContentBlock::search_result("kb://faq/7", "Refunds", vec!["Within 30 days."]);
+        let json = serde_json::to_value(&block).unwrap();
+        assert!(json.get("citations").is_none());
+        let parsed: ContentBlock = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.block_type(), "search_result");
+    }
+
     #[test]
     fn test_deserialize_text_block() {
         let json = r#"{"type":"text","text":"Hello"}"#;
@@ -454,4 +1577,30 @@ mod tests {
             _ => panic!("Expected ToolUse block"),
         }
     }
+
+    #[test]
+    fn test_redacted_thinking_round_trip() {
+        let json = r#"{"type":"redacted_thinking","data":"EmwKAhgBEgy3va3pzix/LafPsn4a"}"#;
+        let block: ContentBlock = serde_json::from_str(json).unwrap();
+        assert!(
+            matches!(&block, ContentBlock::RedactedThinking { data } if data.ends_with("Psn4a"))
+        );
+        assert_eq!(serde_json::to_string(&block).unwrap(), json);
+    }
+
+    #[test]
+    fn test_unknown_block_round_trip() {
+        let json = r#"{"type":"hologram","frames":[1,2],"cache_control":{"type":"ephemeral"}}"#;
+        let block: ContentBlock = serde_json::from_str(json).unwrap();
+        assert!(matches!(&block, ContentBlock::Other(value) if value["frames"][1] == 2));
+        assert_eq!(block.block_type(), "hologram");
+        assert_eq!(
+            serde_json::to_value(&block).unwrap(),
+            serde_json::from_str::<Value>(json).unwrap()
+        );
+
+        // Malformed blocks of known types are still rejected
+        assert!(serde_json::from_str::<ContentBlock>(r#"{"type":"text"}"#).is_err());
+        assert!(serde_json::from_str::<ContentBlock>(r#"{"text":"Hello"}"#).is_err());
+    }
 }
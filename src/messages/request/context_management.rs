@@ -0,0 +1,368 @@
+//! Context management: automatic clearing of old context (beta).
+//!
+//! When a conversation approaches the context window, the API can clear
+//! content the model no longer needs before the request is processed:
+//!
+//! - [`ContextManagement`] - The `context_management` request parameter
+//! - [`ClearToolUses`] - Clear old tool results (and optionally tool inputs)
+//! - [`ClearThinking`] - Clear thinking blocks of earlier turns
+//! - [`ContextAmount`] - Token, tool use, or turn counts used by the edits
+//! - [`AppliedContextEdits`] - What the API cleared, reported in the response
+//!
+//! [`Messages::context_edit`](crate::messages::request::Messages::context_edit)
+//! enables the required beta header.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::context_management::{
+//!     ClearToolUses, ContextAmount,
+//! };
+//! use anthropic_tools::messages::request::Messages;
+//!
+//! let mut edit = ClearToolUses::new();
+//! edit.trigger(ContextAmount::InputTokens(100_000))
+//!     .keep(ContextAmount::ToolUses(3))
+//!     .exclude_tools(vec!["web_search"]);
+//! assert!(edit.validate().is_ok());
+//!
+//! let mut client = Messages::new();
+//! client.context_edit(edit);
+//! ```
+
+use crate::common::errors::{AnthropicToolError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Type of the edit clearing tool uses
+pub const CLEAR_TOOL_USES_TYPE: &str = "clear_tool_uses_20250919";
+
+/// Type of the edit clearing thinking blocks
+pub const CLEAR_THINKING_TYPE: &str = "clear_thinking_20251015";
+
+/// The `context_management` request parameter
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ContextManagement {
+    /// Edits applied in order
+    pub edits: Vec<ContextEdit>,
+}
+
+impl ContextManagement {
+    /// Create a configuration without edits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an edit
+    pub fn edit<E: Into<ContextEdit>>(&mut self, edit: E) -> &mut Self {
+        self.edits.push(edit.into());
+        self
+    }
+
+    /// Validate the edits
+    ///
+    /// A [`ClearThinking`] edit must come first.
+    pub fn validate(&self) -> Result<()> {
+        for (index, edit) in self.edits.iter().enumerate() {
+            match edit {
+                ContextEdit::ClearToolUses(edit) => edit.validate()?,
+                ContextEdit::ClearThinking(edit) => {
+                    if index > 0 {
+                        return Err(AnthropicToolError::InvalidParameter(
+                            "the clear_thinking context edit must come first".to_string(),
+                        ));
+                    }
+                    edit.validate()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Context edit strategy
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ContextEdit {
+    /// Clear the oldest tool results
+    #[serde(rename = "clear_tool_uses_20250919")]
+    ClearToolUses(ClearToolUses),
+
+    /// Clear thinking blocks of earlier turns
+    #[serde(rename = "clear_thinking_20251015")]
+    ClearThinking(ClearThinking),
+}
+
+impl From<ClearToolUses> for ContextEdit {
+    fn from(edit: ClearToolUses) -> Self {
+        ContextEdit::ClearToolUses(edit)
+    }
+}
+
+impl From<ClearThinking> for ContextEdit {
+    fn from(edit: ClearThinking) -> Self {
+        ContextEdit::ClearThinking(edit)
+    }
+}
+
+/// Count used by the settings of a context edit
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ContextAmount {
+    /// Number of input tokens
+    InputTokens(u64),
+    /// Number of tool uses
+    ToolUses(u64),
+    /// Number of assistant turns with thinking
+    ThinkingTurns(u64),
+}
+
+impl ContextAmount {
+    /// Get the count
+    pub fn value(&self) -> u64 {
+        match self {
+            ContextAmount::InputTokens(value)
+            | ContextAmount::ToolUses(value)
+            | ContextAmount::ThinkingTurns(value) => *value,
+        }
+    }
+}
+
+/// Clear the oldest tool results once the context grows past a threshold
+///
+/// Unset settings use the API defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ClearToolUses {
+    /// When clearing starts (input tokens or tool uses)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<ContextAmount>,
+
+    /// Most recent tool uses to keep
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep: Option<ContextAmount>,
+
+    /// Minimum input tokens to clear, so cache invalidation pays off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear_at_least: Option<ContextAmount>,
+
+    /// Tools whose results are never cleared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_tools: Option<Vec<String>>,
+
+    /// Also clear the inputs of the cleared tool calls
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear_tool_inputs: Option<bool>,
+}
+
+impl ClearToolUses {
+    /// Create the edit with the API defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start clearing at this many input tokens or tool uses
+    pub fn trigger(&mut self, trigger: ContextAmount) -> &mut Self {
+        self.trigger = Some(trigger);
+        self
+    }
+
+    /// Keep this many of the most recent tool uses
+    pub fn keep(&mut self, keep: ContextAmount) -> &mut Self {
+        self.keep = Some(keep);
+        self
+    }
+
+    /// Clear at least this many input tokens, or nothing
+    pub fn clear_at_least(&mut self, tokens: u64) -> &mut Self {
+        self.clear_at_least = Some(ContextAmount::InputTokens(tokens));
+        self
+    }
+
+    /// Never clear the results of these tools
+    pub fn exclude_tools<S: AsRef<str>>(&mut self, tools: Vec<S>) -> &mut Self {
+        self.exclude_tools = Some(tools.iter().map(|t| t.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Also clear the inputs of the cleared tool calls
+    pub fn clear_tool_inputs(&mut self, enabled: bool) -> &mut Self {
+        self.clear_tool_inputs = Some(enabled);
+        self
+    }
+
+    /// Validate the settings
+    pub fn validate(&self) -> Result<()> {
+        if matches!(self.trigger, Some(ContextAmount::ThinkingTurns(_))) {
+            return Err(AnthropicToolError::InvalidParameter(
+                "clear_tool_uses trigger must count input tokens or tool uses".to_string(),
+            ));
+        }
+        if self
+            .keep
+            .is_some_and(|keep| !matches!(keep, ContextAmount::ToolUses(_)))
+        {
+            return Err(AnthropicToolError::InvalidParameter(
+                "clear_tool_uses keep must count tool uses".to_string(),
+            ));
+        }
+        if self
+            .trigger
+            .iter()
+            .chain(&self.clear_at_least)
+            .any(|amount| amount.value() == 0)
+        {
+            return Err(AnthropicToolError::InvalidParameter(
+                "clear_tool_uses trigger and clear_at_least must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Clear the thinking blocks of earlier assistant turns
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ClearThinking {
+    /// Most recent turns whose thinking is kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep: Option<ContextAmount>,
+}
+
+impl ClearThinking {
+    /// Create the edit with the API defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the thinking of this many of the most recent turns
+    pub fn keep_turns(&mut self, turns: u64) -> &mut Self {
+        self.keep = Some(ContextAmount::ThinkingTurns(turns));
+        self
+    }
+
+    /// Validate the settings
+    pub fn validate(&self) -> Result<()> {
+        match self.keep {
+            None | Some(ContextAmount::ThinkingTurns(_)) => Ok(()),
+            Some(_) => Err(AnthropicToolError::InvalidParameter(
+                "clear_thinking keep must count thinking turns".to_string(),
+            )),
+        }
+    }
+}
+
+/// Context edits the API applied, from the `context_management` response field
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AppliedContextEdits {
+    #[serde(default)]
+    pub applied_edits: Vec<AppliedContextEdit>,
+}
+
+impl AppliedContextEdits {
+    /// Get the input tokens cleared by all edits
+    pub fn cleared_input_tokens(&self) -> u64 {
+        self.applied_edits
+            .iter()
+            .map(|edit| edit.cleared_input_tokens)
+            .sum()
+    }
+}
+
+/// Context edit applied to a request
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AppliedContextEdit {
+    /// Edit type, e.g. [`CLEAR_TOOL_USES_TYPE`]
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// Input tokens removed from the prompt
+    #[serde(default)]
+    pub cleared_input_tokens: u64,
+
+    /// Tool uses cleared (`clear_tool_uses`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleared_tool_uses: Option<u64>,
+
+    /// Turns whose thinking was cleared (`clear_thinking`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleared_thinking_turns: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_management_serialize() {
+        let mut tool_uses = ClearToolUses::new();
+        tool_uses
+            .trigger(ContextAmount::InputTokens(30_000))
+            .keep(ContextAmount::ToolUses(3))
+            .clear_at_least(5_000)
+            .exclude_tools(vec!["web_search"]);
+        let mut thinking = ClearThinking::new();
+        thinking.keep_turns(2);
+        let mut config = ContextManagement::new();
+        config.edit(thinking).edit(tool_uses);
+        assert!(config.validate().is_ok());
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"edits": [
+                {"type": "clear_thinking_20251015",
+                 "keep": {"type": "thinking_turns", "value": 2}},
+                {"type": "clear_tool_uses_20250919",
+                 "trigger": {"type": "input_tokens", "value": 30000},
+                 "keep": {"type": "tool_uses", "value": 3},
+                 "clear_at_least": {"type": "input_tokens", "value": 5000},
+                 "exclude_tools": ["web_search"]}
+            ]})
+        );
+        let parsed: ContextManagement = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, config);
+
+        // Defaults only
+        let mut config = ContextManagement::new();
+        config.edit(ClearToolUses::new());
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::json!({"edits": [{"type": "clear_tool_uses_20250919"}]})
+        );
+    }
+
+    #[test]
+    fn test_context_management_validate() {
+        let mut config = ContextManagement::new();
+        config.edit(ClearToolUses::new()).edit(ClearThinking::new());
+        assert!(config.validate().is_err());
+
+        let mut edit = ClearToolUses::new();
+        edit.keep(ContextAmount::InputTokens(1_000));
+        assert!(edit.validate().is_err());
+
+        let mut edit = ClearToolUses::new();
+        edit.trigger(ContextAmount::ToolUses(0));
+        assert!(edit.validate().is_err());
+
+        let edit = ClearThinking {
+            keep: Some(ContextAmount::ToolUses(1)),
+        };
+        assert!(edit.validate().is_err());
+    }
+
+    #[test]
+    fn test_applied_edits_deserialize() {
+        let applied: AppliedContextEdits = serde_json::from_value(serde_json::json!({
+            "applied_edits": [
+                {"type": "clear_thinking_20251015", "cleared_thinking_turns": 3,
+                 "cleared_input_tokens": 15000},
+                {"type": "clear_tool_uses_20250919", "cleared_tool_uses": 8,
+                 "cleared_input_tokens": 50000}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(applied.cleared_input_tokens(), 65_000);
+        assert_eq!(applied.applied_edits[1].type_name, CLEAR_TOOL_USES_TYPE);
+        assert_eq!(applied.applied_edits[1].cleared_tool_uses, Some(8));
+        assert_eq!(applied.applied_edits[0].cleared_thinking_turns, Some(3));
+    }
+}
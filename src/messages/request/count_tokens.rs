@@ -0,0 +1,119 @@
+//! Token counting for the Messages API.
+//!
+//! [`Messages::count_tokens`] sends the request body (without `max_tokens`
+//! and sampling parameters) to `/v1/messages/count_tokens` and returns a
+//! [`TokenCount`], so requests can be budgeted and cache breakpoints chosen
+//! before paying for a real call.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//!
+//! # async fn example() -> Result<()> {
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .system("You are a helpful assistant.")
+//!     .user("Hello!");
+//!
+//! let count = client.count_tokens().await?;
+//! println!("{} input tokens", count.input_tokens);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Messages::count_tokens`]: crate::messages::request::Messages::count_tokens
+
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::tool::ToolDefinition;
+use crate::messages::request::body::{Body, ToolChoice};
+use crate::messages::request::mcp::McpServer;
+use crate::messages::request::message::{self, Message, SystemPrompt};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Path of the token counting endpoint below the base URL
+pub(crate) const COUNT_TOKENS_PATH: &str = "/v1/messages/count_tokens";
+
+/// Result of a token count
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenCount {
+    /// Total input tokens of the request (system, tools, and messages)
+    pub input_tokens: usize,
+}
+
+/// Request body accepted by the token counting endpoint
+#[derive(Serialize, Debug)]
+pub(crate) struct CountTokensBody<'a> {
+    model: &'a str,
+
+    #[serde(serialize_with = "serialize_messages")]
+    messages: &'a [Message],
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<&'a SystemPrompt>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<&'a Vec<ToolDefinition>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<&'a ToolChoice>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mcp_servers: Option<&'a Vec<McpServer>>,
+}
+
+impl<'a> CountTokensBody<'a> {
+    /// Take the countable fields of a request body
+    pub(crate) fn new(body: &'a Body) -> Result<Self> {
+        if body.model.is_empty() {
+            return Err(AnthropicToolError::MissingRequiredField(
+                "model".to_string(),
+            ));
+        }
+        if body.messages.is_empty() {
+            return Err(AnthropicToolError::MissingRequiredField(
+                "messages".to_string(),
+            ));
+        }
+        Ok(CountTokensBody {
+            model: &body.model,
+            messages: &body.messages,
+            system: body.system.as_ref(),
+            tools: body.tools.as_ref(),
+            tool_choice: body.tool_choice.as_ref(),
+            mcp_servers: body.mcp_servers.as_ref(),
+        })
+    }
+}
+
+fn serialize_messages<S: Serializer>(
+    messages: &&[Message],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    message::serialize_for_api(messages, serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_tokens_body_omits_generation_params() {
+        let mut body = Body::new("claude-sonnet-4-20250514", 1024);
+        body.messages.push(Message::user("Hello!"));
+        body.stream = Some(true);
+
+        let json = serde_json::to_value(CountTokensBody::new(&body).unwrap()).unwrap();
+        assert_eq!(json["model"], "claude-sonnet-4-20250514");
+        assert_eq!(json["messages"][0]["role"], "user");
+        assert!(json.get("max_tokens").is_none());
+        assert!(json.get("stream").is_none());
+    }
+
+    #[test]
+    fn test_count_tokens_body_requires_messages() {
+        let body = Body::new("claude-sonnet-4-20250514", 1024);
+        assert!(CountTokensBody::new(&body).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Deduplication of repeated attachments.
+//!
+//! Document-heavy conversations often resend the same image or PDF in several
+//! messages. [`AttachmentDedup`] detects inline copies by the SHA-256 hash of
+//! their content and, before the request is sent:
+//!
+//! - Replaces every copy with a `file` source when an uploaded file ID is
+//!   registered for the hash (see [`AttachmentDedup::file_id`])
+//! - Otherwise replaces later copies with a short text reference to the first
+//!   one (disable with [`AttachmentDedup::replace_repeats`])
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::Messages;
+//! use anthropic_tools::messages::request::attachment::Attachment;
+//! use anthropic_tools::messages::request::dedup::{AttachmentDedup, content_hash};
+//!
+//! let report = b"%PDF-1.7 ...".to_vec();
+//!
+//! let mut dedup = AttachmentDedup::new();
+//! dedup.file_id(content_hash(&report), "file_011CNha8iCJcU1wXNR6q4V8w");
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .dedup_attachments(dedup)
+//!     .user_with_attachment("Summarize this", Attachment::bytes(report, "application/pdf"))
+//!     .unwrap();
+//! ```
+
+use crate::messages::request::content::{ContentBlock, DocumentSource, ImageSource};
+use crate::messages::request::message::Message;
+use base64::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Settings for replacing repeated attachments
+#[derive(Debug, Clone)]
+pub struct AttachmentDedup {
+    /// Uploaded file IDs by content hash
+    pub file_ids: HashMap<String, String>,
+
+    /// Whether later copies without a file ID are replaced by a text reference
+    pub replace_repeats: bool,
+}
+
+impl Default for AttachmentDedup {
+    fn default() -> Self {
+        AttachmentDedup {
+            file_ids: HashMap::new(),
+            replace_repeats: true,
+        }
+    }
+}
+
+impl AttachmentDedup {
+    /// Create the default settings (replace repeats, no file IDs)
+    pub fn new() -> Self {
+        AttachmentDedup::default()
+    }
+
+    /// Register an uploaded file ID for content with the given hash
+    pub fn file_id<H: AsRef<str>, F: AsRef<str>>(&mut self, hash: H, file_id: F) -> &mut Self {
+        self.file_ids
+            .insert(hash.as_ref().to_string(), file_id.as_ref().to_string());
+        self
+    }
+
+    /// Set whether later copies are replaced by a text reference
+    pub fn replace_repeats(&mut self, enabled: bool) -> &mut Self {
+        self.replace_repeats = enabled;
+        self
+    }
+
+    /// Deduplicate attachments in place and return the number of blocks replaced
+    ///
+    /// Only top-level image and document blocks with inline (`base64` or
+    /// `text`) sources are considered. A text reference keeps the
+    /// `cache_control` of the block it replaces.
+    pub fn apply(&self, messages: &mut [Message]) -> usize {
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        let mut replaced = 0;
+
+        for (message_index, message) in messages.iter_mut().enumerate() {
+            for block in &mut message.content {
+                let Some(hash) = block_hash(block) else {
+                    continue;
+                };
+
+                if let Some(file_id) = self.file_ids.get(&hash) {
+                    use_file_source(block, file_id);
+                    replaced += 1;
+                    continue;
+                }
+
+                match first_seen.get(&hash) {
+                    Some(&first) if self.replace_repeats => {
+                        let mut reference = ContentBlock::text(format!(
+                            "[Repeated {}: identical to the one in message {} above]",
+                            block.block_type(),
+                            first + 1
+                        ));
+                        // Keep the cache breakpoint where the caller put it
+                        if let Some(cache_control) =
+                            block.cache_control_mut().and_then(Option::take)
+                        {
+                            reference = reference.cache_control(cache_control);
+                        }
+                        *block = reference;
+                        replaced += 1;
+                    }
+                    Some(_) => {}
+                    None => {
+                        first_seen.insert(hash, message_index);
+                    }
+                }
+            }
+        }
+        replaced
+    }
+}
+
+/// SHA-256 hash (64 lowercase hex characters) of attachment content
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Hash of the inline content of an image or document block
+///
+/// Returns `None` for other blocks and for URL or file sources.
+pub fn block_hash(block: &ContentBlock) -> Option<String> {
+    let (type_name, data) = match block {
+        ContentBlock::Image { source, .. } => (source.type_name.as_str(), source.data.as_ref()?),
+        ContentBlock::Document { source, .. } => (source.type_name.as_str(), source.data.as_ref()?),
+        _ => return None,
+    };
+    match type_name {
+        "base64" => Some(content_hash(&BASE64_STANDARD.decode(data).ok()?)),
+        "text" => Some(content_hash(data.as_bytes())),
+        _ => None,
+    }
+}
+
+/// Point an image or document block at an uploaded file
+fn use_file_source(block: &mut ContentBlock, file_id: &str) {
+    match block {
+        ContentBlock::Image { source, .. } => *source = ImageSource::from_file_id(file_id),
+        ContentBlock::Document { source, .. } => *source = DocumentSource::from_file_id(file_id),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::request::attachment::Attachment;
+
+    fn pdf_message(data: &[u8]) -> Message {
+        Message::user_with_attachment(
+            "Read this",
+            Attachment::bytes(data.to_vec(), "application/pdf"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_repeats_replaced_by_reference() {
+        let mut messages = vec![
+            pdf_message(b"%PDF report"),
+            Message::assistant("Done."),
+            pdf_message(b"%PDF report"),
+            pdf_message(b"%PDF other"),
+        ];
+
+        let replaced = AttachmentDedup::new().apply(&mut messages);
+        assert_eq!(replaced, 1);
+        assert!(matches!(
+            messages[0].content[0],
+            ContentBlock::Document { .. }
+        ));
+        match &messages[2].content[0] {
+            ContentBlock::Text { text, .. } => {
+                assert_eq!(
+                    text,
+                    "[Repeated document: identical to the one in message 1 above]"
+                )
+            }
+            other => panic!("Expected Text, got {:?}", other),
+        }
+        assert!(matches!(
+            messages[3].content[0],
+            ContentBlock::Document { .. }
+        ));
+    }
+
+    #[test]
+    fn test_reference_keeps_cache_control() {
+        let mut cached = pdf_message(b"%PDF report");
+        cached.content[0] = cached.content[0].clone().with_cache();
+        let mut messages = vec![pdf_message(b"%PDF report"), cached];
+
+        assert_eq!(AttachmentDedup::new().apply(&mut messages), 1);
+        match &messages[1].content[0] {
+            ContentBlock::Text {
+                text,
+                cache_control,
+                ..
+            } => {
+                assert!(text.starts_with("[Repeated document"));
+                assert!(cache_control.is_some());
+            }
+            other => panic!("Expected Text, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_known_file_id_replaces_every_copy() {
+        let mut messages = vec![pdf_message(b"%PDF report"), pdf_message(b"%PDF report")];
+
+        let mut dedup = AttachmentDedup::new();
+        dedup.file_id(content_hash(b"%PDF report"), "file_123");
+        assert_eq!(dedup.apply(&mut messages), 2);
+
+        for message in &messages {
+            match &message.content[0] {
+                ContentBlock::Document { source, .. } => {
+                    assert_eq!(source.type_name, "file");
+                    assert_eq!(source.file_id.as_deref(), Some("file_123"));
+                }
+                other => panic!("Expected Document, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_replace_repeats_disabled() {
+        let mut messages = vec![pdf_message(b"%PDF report"), pdf_message(b"%PDF report")];
+
+        let mut dedup = AttachmentDedup::new();
+        dedup.replace_repeats(false);
+        assert_eq!(dedup.apply(&mut messages), 0);
+        assert!(matches!(
+            messages[1].content[0],
+            ContentBlock::Document { .. }
+        ));
+    }
+}
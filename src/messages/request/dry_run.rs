@@ -0,0 +1,145 @@
+//! Fully rendered requests that are not sent.
+//!
+//! [`Messages::dry_run`](crate::messages::request::Messages::dry_run) renders
+//! the exact JSON body and headers a call to `post()` would send, with the
+//! API key and other credentials masked (see [`is_sensitive_header`]). Use it
+//! for request auditing, prompt review tooling, and deterministic tests.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::Messages;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let mut client = Messages::with_api_key("sk-ant-api03-secret-key");
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .user("Hello!");
+//!
+//! let rendered = client.dry_run().await?;
+//! assert_eq!(rendered.header("x-api-key"), Some("sk-a...-key"));
+//! println!("{}", rendered);
+//! # Ok(())
+//! # }
+//! ```
+
+use serde::Serialize;
+use std::fmt;
+
+/// Request rendered by a dry run
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DryRun {
+    /// HTTP method
+    pub method: String,
+
+    /// Endpoint URL
+    pub url: String,
+
+    /// Headers in the order they are sent (credentials masked)
+    pub headers: Vec<(String, String)>,
+
+    /// Serialized JSON body, byte-identical to what would be sent
+    pub body: String,
+}
+
+impl DryRun {
+    /// Get a header value by name (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse the body into a JSON value
+    pub fn body_json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for DryRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.method, self.url)?;
+        for (name, value) in &self.headers {
+            writeln!(f, "{}: {}", name, value)?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", self.body)
+    }
+}
+
+/// Whether a header may carry a credential and is masked in a dry run
+///
+/// Covers `x-api-key`, `authorization`, `proxy-authorization`, `cookie`, and
+/// any header whose name contains `token`, `secret`, or `key`.
+pub fn is_sensitive_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    matches!(
+        name.as_str(),
+        "authorization" | "proxy-authorization" | "cookie" | "set-cookie"
+    ) || ["token", "secret", "key"]
+        .iter()
+        .any(|word| name.contains(word))
+}
+
+/// Mask a secret, keeping only a short prefix and suffix
+pub(crate) fn mask_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 12 {
+        return "*".repeat(chars.len());
+    }
+    let prefix: String = chars[..4].iter().collect();
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}...{}", prefix, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_secret() {
+        assert_eq!(mask_secret("sk-ant-api03-abcdefgh"), "sk-a...efgh");
+        assert_eq!(mask_secret("short"), "*****");
+        assert_eq!(mask_secret(""), "");
+    }
+
+    #[test]
+    fn test_is_sensitive_header() {
+        for name in [
+            "x-api-key",
+            "Authorization",
+            "proxy-authorization",
+            "Cookie",
+            "x-session-token",
+            "x-client-secret",
+            "x-gateway-key",
+        ] {
+            assert!(is_sensitive_header(name), "{}", name);
+        }
+        for name in [
+            "content-type",
+            "anthropic-version",
+            "anthropic-beta",
+            "x-org",
+        ] {
+            assert!(!is_sensitive_header(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let dry_run = DryRun {
+            method: "POST".to_string(),
+            url: "https://api.anthropic.com/v1/messages".to_string(),
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: "{}".to_string(),
+        };
+        assert_eq!(
+            dry_run.to_string(),
+            "POST https://api.anthropic.com/v1/messages\ncontent-type: application/json\n\n{}"
+        );
+        assert_eq!(dry_run.header("Content-Type"), Some("application/json"));
+    }
+}
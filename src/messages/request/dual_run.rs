@@ -0,0 +1,262 @@
+//! Redundant requests to two models.
+//!
+//! [`Messages::dual_run`] sends the same request to two models concurrently
+//! and returns both outcomes as a [`DualRun`]. [`DualRun::reconcile`] then
+//! picks one response with a [`Reconcile`] strategy:
+//!
+//! - [`Reconcile::PreferA`] / [`Reconcile::PreferB`] - Fixed preference
+//! - [`Reconcile::Judge`] - Ask a third model which answer is better
+//! - [`Reconcile::Custom`] - Caller-supplied comparison
+//!
+//! If only one model succeeded, its response is used regardless of strategy,
+//! so a dual run also hedges against one model being unavailable.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::messages::request::dual_run::Reconcile;
+//!
+//! # async fn example() -> Result<()> {
+//! let mut client = Messages::new();
+//! client.max_tokens(1024).user("Explain lifetimes in Rust");
+//!
+//! let run = client
+//!     .dual_run("claude-sonnet-4-20250514", "claude-opus-4-20250514")
+//!     .await?;
+//! let chosen = run
+//!     .reconcile(&client, &Reconcile::judge("claude-sonnet-4-20250514"))
+//!     .await?;
+//! println!("{:?}: {}", chosen.side, chosen.response.get_text());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Messages::dual_run`]: crate::messages::request::Messages::dual_run
+
+use crate::common::errors::Result;
+use crate::messages::request::Messages;
+use crate::messages::request::body::Body;
+use crate::messages::request::content::ContentBlock;
+use crate::messages::request::message::{Message, SystemPrompt};
+use crate::messages::request::role::Role;
+use crate::messages::response::Response;
+use std::fmt;
+use std::sync::Arc;
+
+/// System prompt of the judge model
+const JUDGE_SYSTEM: &str = "You compare two candidate answers to the same request. \
+Reply with exactly one letter: A if answer A is better, B if answer B is better.";
+
+/// Output token limit of the judge request
+const JUDGE_MAX_TOKENS: usize = 8;
+
+/// One of the two models of a dual run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+/// Comparison function for [`Reconcile::Custom`]
+pub type ReconcileFn = Arc<dyn Fn(&Response, &Response) -> Side + Send + Sync>;
+
+/// Strategy for choosing between the two responses
+#[derive(Clone)]
+pub enum Reconcile {
+    /// Always use model A's response
+    PreferA,
+
+    /// Always use model B's response
+    PreferB,
+
+    /// Ask a judge model to pick the better response
+    ///
+    /// An unclear verdict falls back to model A.
+    Judge { model: String },
+
+    /// Pick with a custom function
+    Custom(ReconcileFn),
+}
+
+impl Reconcile {
+    /// Judge with the given model
+    pub fn judge<T: AsRef<str>>(model: T) -> Self {
+        Reconcile::Judge {
+            model: model.as_ref().to_string(),
+        }
+    }
+
+    /// Pick with a custom function
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&Response, &Response) -> Side + Send + Sync + 'static,
+    {
+        Reconcile::Custom(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for Reconcile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reconcile::PreferA => f.write_str("PreferA"),
+            Reconcile::PreferB => f.write_str("PreferB"),
+            Reconcile::Judge { model } => f.debug_struct("Judge").field("model", model).finish(),
+            Reconcile::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+/// Outcomes of the same request sent to two models
+#[derive(Debug)]
+pub struct DualRun {
+    /// Model A
+    pub model_a: String,
+    /// Model B
+    pub model_b: String,
+    /// Outcome of model A
+    pub a: Result<Response>,
+    /// Outcome of model B
+    pub b: Result<Response>,
+    /// Request body sent to both models (with model A's name)
+    pub(crate) body: Body,
+}
+
+/// Response chosen by [`DualRun::reconcile`]
+#[derive(Debug, Clone)]
+pub struct Reconciled {
+    /// Model the response came from
+    pub side: Side,
+    /// Chosen response
+    pub response: Response,
+    /// Why the response was chosen, when not by a fixed preference
+    pub reason: Option<String>,
+}
+
+impl DualRun {
+    /// Check whether both models answered
+    pub fn both_succeeded(&self) -> bool {
+        self.a.is_ok() && self.b.is_ok()
+    }
+
+    /// Choose one response
+    ///
+    /// `client` sends the judge request for [`Reconcile::Judge`]. Fails only if
+    /// both models failed, with model A's error.
+    pub async fn reconcile(self, client: &Messages, strategy: &Reconcile) -> Result<Reconciled> {
+        let (a, b) = match (self.a, self.b) {
+            (Ok(a), Ok(b)) => (a, b),
+            (Ok(a), Err(error)) => {
+                return Ok(Reconciled {
+                    side: Side::A,
+                    response: a,
+                    reason: Some(format!("{} failed: {}", self.model_b, error)),
+                });
+            }
+            (Err(error), Ok(b)) => {
+                return Ok(Reconciled {
+                    side: Side::B,
+                    response: b,
+                    reason: Some(format!("{} failed: {}", self.model_a, error)),
+                });
+            }
+            (Err(error), Err(_)) => return Err(error),
+        };
+
+        let (side, reason) = match strategy {
+            Reconcile::PreferA => (Side::A, None),
+            Reconcile::PreferB => (Side::B, None),
+            Reconcile::Custom(pick) => (pick(&a, &b), None),
+            Reconcile::Judge { model } => {
+                let body = judge_body(model, &self.body, &a, &b);
+                let verdict = client.with_body(body).post().await?.get_text();
+                match parse_verdict(&verdict) {
+                    Some(side) => (side, Some(format!("judged by {}", model))),
+                    None => (
+                        Side::A,
+                        Some(format!("unclear verdict from {}: {:?}", model, verdict)),
+                    ),
+                }
+            }
+        };
+        let response = match side {
+            Side::A => a,
+            Side::B => b,
+        };
+        Ok(Reconciled {
+            side,
+            response,
+            reason,
+        })
+    }
+}
+
+/// Build the request asking the judge to compare both answers
+fn judge_body(model: &str, original: &Body, a: &Response, b: &Response) -> Body {
+    let mut transcript = String::new();
+    let system = match &original.system {
+        Some(SystemPrompt::Text(text)) => text.clone(),
+        Some(SystemPrompt::Blocks(blocks)) => blocks
+            .iter()
+            .map(|block| block.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => String::new(),
+    };
+    if !system.is_empty() {
+        transcript.push_str(&format!("<system>\n{}\n</system>\n", system));
+    }
+    for message in &original.messages {
+        let role = match message.role {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        };
+        let text: Vec<&str> = message
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        transcript.push_str(&format!("<{0}>\n{1}\n</{0}>\n", role, text.join("\n")));
+    }
+
+    let mut body = Body::new(model, JUDGE_MAX_TOKENS);
+    body.system = Some(SystemPrompt::text(JUDGE_SYSTEM));
+    body.messages.push(Message::user(format!(
+        "<request>\n{}</request>\n\n<answer_a>\n{}\n</answer_a>\n\n<answer_b>\n{}\n</answer_b>",
+        transcript,
+        a.get_text(),
+        b.get_text()
+    )));
+    body
+}
+
+/// Read the judge's verdict (the first word of the reply)
+fn parse_verdict(text: &str) -> Option<Side> {
+    let word: String = text
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric())
+        .collect();
+    match word.as_str() {
+        "A" | "a" => Some(Side::A),
+        "B" | "b" => Some(Side::B),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(parse_verdict("A"), Some(Side::A));
+        assert_eq!(parse_verdict("\nB."), Some(Side::B));
+        assert_eq!(parse_verdict("Both"), None);
+        assert_eq!(parse_verdict("Neither"), None);
+        assert_eq!(parse_verdict(""), None);
+    }
+}
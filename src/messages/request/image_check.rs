@@ -0,0 +1,401 @@
+//! Pre-flight checks of the images in a request.
+//!
+//! The API rejects a whole request when one image breaks its limits. The
+//! checks here find every such image locally, before anything is sent:
+//!
+//! - Format: PNG, JPEG, GIF, or WebP, matching the declared media type
+//! - Encoded size: at most [`MAX_IMAGE_BYTES`] of base64 per image
+//! - Dimensions: at most [`MAX_IMAGE_DIMENSION`] pixels per side, or
+//!   [`MAX_IMAGE_DIMENSION_MANY`] when a request has more than
+//!   [`MANY_IMAGES_THRESHOLD`] images
+//! - Count: at most [`MAX_IMAGES_PER_REQUEST`] images
+//!
+//! Images referenced by URL or file ID are only counted, as their data is not
+//! available locally. [`Body::validate`](super::body::Body::validate) runs the
+//! checks and fails with [`AnthropicToolError::InvalidImages`], which lists
+//! every violation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::content::{ContentBlock, MediaType};
+//! use anthropic_tools::messages::request::image_check::{ImageIssue, check_images};
+//! use anthropic_tools::messages::request::message::Message;
+//! use anthropic_tools::messages::request::role::Role;
+//!
+//! let jpeg = [0xFF, 0xD8, 0xFF, 0xE0];
+//! let message = Message::new(
+//!     Role::User,
+//!     vec![ContentBlock::image_from_bytes(MediaType::Png, jpeg)],
+//! );
+//! let violations = check_images(&[message]);
+//! assert_eq!(violations[0].location, "messages[0].content[0]");
+//! assert!(matches!(violations[0].issue, ImageIssue::FormatMismatch { .. }));
+//! ```
+
+use super::content::{ContentBlock, ImageSource, MediaType};
+use super::message::Message;
+use crate::common::errors::{AnthropicToolError, Result};
+use base64::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest base64-encoded image accepted by the API, in bytes
+pub const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Longest image side accepted by the API, in pixels
+pub const MAX_IMAGE_DIMENSION: u32 = 8000;
+
+/// Longest image side accepted when a request has many images, in pixels
+pub const MAX_IMAGE_DIMENSION_MANY: u32 = 2000;
+
+/// Number of images above which [`MAX_IMAGE_DIMENSION_MANY`] applies
+pub const MANY_IMAGES_THRESHOLD: usize = 20;
+
+/// Most images accepted in a single request
+pub const MAX_IMAGES_PER_REQUEST: usize = 100;
+
+/// Problem with an image that the API would reject
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageIssue {
+    /// Media type other than PNG, JPEG, GIF, or WebP
+    UnsupportedFormat { media_type: String },
+    /// Data in a different format than its media type says
+    FormatMismatch { declared: String, actual: MediaType },
+    /// Data that is not valid base64 or not a readable image
+    InvalidData { message: String },
+    /// Base64 data larger than the limit
+    TooLarge { bytes: usize, limit: usize },
+    /// Width or height above the limit
+    DimensionsTooLarge { width: u32, height: u32, limit: u32 },
+    /// More images in the request than allowed
+    TooManyImages { count: usize, limit: usize },
+}
+
+impl fmt::Display for ImageIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageIssue::UnsupportedFormat { media_type } => write!(
+                f,
+                "unsupported format {} (use png, jpeg, gif, or webp)",
+                media_type
+            ),
+            ImageIssue::FormatMismatch { declared, actual } => {
+                write!(f, "data is {} but the media type is {}", actual, declared)
+            }
+            ImageIssue::InvalidData { message } => write!(f, "invalid image data: {}", message),
+            ImageIssue::TooLarge { bytes, limit } => write!(
+                f,
+                "{} bytes encoded, above the limit of {} bytes; downscale or recompress it",
+                bytes, limit
+            ),
+            ImageIssue::DimensionsTooLarge {
+                width,
+                height,
+                limit,
+            } => write!(
+                f,
+                "{}x{} pixels, above the limit of {} pixels per side; resize it",
+                width, height, limit
+            ),
+            ImageIssue::TooManyImages { count, limit } => write!(
+                f,
+                "{} images, above the limit of {} per request",
+                count, limit
+            ),
+        }
+    }
+}
+
+/// Image issue with the location of the image in the request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageViolation {
+    /// Path of the block, e.g. `messages[0].content[1]` (`messages` for the
+    /// request as a whole)
+    pub location: String,
+    pub issue: ImageIssue,
+}
+
+impl fmt::Display for ImageViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.issue)
+    }
+}
+
+/// Check every image of a conversation, including images in tool results
+///
+/// Returns all violations, in order; an empty list means the images are fine.
+pub fn check_images(messages: &[Message]) -> Vec<ImageViolation> {
+    let mut images = Vec::new();
+    for (i, message) in messages.iter().enumerate() {
+        collect_images(
+            &message.content,
+            &format!("messages[{}].content", i),
+            &mut images,
+        );
+    }
+
+    let mut violations = Vec::new();
+    if images.len() > MAX_IMAGES_PER_REQUEST {
+        violations.push(ImageViolation {
+            location: "messages".to_string(),
+            issue: ImageIssue::TooManyImages {
+                count: images.len(),
+                limit: MAX_IMAGES_PER_REQUEST,
+            },
+        });
+    }
+    let max_dimension = if images.len() > MANY_IMAGES_THRESHOLD {
+        MAX_IMAGE_DIMENSION_MANY
+    } else {
+        MAX_IMAGE_DIMENSION
+    };
+    for (location, source) in images {
+        violations.extend(check_image(source, max_dimension).into_iter().map(|issue| {
+            ImageViolation {
+                location: location.clone(),
+                issue,
+            }
+        }));
+    }
+    violations
+}
+
+/// Check the images of a conversation, failing with every violation
+pub fn validate_images(messages: &[Message]) -> Result<()> {
+    let violations = check_images(messages);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(AnthropicToolError::InvalidImages(violations))
+    }
+}
+
+/// Check a single image against a maximum side length
+///
+/// Only base64 sources are inspected; URL and file sources pass.
+pub fn check_image(source: &ImageSource, max_dimension: u32) -> Vec<ImageIssue> {
+    let Some(encoded) = source.data.as_deref() else {
+        return Vec::new();
+    };
+    let declared = source.media_type.as_deref().unwrap_or_default();
+    let mut issues = Vec::new();
+    let media_type = MediaType::from_str(declared).ok();
+    if media_type.is_none() {
+        issues.push(ImageIssue::UnsupportedFormat {
+            media_type: declared.to_string(),
+        });
+    }
+    if encoded.len() > MAX_IMAGE_BYTES {
+        issues.push(ImageIssue::TooLarge {
+            bytes: encoded.len(),
+            limit: MAX_IMAGE_BYTES,
+        });
+    }
+
+    let data = match BASE64_STANDARD.decode(encoded) {
+        Ok(data) => data,
+        Err(e) => {
+            issues.push(ImageIssue::InvalidData {
+                message: format!("not valid base64 ({})", e),
+            });
+            return issues;
+        }
+    };
+    match MediaType::detect(&data) {
+        Some(actual) if media_type.as_ref().is_some_and(|m| *m != actual) => {
+            issues.push(ImageIssue::FormatMismatch {
+                declared: declared.to_string(),
+                actual,
+            });
+        }
+        Some(_) => {}
+        None if media_type.is_some() => issues.push(ImageIssue::InvalidData {
+            message: format!("not a {} image", declared),
+        }),
+        None => {}
+    }
+    if let Some((width, height)) = image_dimensions(&data)
+        && width.max(height) > max_dimension
+    {
+        issues.push(ImageIssue::DimensionsTooLarge {
+            width,
+            height,
+            limit: max_dimension,
+        });
+    }
+    issues
+}
+
+/// Read the width and height from the header of PNG, JPEG, GIF, or WebP data
+///
+/// Nothing is decoded, so this is cheap even for large images.
+pub fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| Some(u16::from_be_bytes([*data.get(i)?, *data.get(i + 1)?]) as u32);
+    let le16 = |i: usize| Some(u16::from_le_bytes([*data.get(i)?, *data.get(i + 1)?]) as u32);
+    let le24 = |i: usize| {
+        Some(u32::from_le_bytes([
+            *data.get(i)?,
+            *data.get(i + 1)?,
+            *data.get(i + 2)?,
+            0,
+        ]))
+    };
+    match MediaType::detect(data)? {
+        MediaType::Png => {
+            let width = u32::from_be_bytes(data.get(16..20)?.try_into().ok()?);
+            let height = u32::from_be_bytes(data.get(20..24)?.try_into().ok()?);
+            Some((width, height))
+        }
+        MediaType::Gif => Some((le16(6)?, le16(8)?)),
+        MediaType::Webp => match data.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3FFF, le16(28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        },
+        MediaType::Jpeg => {
+            let mut i = 2;
+            while i + 3 < data.len() {
+                if data[i] != 0xFF {
+                    return None;
+                }
+                let marker = data[i + 1];
+                match marker {
+                    0xFF => i += 1,
+                    0x01 | 0xD0..=0xD9 => i += 2,
+                    0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                        return Some((be16(i + 7)?, be16(i + 5)?));
+                    }
+                    _ => i += 2 + be16(i + 2)? as usize,
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Collect the image sources of blocks (and nested tool results) with paths
+fn collect_images<'a>(
+    blocks: &'a [ContentBlock],
+    path: &str,
+    images: &mut Vec<(String, &'a ImageSource)>,
+) {
+    for (i, block) in blocks.iter().enumerate() {
+        let location = format!("{}[{}]", path, i);
+        match block {
+            ContentBlock::Image { source, .. } => images.push((location, source)),
+            ContentBlock::ToolResult {
+                content: Some(content),
+                ..
+            } => collect_images(content, &format!("{}.content", location), images),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::request::role::Role;
+
+    /// PNG header (signature and IHDR) of the given size
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_image_dimensions() {
+        assert_eq!(image_dimensions(&png_header(640, 480)), Some((640, 480)));
+        assert_eq!(
+            image_dimensions(b"GIF89a\x20\x03\x58\x02"),
+            Some((800, 600))
+        );
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, // APP0
+            0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0xE0, 0x02, 0x80, // SOF0: 480x640
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((640, 480)));
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+        webp.extend_from_slice(&[0x7F, 0x02, 0x00, 0xDF, 0x01, 0x00]);
+        assert_eq!(image_dimensions(&webp), Some((640, 480)));
+        assert_eq!(image_dimensions(b"%PDF-1.7"), None);
+    }
+
+    #[test]
+    fn test_check_images() {
+        let messages = vec![
+            Message::new(
+                Role::User,
+                vec![
+                    ContentBlock::text("Compare these"),
+                    ContentBlock::image_from_bytes(MediaType::Png, png_header(640, 480)),
+                    ContentBlock::image_from_bytes(MediaType::Png, png_header(9000, 100)),
+                ],
+            ),
+            Message::new(
+                Role::User,
+                vec![ContentBlock::tool_result_image(
+                    "toolu_1",
+                    ImageSource {
+                        type_name: "base64".to_string(),
+                        media_type: Some("image/bmp".to_string()),
+                        data: Some("Qk0=".to_string()),
+                        url: None,
+                        file_id: None,
+                    },
+                )],
+            ),
+        ];
+        let violations = check_images(&messages);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].to_string(),
+            "messages[0].content[2]: 9000x100 pixels, above the limit of 8000 pixels per side; resize it"
+        );
+        assert_eq!(violations[1].location, "messages[1].content[0].content[0]");
+        assert!(matches!(
+            violations[1].issue,
+            ImageIssue::UnsupportedFormat { .. }
+        ));
+
+        let err = validate_images(&messages).unwrap_err();
+        assert_eq!(err.error_type(), "invalid_images");
+        assert!(validate_images(&messages[..1]).is_err());
+        assert!(validate_images(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_many_images_limits() {
+        let blocks = (0..21)
+            .map(|_| ContentBlock::image_from_bytes(MediaType::Png, png_header(2500, 100)))
+            .collect();
+        let violations = check_images(&[Message::new(Role::User, blocks)]);
+        assert_eq!(violations.len(), 21);
+        assert!(matches!(
+            violations[0].issue,
+            ImageIssue::DimensionsTooLarge { limit: 2000, .. }
+        ));
+
+        let blocks = (0..101)
+            .map(|_| ContentBlock::image_from_url("https://example.com/a.png"))
+            .collect();
+        let violations = check_images(&[Message::new(Role::User, blocks)]);
+        assert_eq!(
+            violations,
+            vec![ImageViolation {
+                location: "messages".to_string(),
+                issue: ImageIssue::TooManyImages {
+                    count: 101,
+                    limit: 100
+                },
+            }]
+        );
+    }
+}
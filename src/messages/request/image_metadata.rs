@@ -0,0 +1,399 @@
+//! Removal of EXIF and other metadata from images.
+//!
+//! Photos often carry EXIF metadata such as GPS coordinates, camera serial
+//! numbers, or capture times. [`strip_metadata`] removes it without decoding
+//! the image, so the pixels are sent unchanged:
+//!
+//! - JPEG: `APP1` (EXIF, XMP), `APP13` (IPTC), and comment segments
+//! - WebP: `EXIF` and `XMP ` chunks (the `VP8X` flags are updated)
+//! - PNG: `eXIf` and text chunks
+//!
+//! Color profiles are kept, and so is the EXIF `Orientation` tag: an EXIF
+//! block holding only that tag replaces the original, so rotated photos are
+//! still displayed upright. Enable
+//! [`Messages::strip_image_metadata`](super::Messages::strip_image_metadata)
+//! to strip every inline image of a request before it is sent.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::image_metadata::strip_metadata;
+//!
+//! let jpeg = [
+//!     &[0xFF, 0xD8][..], // SOI
+//!     &[0xFF, 0xE1, 0x00, 0x08, b'E', b'x', b'i', b'f', 0, 0], // APP1 (EXIF)
+//!     &[0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9], // SOS ... EOI
+//! ]
+//! .concat();
+//! assert_eq!(strip_metadata(&jpeg), [0xFF, 0xD8, 0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9]);
+//! ```
+
+use super::content::{ContentBlock, MediaType};
+use super::message::Message;
+use base64::prelude::*;
+
+/// Remove metadata from JPEG, WebP, or PNG data
+///
+/// Other formats and data that cannot be parsed are returned unchanged.
+pub fn strip_metadata(data: &[u8]) -> Vec<u8> {
+    let stripped = match MediaType::detect(data) {
+        Some(MediaType::Jpeg) => strip_jpeg(data),
+        Some(MediaType::Webp) => strip_webp(data),
+        Some(MediaType::Png) => strip_png(data),
+        _ => None,
+    };
+    stripped.unwrap_or_else(|| data.to_vec())
+}
+
+/// Strip the metadata of every base64 image in a conversation
+///
+/// Images in tool results are included; URL and file images are left alone.
+pub fn strip_messages(messages: &mut [Message]) {
+    for message in messages {
+        strip_blocks(&mut message.content);
+    }
+}
+
+fn strip_blocks(blocks: &mut [ContentBlock]) {
+    for block in blocks {
+        match block {
+            ContentBlock::Image { source, .. } => {
+                if let Some(data) = &mut source.data
+                    && let Ok(bytes) = BASE64_STANDARD.decode(data.as_bytes())
+                {
+                    *data = BASE64_STANDARD.encode(strip_metadata(&bytes));
+                }
+            }
+            ContentBlock::ToolResult {
+                content: Some(content),
+                ..
+            } => strip_blocks(content),
+            _ => {}
+        }
+    }
+}
+
+/// Drop APP1, APP13, and COM segments before the image data
+///
+/// An EXIF segment with a non-default orientation is replaced by one holding
+/// only that orientation.
+fn strip_jpeg(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = data.get(..2)?.to_vec();
+    let mut i = 2;
+    loop {
+        if *data.get(i)? != 0xFF {
+            return None;
+        }
+        let marker = *data.get(i + 1)?;
+        match marker {
+            // Start of scan: the rest is image data
+            0xDA | 0xD9 => {
+                out.extend_from_slice(&data[i..]);
+                return Some(out);
+            }
+            0xFF => i += 1,
+            0x01 | 0xD0..=0xD7 => {
+                out.extend_from_slice(&data[i..i + 2]);
+                i += 2;
+            }
+            _ => {
+                let len = u16::from_be_bytes([*data.get(i + 2)?, *data.get(i + 3)?]) as usize;
+                let segment = data.get(i..i + 2 + len)?;
+                if !matches!(marker, 0xE1 | 0xED | 0xFE) {
+                    out.extend_from_slice(segment);
+                } else if marker == 0xE1
+                    && let Some(tiff) = segment.get(4..).and_then(|p| p.strip_prefix(EXIF_HEADER))
+                    && let Some(orientation) = exif_orientation(tiff)
+                {
+                    let exif = orientation_exif(orientation);
+                    let len = u16::try_from(2 + EXIF_HEADER.len() + exif.len()).ok()?;
+                    out.extend_from_slice(&[0xFF, 0xE1]);
+                    out.extend_from_slice(&len.to_be_bytes());
+                    out.extend_from_slice(EXIF_HEADER);
+                    out.extend_from_slice(&exif);
+                }
+                i += 2 + len;
+            }
+        }
+    }
+}
+
+/// Drop EXIF and XMP chunks, clearing their flags in the VP8X header
+///
+/// An EXIF chunk with a non-default orientation is replaced by one holding
+/// only that orientation.
+fn strip_webp(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = data.get(..12)?.to_vec();
+    let mut flags = None;
+    let mut kept_exif = false;
+    let mut i = 12;
+    while i < data.len() {
+        let fourcc = data.get(i..i + 4)?;
+        let size = u32::from_le_bytes(data.get(i + 4..i + 8)?.try_into().ok()?) as usize;
+        let end = i + 8 + size + size % 2;
+        let chunk = data.get(i..end.min(data.len()))?;
+        match fourcc {
+            b"EXIF" => {
+                let payload = chunk.get(8..8 + size)?;
+                let tiff = payload.strip_prefix(EXIF_HEADER).unwrap_or(payload);
+                if let Some(orientation) = exif_orientation(tiff) {
+                    let exif = orientation_exif(orientation);
+                    out.extend_from_slice(b"EXIF");
+                    out.extend_from_slice(&(exif.len() as u32).to_le_bytes());
+                    out.extend_from_slice(&exif);
+                    kept_exif = true;
+                }
+            }
+            b"XMP " => {}
+            b"VP8X" => {
+                let start = out.len();
+                out.extend_from_slice(chunk);
+                // Flags byte: 0x08 EXIF, 0x04 XMP
+                *out.get_mut(start + 8)? &= !0x0C;
+                flags = Some(start + 8);
+            }
+            _ => out.extend_from_slice(chunk),
+        }
+        i = end;
+    }
+    if kept_exif && let Some(flags) = flags {
+        out[flags] |= 0x08;
+    }
+    let riff_size = u32::try_from(out.len() - 8).ok()?;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(out)
+}
+
+/// Prefix of the EXIF data in a JPEG `APP1` segment (optional in WebP)
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+/// Read a non-default `Orientation` tag (2-8) from the first IFD of TIFF data
+fn exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let big_endian = match tiff.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let read_u16 = |at: usize| {
+        let bytes = tiff.get(at..at + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let read_u32 = |at: usize| {
+        let bytes = tiff.get(at..at + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+    let ifd = read_u32(4)? as usize;
+    (0..read_u16(ifd)? as usize)
+        .map(|n| ifd + 2 + 12 * n)
+        // Tag 0x0112 (Orientation), type SHORT
+        .find(|&entry| read_u16(entry) == Some(0x0112) && read_u16(entry + 2) == Some(3))
+        .and_then(|entry| read_u16(entry + 8))
+        .filter(|orientation| (2..=8).contains(orientation))
+}
+
+/// Build little-endian TIFF data holding only an `Orientation` tag
+fn orientation_exif(orientation: u16) -> Vec<u8> {
+    let mut tiff = b"II*\0".to_vec();
+    tiff.extend_from_slice(&8u32.to_le_bytes()); // offset of the first IFD
+    tiff.extend_from_slice(&1u16.to_le_bytes()); // one entry
+    tiff.extend_from_slice(&0x0112u16.to_le_bytes());
+    tiff.extend_from_slice(&3u16.to_le_bytes()); // SHORT
+    tiff.extend_from_slice(&1u32.to_le_bytes()); // one value
+    tiff.extend_from_slice(&orientation.to_le_bytes());
+    tiff.extend_from_slice(&[0, 0]);
+    tiff.extend_from_slice(&0u32.to_le_bytes()); // no next IFD
+    tiff
+}
+
+/// Drop eXIf and text chunks
+fn strip_png(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = data.get(..8)?.to_vec();
+    let mut i = 8;
+    while i < data.len() {
+        let len = u32::from_be_bytes(data.get(i..i + 4)?.try_into().ok()?) as usize;
+        let chunk = data.get(i..i + 12 + len)?;
+        if !matches!(&chunk[4..8], b"eXIf" | b"tEXt" | b"iTXt" | b"zTXt") {
+            out.extend_from_slice(chunk);
+        }
+        i += 12 + len;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::request::role::Role;
+
+    fn jpeg_with_exif() -> Vec<u8> {
+        [
+            &[0xFF, 0xD8][..],
+            &[0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46], // APP0 (JFIF)
+            &[0xFF, 0xE1, 0x00, 0x08, b'E', b'x', b'i', b'f', 0, 0], // APP1 (EXIF)
+            &[0xFF, 0xE2, 0x00, 0x04, 0x49, 0x43], // APP2 (ICC profile)
+            &[0xFF, 0xFE, 0x00, 0x05, b'c', b'a', b'm'], // COM
+            &[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9],
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_strip_jpeg() {
+        let stripped = strip_metadata(&jpeg_with_exif());
+        let expected = [
+            &[0xFF, 0xD8][..],
+            &[0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46],
+            &[0xFF, 0xE2, 0x00, 0x04, 0x49, 0x43],
+            &[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9],
+        ]
+        .concat();
+        assert_eq!(stripped, expected);
+
+        // Truncated data is left alone
+        let truncated = &jpeg_with_exif()[..9];
+        assert_eq!(strip_metadata(truncated), truncated);
+    }
+
+    /// Big-endian TIFF data with GPS and `Orientation` tags in its first IFD
+    fn tiff_with_orientation(orientation: u16) -> Vec<u8> {
+        let mut tiff = b"MM\0*\0\0\0\x08\0\x02".to_vec();
+        tiff.extend_from_slice(&[0x01, 0x12, 0, 3, 0, 0, 0, 1]);
+        tiff.extend_from_slice(&orientation.to_be_bytes());
+        tiff.extend_from_slice(&[0, 0]);
+        tiff.extend_from_slice(&[0x88, 0x25, 0, 4, 0, 0, 0, 1, 0, 0, 0, 0x26]); // GPS IFD
+        tiff.extend_from_slice(&[0, 0, 0, 0]);
+        tiff.extend_from_slice(b"GPS data");
+        tiff
+    }
+
+    #[test]
+    fn test_strip_keeps_orientation() {
+        let tiff = tiff_with_orientation(6);
+        let app1_len = (2 + 6 + tiff.len()) as u16;
+        let jpeg = [
+            &[0xFF, 0xD8, 0xFF, 0xE1][..],
+            &app1_len.to_be_bytes(),
+            b"Exif\0\0",
+            &tiff,
+            &[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9],
+        ]
+        .concat();
+        let stripped = strip_metadata(&jpeg);
+        assert!(!stripped.windows(3).any(|w| w == b"GPS"));
+        let app1 = &stripped[2..];
+        assert_eq!(&app1[..2], &[0xFF, 0xE1]);
+        let len = u16::from_be_bytes([app1[2], app1[3]]) as usize;
+        let tiff = app1[4..2 + len].strip_prefix(EXIF_HEADER).unwrap();
+        assert_eq!(exif_orientation(tiff), Some(6));
+        assert_eq!(
+            &app1[2 + len..],
+            &[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]
+        );
+
+        // The default orientation needs no EXIF at all
+        let upright = [
+            &[0xFF, 0xD8, 0xFF, 0xE1][..],
+            &app1_len.to_be_bytes(),
+            b"Exif\0\0",
+            &tiff_with_orientation(1),
+            &[0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9],
+        ]
+        .concat();
+        assert_eq!(
+            strip_metadata(&upright),
+            [0xFF, 0xD8, 0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9]
+        );
+
+        let tiff = tiff_with_orientation(8);
+        let mut webp = b"RIFF\0\0\0\0WEBP".to_vec();
+        webp.extend_from_slice(b"VP8X\x0a\0\0\0\x0c\0\0\0\x7f\x02\0\xdf\x01\0");
+        webp.extend_from_slice(b"VP8 \x02\0\0\0\x01\x02");
+        webp.extend_from_slice(b"EXIF");
+        webp.extend_from_slice(&(tiff.len() as u32).to_le_bytes());
+        webp.extend_from_slice(&tiff);
+        let size = (webp.len() - 8) as u32;
+        webp[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let stripped = strip_metadata(&webp);
+        assert!(!stripped.windows(3).any(|w| w == b"GPS"));
+        assert_eq!(stripped[20], 0x08);
+        let exif = &stripped[12 + 18 + 10..];
+        assert_eq!(&exif[..4], b"EXIF");
+        assert_eq!(exif_orientation(&exif[8..]), Some(8));
+        assert_eq!(
+            &stripped[4..8],
+            &((stripped.len() - 8) as u32).to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn test_strip_webp() {
+        let mut webp = b"RIFF\0\0\0\0WEBP".to_vec();
+        webp.extend_from_slice(b"VP8X\x0a\0\0\0\x0c\0\0\0\x7f\x02\0\xdf\x01\0");
+        webp.extend_from_slice(b"VP8 \x02\0\0\0\x01\x02");
+        webp.extend_from_slice(b"EXIF\x03\0\0\0GPS\0");
+        webp.extend_from_slice(b"XMP \x02\0\0\0<x");
+        let size = (webp.len() - 8) as u32;
+        webp[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let stripped = strip_metadata(&webp);
+        assert_eq!(stripped.len(), 12 + 18 + 10);
+        assert_eq!(&stripped[4..8], &32u32.to_le_bytes());
+        assert_eq!(stripped[20], 0x00);
+        assert!(!stripped.windows(4).any(|w| w == b"EXIF" || w == b"XMP "));
+    }
+
+    #[test]
+    fn test_strip_png() {
+        let chunk = |kind: &[u8], data: &[u8]| {
+            let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+            chunk.extend_from_slice(kind);
+            chunk.extend_from_slice(data);
+            chunk.extend_from_slice(&[0, 0, 0, 0]);
+            chunk
+        };
+        let ihdr = chunk(b"IHDR", &[0; 13]);
+        let iend = chunk(b"IEND", &[]);
+        let png = [
+            &b"\x89PNG\r\n\x1a\n"[..],
+            &ihdr,
+            &chunk(b"eXIf", b"MM\0*"),
+            &chunk(b"tEXt", b"Author\0me"),
+            &iend,
+        ]
+        .concat();
+        let expected = [&b"\x89PNG\r\n\x1a\n"[..], &ihdr, &iend].concat();
+        assert_eq!(strip_metadata(&png), expected);
+        assert_eq!(strip_metadata(b"GIF89a"), b"GIF89a");
+    }
+
+    #[test]
+    fn test_strip_messages() {
+        let mut messages = vec![Message::new(
+            Role::User,
+            vec![ContentBlock::tool_result_with_blocks(
+                "toolu_1",
+                vec![ContentBlock::image_from_bytes(
+                    MediaType::Jpeg,
+                    jpeg_with_exif(),
+                )],
+            )],
+        )];
+        strip_messages(&mut messages);
+        let json = serde_json::to_value(&messages[0]).unwrap();
+        let data = json["content"][0]["content"][0]["source"]["data"]
+            .as_str()
+            .unwrap();
+        let bytes = BASE64_STANDARD.decode(data).unwrap();
+        assert_eq!(bytes, strip_metadata(&jpeg_with_exif()));
+        assert!(!bytes.windows(4).any(|w| w == b"Exif"));
+    }
+}
@@ -4,45 +4,193 @@
 //! can use during conversations:
 //!
 //! - [`McpServer`] - MCP server connection configuration
+//! - [`McpServerType`] - Kind of server connection
 //! - [`ToolConfiguration`] - Tool access configuration for servers
+//! - [`McpToolset`] - Entry of the `tools` array exposing a server's tools
 //!
 //! # Note
 //!
 //! MCP support is currently in beta. See the Anthropic documentation
 //! for the latest information on MCP capabilities and configuration.
+//! [`Messages::add_mcp_server`](crate::messages::request::Messages::add_mcp_server)
+//! enables the required beta header.
 //!
 //! # Example
 //!
 //! ```rust
-//! use anthropic_tools::messages::request::mcp::{McpServer, ToolConfiguration};
-//!
-//! let server = McpServer {
-//!     name: "my-server".to_string(),
-//!     type_name: "url".to_string(),
-//!     url: "https://mcp.example.com".to_string(),
-//!     authorization_token: Some("token".to_string()),
-//!     tool_configuration: Some(ToolConfiguration {
-//!         allowed_tools: vec!["tool1".to_string(), "tool2".to_string()],
-//!         enabled: true,
-//!     }),
-//! };
+//! use anthropic_tools::messages::request::mcp::McpServer;
+//! use anthropic_tools::messages::request::Messages;
+//!
+//! let mut server = McpServer::url("my-server", "https://mcp.example.com/sse");
+//! server
+//!     .authorization_token("token")
+//!     .allow_tools(vec!["tool1", "tool2"]);
+//! assert!(server.validate().is_ok());
+//!
+//! let mut client = Messages::new();
+//! client.add_mcp_server(server);
 //! ```
 
+use crate::common::errors::{AnthropicToolError, Result};
 use serde::{Deserialize, Serialize};
 
+/// Type of an MCP toolset in the `tools` array
+pub const MCP_TOOLSET_TYPE: &str = "mcp_toolset";
+
 /// Tool configuration for MCP servers
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ToolConfiguration {
+    /// Tools the model may call; empty allows all tools
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allowed_tools: Vec<String>,
     pub enabled: bool,
 }
 
+/// Kind of MCP server connection
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum McpServerType {
+    /// Server reached over HTTP (Streamable HTTP or SSE)
+    #[default]
+    Url,
+}
+
+/// MCP server the API connects to
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct McpServer {
     pub name: String,
     #[serde(rename = "type")]
-    pub type_name: String,
+    pub type_name: McpServerType,
     pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub authorization_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_configuration: Option<ToolConfiguration>,
 }
+
+impl McpServer {
+    /// Create a URL server with all its tools enabled
+    pub fn url<S: AsRef<str>>(name: S, url: S) -> Self {
+        McpServer {
+            name: name.as_ref().to_string(),
+            type_name: McpServerType::Url,
+            url: url.as_ref().to_string(),
+            authorization_token: None,
+            tool_configuration: None,
+        }
+    }
+
+    /// Set the OAuth token sent to the server
+    pub fn authorization_token<S: AsRef<str>>(&mut self, token: S) -> &mut Self {
+        self.authorization_token = Some(token.as_ref().to_string());
+        self
+    }
+
+    /// Only allow the listed tools of the server
+    pub fn allow_tools<S: AsRef<str>>(&mut self, tools: Vec<S>) -> &mut Self {
+        let allowed_tools = tools.iter().map(|t| t.as_ref().to_string()).collect();
+        match &mut self.tool_configuration {
+            Some(config) => config.allowed_tools = allowed_tools,
+            None => {
+                self.tool_configuration = Some(ToolConfiguration {
+                    allowed_tools,
+                    enabled: true,
+                })
+            }
+        }
+        self
+    }
+
+    /// Enable or disable the server's tools
+    pub fn enabled(&mut self, enabled: bool) -> &mut Self {
+        self.tool_configuration
+            .get_or_insert_with(|| ToolConfiguration {
+                allowed_tools: Vec::new(),
+                enabled,
+            })
+            .enabled = enabled;
+        self
+    }
+
+    /// Check the configuration against the API's rules
+    ///
+    /// The name must not be empty, and the URL must use `https`.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: &str| {
+            AnthropicToolError::InvalidParameter(format!("mcp server `{}`: {}", self.name, message))
+        };
+        if self.name.trim().is_empty() {
+            return Err(invalid("name must not be empty"));
+        }
+        if !self.url.starts_with("https://") {
+            return Err(invalid("url must start with https://"));
+        }
+        Ok(())
+    }
+}
+
+/// Tools of an MCP server, as an entry of the `tools` array
+///
+/// Options such as per-tool configurations are kept in `extra` and sent
+/// unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct McpToolset {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// Name of the server in `mcp_servers`
+    pub mcp_server_name: String,
+
+    #[serde(flatten, default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl McpToolset {
+    /// Expose all tools of the named server
+    pub fn new<S: AsRef<str>>(mcp_server_name: S) -> Self {
+        McpToolset {
+            type_name: MCP_TOOLSET_TYPE.to_string(),
+            mcp_server_name: mcp_server_name.as_ref().to_string(),
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_builder() {
+        let mut server = McpServer::url("docs", "https://mcp.example.com");
+        assert_eq!(
+            serde_json::to_value(&server).unwrap(),
+            serde_json::json!({"name": "docs", "type": "url", "url": "https://mcp.example.com"})
+        );
+        assert!(server.validate().is_ok());
+
+        server.enabled(false);
+        assert_eq!(
+            serde_json::to_value(&server).unwrap()["tool_configuration"],
+            serde_json::json!({"enabled": false})
+        );
+        server.allow_tools(vec!["search"]);
+        let config = server.tool_configuration.as_ref().unwrap();
+        assert_eq!(config.allowed_tools, vec!["search"]);
+        assert!(!config.enabled);
+
+        assert!(
+            McpServer::url("docs", "http://mcp.example.com")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            McpServer::url(" ", "https://mcp.example.com")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            serde_json::from_str::<McpServer>(r#"{"name":"a","type":"stdio","url":"x"}"#).is_err()
+        );
+    }
+}
@@ -5,6 +5,7 @@
 //! - [`Message`] - A message in the conversation with role and content
 //! - [`SystemPrompt`] - System prompt configuration
 //! - [`SystemBlock`] - Structured system prompt blocks
+//! - [`SystemPromptBuilder`] - Builder for system prompts made of several blocks
 //!
 //! # Creating Messages
 //!
@@ -30,6 +31,23 @@
 //! );
 //! ```
 //!
+//! # Annotations
+//!
+//! Messages can carry local-only metadata (timestamps, author ids, moderation
+//! verdicts). Annotations survive serializing a [`Message`] on its own, e.g.
+//! for a conversation store, but are stripped from the request body sent to
+//! the API.
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::message::Message;
+//!
+//! let mut msg = Message::user("Hello!");
+//! msg.annotate("author_id", "u_42")
+//!     .annotate("created_at", 1_760_000_000);
+//!
+//! assert_eq!(msg.annotation("author_id").unwrap(), "u_42");
+//! ```
+//!
 //! # System Prompts
 //!
 //! ```rust
@@ -40,23 +58,52 @@
 //!
 //! // With prompt caching
 //! let cached = SystemPrompt::with_cache("Long system prompt...");
+//!
+//! // Cached instructions and reference document, then a dynamic block
+//! let composed = SystemPrompt::builder()
+//!     .cached_text("You are a support agent for Acme.")
+//!     .cached_text("<manual>...</manual>")
+//!     .text("Today is 2025-06-01.")
+//!     .build();
 //! ```
 
-use crate::messages::request::content::{CacheControl, ContentBlock, MediaType};
+use crate::common::errors::Result;
+use crate::messages::request::attachment::Attachment;
+use crate::messages::request::content::{CacheControl, ContentBlock};
 use crate::messages::request::role::Role;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Local-only message annotations, keyed by name
+pub type Annotations = BTreeMap<String, Value>;
 
 /// Message in a conversation
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Message {
     pub role: Role,
     pub content: Vec<ContentBlock>,
+
+    /// Local-only metadata, never sent to the API
+    #[serde(default, skip_serializing_if = "Annotations::is_empty")]
+    pub annotations: Annotations,
+}
+
+/// Message as sent to the API (without annotations)
+#[derive(Serialize)]
+struct ApiMessage<'a> {
+    role: &'a Role,
+    content: &'a [ContentBlock],
 }
 
 impl Message {
     /// Create a new message with role and content blocks
     pub fn new(role: Role, content: Vec<ContentBlock>) -> Self {
-        Message { role, content }
+        Message {
+            role,
+            content,
+            annotations: Annotations::new(),
+        }
     }
 
     /// Create a user message with text content
@@ -64,6 +111,7 @@ impl Message {
         Message {
             role: Role::User,
             content: vec![ContentBlock::text(text)],
+            annotations: Annotations::new(),
         }
     }
 
@@ -72,18 +120,21 @@ impl Message {
         Message {
             role: Role::Assistant,
             content: vec![ContentBlock::text(text)],
+            annotations: Annotations::new(),
         }
     }
 
     /// Create a user message with an image from file path
-    pub fn user_with_image<T: AsRef<str>>(text: T, media_type: MediaType, image_path: T) -> Self {
-        Message {
+    #[cfg(feature = "image")]
+    pub fn user_with_image<T: AsRef<str>>(text: T, image_path: T) -> Result<Self> {
+        Ok(Message {
             role: Role::User,
             content: vec![
-                ContentBlock::image_from_path(media_type, image_path),
+                ContentBlock::image_from_path(image_path)?,
                 ContentBlock::text(text),
             ],
-        }
+            annotations: Annotations::new(),
+        })
     }
 
     /// Create a user message with an image from URL
@@ -94,14 +145,49 @@ impl Message {
                 ContentBlock::image_from_url(image_url),
                 ContentBlock::text(text),
             ],
+            annotations: Annotations::new(),
         }
     }
 
+    /// Create a user message with an attachment (image, PDF, or text file)
+    pub fn user_with_attachment<T: AsRef<str>>(text: T, attachment: Attachment) -> Result<Self> {
+        Ok(Message {
+            role: Role::User,
+            content: vec![attachment.into_content_block()?, ContentBlock::text(text)],
+            annotations: Annotations::new(),
+        })
+    }
+
     /// Create a user message with tool result
     pub fn tool_result<S: AsRef<str>>(tool_use_id: S, result_text: S) -> Self {
         Message {
             role: Role::User,
             content: vec![ContentBlock::tool_result_text(tool_use_id, result_text)],
+            annotations: Annotations::new(),
+        }
+    }
+
+    /// Create a user message with a tool result serialized as JSON text
+    pub fn tool_result_json<S: AsRef<str>, T: Serialize + ?Sized>(
+        tool_use_id: S,
+        value: &T,
+    ) -> Result<Self> {
+        Ok(Message {
+            role: Role::User,
+            content: vec![ContentBlock::tool_result_json(tool_use_id, value)?],
+            annotations: Annotations::new(),
+        })
+    }
+
+    /// Create a user message with a tool result made of content blocks (text, images)
+    pub fn tool_result_with_blocks<S: AsRef<str>>(
+        tool_use_id: S,
+        content: Vec<ContentBlock>,
+    ) -> Self {
+        Message {
+            role: Role::User,
+            content: vec![ContentBlock::tool_result_with_blocks(tool_use_id, content)],
+            annotations: Annotations::new(),
         }
     }
 
@@ -110,6 +196,7 @@ impl Message {
         Message {
             role: Role::User,
             content: vec![ContentBlock::tool_result_error(tool_use_id, error_message)],
+            annotations: Annotations::new(),
         }
     }
 
@@ -126,14 +213,10 @@ impl Message {
     }
 
     /// Add image from path to the message
-    pub fn add_image_from_path<T: AsRef<str>>(
-        &mut self,
-        media_type: MediaType,
-        path: T,
-    ) -> &mut Self {
-        self.content
-            .push(ContentBlock::image_from_path(media_type, path));
-        self
+    #[cfg(feature = "image")]
+    pub fn add_image_from_path<T: AsRef<str>>(&mut self, path: T) -> Result<&mut Self> {
+        self.content.push(ContentBlock::image_from_path(path)?);
+        Ok(self)
     }
 
     /// Add image from URL to the message
@@ -141,6 +224,55 @@ impl Message {
         self.content.push(ContentBlock::image_from_url(url));
         self
     }
+
+    /// Add an attachment to the message
+    pub fn add_attachment(&mut self, attachment: Attachment) -> Result<&mut Self> {
+        self.content.push(attachment.into_content_block()?);
+        Ok(self)
+    }
+
+    /// Mark the last cacheable block as a prompt cache breakpoint (`ephemeral`)
+    ///
+    /// The conversation up to and including this message is then cached.
+    pub fn with_cache(&mut self) -> &mut Self {
+        self.cache_control(CacheControl::ephemeral())
+    }
+
+    /// Set the cache control of the last block that can be cached
+    pub fn cache_control(&mut self, cache_control: CacheControl) -> &mut Self {
+        if let Some(slot) = self
+            .content
+            .iter_mut()
+            .rev()
+            .find_map(ContentBlock::cache_control_mut)
+        {
+            *slot = Some(cache_control);
+        }
+        self
+    }
+
+    /// Attach a local-only annotation (replaces any existing value for the key)
+    pub fn annotate<K: AsRef<str>, V: Into<Value>>(&mut self, key: K, value: V) -> &mut Self {
+        self.annotations
+            .insert(key.as_ref().to_string(), value.into());
+        self
+    }
+
+    /// Get an annotation by key
+    pub fn annotation(&self, key: &str) -> Option<&Value> {
+        self.annotations.get(key)
+    }
+}
+
+/// Serialize messages for the API request body, dropping annotations
+pub(crate) fn serialize_for_api<S: Serializer>(
+    messages: &[Message],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(messages.iter().map(|message| ApiMessage {
+        role: &message.role,
+        content: &message.content,
+    }))
 }
 
 /// System prompt for the conversation
@@ -182,6 +314,59 @@ impl SystemPrompt {
     pub fn blocks(blocks: Vec<SystemBlock>) -> Self {
         SystemPrompt::Blocks(blocks)
     }
+
+    /// Start a system prompt made of several blocks
+    pub fn builder() -> SystemPromptBuilder {
+        SystemPromptBuilder::new()
+    }
+}
+
+/// Builder for a system prompt made of several text blocks
+///
+/// Blocks are sent in the order they are added. A cached block caches the
+/// prompt up to and including it, so put stable blocks first and dynamic
+/// ones last.
+#[derive(Debug, Clone, Default)]
+pub struct SystemPromptBuilder {
+    blocks: Vec<SystemBlock>,
+}
+
+impl SystemPromptBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a text block without caching
+    pub fn text<T: AsRef<str>>(&mut self, text: T) -> &mut Self {
+        self.block(SystemBlock::text(text))
+    }
+
+    /// Add a text block that is a cache breakpoint (`ephemeral`)
+    pub fn cached_text<T: AsRef<str>>(&mut self, text: T) -> &mut Self {
+        self.block(SystemBlock::text_with_cache(text))
+    }
+
+    /// Add a block
+    pub fn block(&mut self, block: SystemBlock) -> &mut Self {
+        self.blocks.push(block);
+        self
+    }
+
+    /// Set the cache control of the last block, e.g. [`CacheControl::ephemeral_1h`]
+    ///
+    /// Blocks cached for 1 hour must come before those cached for 5 minutes.
+    pub fn cache_control(&mut self, cache_control: CacheControl) -> &mut Self {
+        if let Some(block) = self.blocks.last_mut() {
+            block.cache_control = Some(cache_control);
+        }
+        self
+    }
+
+    /// Build the system prompt
+    pub fn build(&self) -> SystemPrompt {
+        SystemPrompt::Blocks(self.blocks.clone())
+    }
 }
 
 impl SystemBlock {
@@ -204,7 +389,6 @@ impl SystemBlock {
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +421,10 @@ mod tests {
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("\"role\":\"user\""));
         assert!(json.contains("\"tool_use_id\":\"tool_123\""));
+
+        let msg = Message::tool_result_json("tool_123", &serde_json::json!({"ok": true})).unwrap();
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["content"][0]["content"][0]["text"], r#"{"ok":true}"#);
     }
 
     #[test]
@@ -254,6 +442,30 @@ mod tests {
         assert!(json.contains("\"type\":\"ephemeral\""));
     }
 
+    #[test]
+    fn test_system_prompt_builder() {
+        let system = SystemPrompt::builder()
+            .text("Instructions")
+            .cache_control(CacheControl::ephemeral_1h())
+            .cached_text("Reference document")
+            .text("Dynamic context")
+            .build();
+        let json = serde_json::to_value(&system).unwrap();
+        let blocks = json.as_array().unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0]["text"], "Instructions");
+        assert_eq!(blocks[0]["cache_control"]["ttl"], "1h");
+        assert_eq!(blocks[1]["cache_control"]["type"], "ephemeral");
+        assert!(blocks[1]["cache_control"].get("ttl").is_none());
+        assert!(blocks[2].get("cache_control").is_none());
+
+        // Nothing to mark yet
+        let system = SystemPromptBuilder::new()
+            .cache_control(CacheControl::ephemeral())
+            .build();
+        assert!(matches!(system, SystemPrompt::Blocks(blocks) if blocks.is_empty()));
+    }
+
     #[test]
     fn test_message_builder() {
         let mut msg = Message::user("Initial text");
@@ -263,6 +475,48 @@ mod tests {
         assert_eq!(msg.content.len(), 3);
     }
 
+    #[test]
+    fn test_user_with_attachment() {
+        let attachment = Attachment::url("https://example.com/doc.pdf");
+        let msg = Message::user_with_attachment("Summarize this", attachment).unwrap();
+        assert_eq!(msg.content.len(), 2);
+        assert!(matches!(msg.content[0], ContentBlock::Document { .. }));
+    }
+
+    #[test]
+    fn test_message_with_cache() {
+        let mut msg = Message::new(
+            Role::User,
+            vec![
+                ContentBlock::text("Question"),
+                ContentBlock::document_from_url("https://example.com/a.pdf"),
+                ContentBlock::RedactedThinking {
+                    data: "EmwK".to_string(),
+                },
+            ],
+        );
+        msg.with_cache();
+        assert!(!msg.content[0].has_cache_control());
+        assert!(msg.content[1].has_cache_control());
+        assert!(!msg.content[2].has_cache_control());
+    }
+
+    #[test]
+    fn test_annotations() {
+        let mut msg = Message::user("Hello!");
+        msg.annotate("author_id", "u_42").annotate("flagged", false);
+
+        // Kept when the message itself is serialized
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains(r#""annotations":{"author_id":"u_42","flagged":false}"#));
+        let restored: Message = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.annotation("author_id").unwrap(), "u_42");
+
+        // Omitted when empty
+        let json = serde_json::to_string(&Message::user("Hi")).unwrap();
+        assert!(!json.contains("annotations"));
+    }
+
     #[tokio::test]
     async fn test_image_source_from_url_async() {
         // Test that async URL fetching works
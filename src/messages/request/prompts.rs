@@ -0,0 +1,294 @@
+//! Versioned system prompts and rollouts.
+//!
+//! A [`PromptRegistry`] holds named system prompts in several versions. Each
+//! conversation selects one version, either the latest registered or, while a
+//! rollout is configured, a weighted choice that is stable per conversation ID.
+//! The selected [`PromptVersion`] is recorded on the client and reported in
+//! every [`UsageReport`], so A/B tests can be tracked end to end.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::Messages;
+//! use anthropic_tools::messages::request::prompts::PromptRegistry;
+//!
+//! let mut prompts = PromptRegistry::new();
+//! prompts
+//!     .register("support", "v1", "You are a support agent.")
+//!     .register("support", "v2", "You are a concise, friendly support agent.")
+//!     .rollout("support", [("v1", 90), ("v2", 10)])
+//!     .unwrap();
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .system_version(&prompts, "support", "conversation-42")
+//!     .unwrap()
+//!     .user("My order is late");
+//!
+//! let version = client.prompt_version().unwrap();
+//! assert_eq!(version.name, "support");
+//! ```
+//!
+//! [`UsageReport`]: crate::common::usage::UsageReport
+
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::messages::request::message::SystemPrompt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Identifier of a selected system prompt version
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PromptVersion {
+    /// Prompt name
+    pub name: String,
+    /// Version label
+    pub version: String,
+}
+
+impl fmt::Display for PromptVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+/// All versions of one named prompt
+#[derive(Debug, Clone, Default)]
+struct PromptEntry {
+    /// Versions in registration order
+    versions: Vec<(String, SystemPrompt)>,
+    /// Rollout weights by version, if a rollout is active
+    rollout: Vec<(String, u32)>,
+}
+
+/// Registry of named, versioned system prompts
+#[derive(Debug, Clone, Default)]
+pub struct PromptRegistry {
+    prompts: BTreeMap<String, PromptEntry>,
+}
+
+impl PromptRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        PromptRegistry::default()
+    }
+
+    /// Register a text prompt version (replaces an existing version with the same label)
+    pub fn register<N: AsRef<str>, V: AsRef<str>, T: AsRef<str>>(
+        &mut self,
+        name: N,
+        version: V,
+        text: T,
+    ) -> &mut Self {
+        self.register_prompt(name, version, SystemPrompt::text(text))
+    }
+
+    /// Register a prompt version with structured blocks (e.g. with cache control)
+    pub fn register_prompt<N: AsRef<str>, V: AsRef<str>>(
+        &mut self,
+        name: N,
+        version: V,
+        prompt: SystemPrompt,
+    ) -> &mut Self {
+        let entry = self.prompts.entry(name.as_ref().to_string()).or_default();
+        let version = version.as_ref().to_string();
+        match entry
+            .versions
+            .iter_mut()
+            .find(|(label, _)| *label == version)
+        {
+            Some((_, existing)) => *existing = prompt,
+            None => entry.versions.push((version, prompt)),
+        }
+        self
+    }
+
+    /// Split conversations between versions by weight
+    ///
+    /// Every version must be registered and at least one weight must be
+    /// non-zero. An empty rollout reverts to the latest version.
+    pub fn rollout<N, I, V>(&mut self, name: N, weights: I) -> Result<&mut Self>
+    where
+        N: AsRef<str>,
+        I: IntoIterator<Item = (V, u32)>,
+        V: AsRef<str>,
+    {
+        let entry = self.entry_mut(name.as_ref())?;
+        let weights: Vec<(String, u32)> = weights
+            .into_iter()
+            .map(|(version, weight)| (version.as_ref().to_string(), weight))
+            .collect();
+
+        for (version, _) in &weights {
+            if !entry.versions.iter().any(|(label, _)| label == version) {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "unknown version '{}' of prompt '{}'",
+                    version,
+                    name.as_ref()
+                )));
+            }
+        }
+        if !weights.is_empty() && weights.iter().all(|(_, weight)| *weight == 0) {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "rollout of prompt '{}' has no non-zero weight",
+                name.as_ref()
+            )));
+        }
+        entry.rollout = weights;
+        Ok(self)
+    }
+
+    /// Get a specific prompt version
+    pub fn get(&self, name: &str, version: &str) -> Option<&SystemPrompt> {
+        self.prompts
+            .get(name)?
+            .versions
+            .iter()
+            .find(|(label, _)| label == version)
+            .map(|(_, prompt)| prompt)
+    }
+
+    /// Get the registered version labels of a prompt, in registration order
+    pub fn versions(&self, name: &str) -> Vec<&str> {
+        self.prompts
+            .get(name)
+            .map(|entry| {
+                entry
+                    .versions
+                    .iter()
+                    .map(|(label, _)| label.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Select the version used by a conversation
+    ///
+    /// With an active rollout, the choice depends only on the conversation ID,
+    /// so every turn of a conversation sees the same version. Otherwise the
+    /// latest registered version is used.
+    pub fn select(
+        &self,
+        name: &str,
+        conversation_id: &str,
+    ) -> Result<(PromptVersion, &SystemPrompt)> {
+        let entry = self.prompts.get(name).ok_or_else(|| unknown_prompt(name))?;
+
+        let version = if entry.rollout.is_empty() {
+            entry.versions.last().map(|(label, _)| label.as_str())
+        } else {
+            let total: u64 = entry
+                .rollout
+                .iter()
+                .map(|(_, weight)| u64::from(*weight))
+                .sum();
+            let mut point = bucket(name, conversation_id) % total;
+            entry.rollout.iter().find_map(|(label, weight)| {
+                if point < u64::from(*weight) {
+                    Some(label.as_str())
+                } else {
+                    point -= u64::from(*weight);
+                    None
+                }
+            })
+        }
+        .ok_or_else(|| unknown_prompt(name))?;
+
+        let prompt = self
+            .get(name, version)
+            .ok_or_else(|| unknown_prompt(name))?;
+        Ok((
+            PromptVersion {
+                name: name.to_string(),
+                version: version.to_string(),
+            },
+            prompt,
+        ))
+    }
+
+    fn entry_mut(&mut self, name: &str) -> Result<&mut PromptEntry> {
+        self.prompts
+            .get_mut(name)
+            .ok_or_else(|| unknown_prompt(name))
+    }
+}
+
+fn unknown_prompt(name: &str) -> AnthropicToolError {
+    AnthropicToolError::InvalidParameter(format!("unknown system prompt '{}'", name))
+}
+
+/// Stable bucket for a conversation, independent of process and platform
+fn bucket(name: &str, conversation_id: &str) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    hasher.update([0]);
+    hasher.update(conversation_id.as_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> PromptRegistry {
+        let mut prompts = PromptRegistry::new();
+        prompts
+            .register("support", "v1", "First")
+            .register("support", "v2", "Second");
+        prompts
+    }
+
+    #[test]
+    fn test_latest_version_by_default() {
+        let prompts = registry();
+        let (version, prompt) = prompts.select("support", "c1").unwrap();
+        assert_eq!(version.to_string(), "support@v2");
+        assert!(matches!(prompt, SystemPrompt::Text(text) if text == "Second"));
+        assert_eq!(prompts.versions("support"), vec!["v1", "v2"]);
+    }
+
+    #[test]
+    fn test_rollout_is_stable_and_weighted() {
+        let mut prompts = registry();
+        prompts
+            .rollout("support", [("v1", 50), ("v2", 50)])
+            .unwrap();
+
+        let first = prompts.select("support", "conversation-7").unwrap().0;
+        for _ in 0..5 {
+            assert_eq!(
+                prompts.select("support", "conversation-7").unwrap().0,
+                first
+            );
+        }
+
+        let v2 = (0..1000)
+            .filter(|i| {
+                prompts
+                    .select("support", &format!("c{}", i))
+                    .unwrap()
+                    .0
+                    .version
+                    == "v2"
+            })
+            .count();
+        assert!((400..600).contains(&v2), "v2 selected {} times", v2);
+
+        prompts.rollout("support", [("v1", 1), ("v2", 0)]).unwrap();
+        assert_eq!(prompts.select("support", "any").unwrap().0.version, "v1");
+    }
+
+    #[test]
+    fn test_rollout_validation() {
+        let mut prompts = registry();
+        assert!(prompts.rollout("support", [("v3", 10)]).is_err());
+        assert!(prompts.rollout("support", [("v1", 0)]).is_err());
+        assert!(prompts.rollout("missing", [("v1", 10)]).is_err());
+        assert!(prompts.select("missing", "c1").is_err());
+    }
+}
@@ -0,0 +1,218 @@
+//! Checked sampling parameter types.
+//!
+//! This module provides newtypes that guarantee sampling parameters are within
+//! the ranges accepted by the API, so invalid values are caught when they are
+//! constructed instead of when the request is sent:
+//!
+//! - [`Temperature`] - Sampling temperature in `0.0..=1.0`
+//! - [`TopP`] - Nucleus sampling probability in `0.0..=1.0`
+//! - [`TopK`] - Top-k sampling limit of at least 1
+//!
+//! Each type offers a strict constructor (`new`, returning an error) and a
+//! lenient one (`clamped`, which clamps into range and logs a warning). The
+//! builder methods on [`Messages`](crate::messages::request::Messages) accept
+//! raw numbers and clamp them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::sampling::{Temperature, TopK};
+//!
+//! let temperature = Temperature::new(0.7).unwrap();
+//! assert_eq!(temperature.value(), 0.7);
+//!
+//! assert!(Temperature::new(1.5).is_err());
+//! assert_eq!(Temperature::clamped(1.5).value(), 1.0);
+//!
+//! assert!(TopK::new(0).is_err());
+//! ```
+
+use crate::common::errors::{AnthropicToolError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Sampling temperature (0.0 to 1.0)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[serde(try_from = "f32", into = "f32")]
+pub struct Temperature(f32);
+
+impl Temperature {
+    /// Lowest accepted temperature
+    pub const MIN: f32 = 0.0;
+    /// Highest accepted temperature
+    pub const MAX: f32 = 1.0;
+
+    /// Create a temperature, rejecting values outside `0.0..=1.0`
+    pub fn new(value: f32) -> Result<Self> {
+        if !(Self::MIN..=Self::MAX).contains(&value) {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "temperature must be between 0.0 and 1.0, got {}",
+                value
+            )));
+        }
+        Ok(Temperature(value))
+    }
+
+    /// Create a temperature, clamping out-of-range values (NaN becomes 1.0)
+    pub fn clamped(value: f32) -> Self {
+        Temperature(clamp_unit("temperature", value))
+    }
+
+    /// Get the raw value
+    pub fn value(self) -> f32 {
+        self.0
+    }
+}
+
+impl TryFrom<f32> for Temperature {
+    type Error = AnthropicToolError;
+
+    fn try_from(value: f32) -> Result<Self> {
+        Temperature::new(value)
+    }
+}
+
+impl From<Temperature> for f32 {
+    fn from(temperature: Temperature) -> Self {
+        temperature.0
+    }
+}
+
+/// Top-p (nucleus) sampling parameter (0.0 to 1.0)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[serde(try_from = "f32", into = "f32")]
+pub struct TopP(f32);
+
+impl TopP {
+    /// Lowest accepted value
+    pub const MIN: f32 = 0.0;
+    /// Highest accepted value
+    pub const MAX: f32 = 1.0;
+
+    /// Create a top_p value, rejecting values outside `0.0..=1.0`
+    pub fn new(value: f32) -> Result<Self> {
+        if !(Self::MIN..=Self::MAX).contains(&value) {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "top_p must be between 0.0 and 1.0, got {}",
+                value
+            )));
+        }
+        Ok(TopP(value))
+    }
+
+    /// Create a top_p value, clamping out-of-range values (NaN becomes 1.0)
+    pub fn clamped(value: f32) -> Self {
+        TopP(clamp_unit("top_p", value))
+    }
+
+    /// Get the raw value
+    pub fn value(self) -> f32 {
+        self.0
+    }
+}
+
+impl TryFrom<f32> for TopP {
+    type Error = AnthropicToolError;
+
+    fn try_from(value: f32) -> Result<Self> {
+        TopP::new(value)
+    }
+}
+
+impl From<TopP> for f32 {
+    fn from(top_p: TopP) -> Self {
+        top_p.0
+    }
+}
+
+/// Top-k sampling parameter (at least 1)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct TopK(u32);
+
+impl TopK {
+    /// Create a top_k value, rejecting zero
+    pub fn new(value: u32) -> Result<Self> {
+        if value == 0 {
+            return Err(AnthropicToolError::InvalidParameter(
+                "top_k must be greater than 0".to_string(),
+            ));
+        }
+        Ok(TopK(value))
+    }
+
+    /// Create a top_k value, raising zero to 1
+    pub fn clamped(value: u32) -> Self {
+        if value == 0 {
+            tracing::warn!("top_k must be greater than 0, using 1");
+        }
+        TopK(value.max(1))
+    }
+
+    /// Get the raw value
+    pub fn value(self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for TopK {
+    type Error = AnthropicToolError;
+
+    fn try_from(value: u32) -> Result<Self> {
+        TopK::new(value)
+    }
+}
+
+impl From<TopK> for u32 {
+    fn from(top_k: TopK) -> Self {
+        top_k.0
+    }
+}
+
+/// Clamp a value into `0.0..=1.0`, warning when it had to be adjusted
+fn clamp_unit(name: &str, value: f32) -> f32 {
+    let clamped = if value.is_nan() {
+        1.0
+    } else {
+        value.clamp(0.0, 1.0)
+    };
+    if clamped != value {
+        tracing::warn!("{} {} is out of range, using {}", name, value, clamped);
+    }
+    clamped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temperature_range() {
+        assert!(Temperature::new(0.0).is_ok());
+        assert!(Temperature::new(1.0).is_ok());
+        assert!(Temperature::new(-0.1).is_err());
+        assert!(Temperature::new(f32::NAN).is_err());
+    }
+
+    #[test]
+    fn test_clamped() {
+        assert_eq!(Temperature::clamped(2.0).value(), 1.0);
+        assert_eq!(Temperature::clamped(f32::NAN).value(), 1.0);
+        assert_eq!(TopP::clamped(-1.0).value(), 0.0);
+        assert_eq!(TopK::clamped(0).value(), 1);
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let temperature = Temperature::new(0.5).unwrap();
+        assert_eq!(serde_json::to_string(&temperature).unwrap(), "0.5");
+        let parsed: TopK = serde_json::from_str("40").unwrap();
+        assert_eq!(parsed.value(), 40);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_out_of_range() {
+        assert!(serde_json::from_str::<Temperature>("1.5").is_err());
+        assert!(serde_json::from_str::<TopP>("-0.5").is_err());
+        assert!(serde_json::from_str::<TopK>("0").is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! Checked stop sequences.
+//!
+//! [`StopSequences`] is a validated list of stop sequences, built with
+//! [`StopSequences::builder`]. The builder rejects empty, whitespace-only,
+//! duplicate, and overlong sequences, offers presets for common delimiters,
+//! and can guard against a subtle bug: a stop sequence that also appears in
+//! the prompt (for example in a few-shot example or an assistant prefill)
+//! tends to be echoed by the model, cutting the response short.
+//!
+//! - [`StopSequencesBuilder::xml_close`] - Closing tag such as `</answer>`
+//! - [`StopSequencesBuilder::markdown_fence`] - Code fence (three backticks)
+//! - [`StopSequencesBuilder::guard`] - Text that must not contain any sequence
+//! - [`StopSequences::escape`] - Break up occurrences of the sequences in a text
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::messages::request::Messages;
+//! use anthropic_tools::messages::request::stop::StopSequences;
+//!
+//! let prompt = "Answer inside <answer> tags.";
+//! let stops = StopSequences::builder()
+//!     .xml_close("answer")
+//!     .guard(prompt)
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut client = Messages::new();
+//! client.user(prompt).stop_sequences(stops);
+//!
+//! // A prompt quoting the closing tag is rejected
+//! assert!(StopSequences::builder()
+//!     .xml_close("answer")
+//!     .guard("Example: <answer>42</answer>")
+//!     .build()
+//!     .is_err());
+//! ```
+
+use crate::common::errors::{AnthropicToolError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Zero-width space used by [`StopSequences::escape`]
+const ZERO_WIDTH_SPACE: char = '\u{200B}';
+
+/// Validated list of stop sequences
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct StopSequences(Vec<String>);
+
+impl StopSequences {
+    /// Most sequences accepted by the builder
+    pub const MAX_COUNT: usize = 16;
+    /// Longest sequence accepted by the builder, in characters
+    pub const MAX_LEN: usize = 256;
+
+    /// Start building a list of stop sequences
+    pub fn builder() -> StopSequencesBuilder {
+        StopSequencesBuilder::default()
+    }
+
+    /// Validate a list of stop sequences
+    pub fn new<I, T>(sequences: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut builder = StopSequences::builder();
+        for sequence in sequences {
+            builder.add(sequence);
+        }
+        builder.build()
+    }
+
+    /// Check a list of stop sequences against the limits of the builder
+    ///
+    /// Used by [`Body::validate`](super::body::Body::validate) for sequences
+    /// set without the builder.
+    pub fn validate(sequences: &[String]) -> Result<()> {
+        if sequences.len() > StopSequences::MAX_COUNT {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "at most {} stop sequences are allowed, got {}",
+                StopSequences::MAX_COUNT,
+                sequences.len()
+            )));
+        }
+        for (index, sequence) in sequences.iter().enumerate() {
+            if sequence.trim().is_empty() {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "stop sequence {:?} must contain non-whitespace characters",
+                    sequence
+                )));
+            }
+            if sequence.chars().count() > StopSequences::MAX_LEN {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "stop sequence {:?} exceeds {} characters",
+                    sequence,
+                    StopSequences::MAX_LEN
+                )));
+            }
+            if sequences[..index].contains(sequence) {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "duplicate stop sequence {:?}",
+                    sequence
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the sequences
+    pub fn as_slice(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Get the number of sequences
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if there are no sequences
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Find the first sequence that occurs in a text
+    pub fn find_in(&self, text: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|sequence| text.contains(sequence.as_str()))
+            .map(String::as_str)
+    }
+
+    /// Break up every occurrence of the sequences in a text
+    ///
+    /// A zero-width space is inserted after the first character of each
+    /// occurrence, so the text reads the same but no longer contains the
+    /// sequence verbatim.
+    pub fn escape(&self, text: &str) -> String {
+        let mut escaped = text.to_string();
+        for sequence in &self.0 {
+            let Some(first) = sequence.chars().next() else {
+                continue;
+            };
+            let split = first.len_utf8();
+            let replacement = format!(
+                "{}{}{}",
+                &sequence[..split],
+                ZERO_WIDTH_SPACE,
+                &sequence[split..]
+            );
+            escaped = escaped.replace(sequence.as_str(), &replacement);
+        }
+        escaped
+    }
+}
+
+impl TryFrom<Vec<String>> for StopSequences {
+    type Error = AnthropicToolError;
+
+    fn try_from(sequences: Vec<String>) -> Result<Self> {
+        StopSequences::new(sequences)
+    }
+}
+
+impl From<StopSequences> for Vec<String> {
+    fn from(sequences: StopSequences) -> Self {
+        sequences.0
+    }
+}
+
+/// Builder for [`StopSequences`]
+#[derive(Debug, Clone, Default)]
+pub struct StopSequencesBuilder {
+    sequences: Vec<String>,
+    guarded: Vec<String>,
+}
+
+impl StopSequencesBuilder {
+    /// Add a literal stop sequence
+    pub fn add<T: AsRef<str>>(&mut self, sequence: T) -> &mut Self {
+        self.sequences.push(sequence.as_ref().to_string());
+        self
+    }
+
+    /// Add the closing tag of an XML element, e.g. `</answer>` for `answer`
+    pub fn xml_close<T: AsRef<str>>(&mut self, tag: T) -> &mut Self {
+        let tag = tag.as_ref().trim_start_matches('<').trim_end_matches('>');
+        let tag = tag.trim_start_matches('/');
+        self.add(format!("</{}>", tag))
+    }
+
+    /// Add a markdown code fence (three backticks)
+    pub fn markdown_fence(&mut self) -> &mut Self {
+        self.add("```")
+    }
+
+    /// Reject the list if any sequence appears in this text
+    ///
+    /// Pass prompt parts the model sees, such as examples or an assistant prefill.
+    pub fn guard<T: AsRef<str>>(&mut self, text: T) -> &mut Self {
+        self.guarded.push(text.as_ref().to_string());
+        self
+    }
+
+    /// Validate the sequences and build the list
+    pub fn build(&self) -> Result<StopSequences> {
+        StopSequences::validate(&self.sequences)?;
+
+        let sequences = StopSequences(self.sequences.clone());
+        for text in &self.guarded {
+            if let Some(sequence) = sequences.find_in(text) {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "stop sequence {:?} appears in the prompt; the response would stop early",
+                    sequence
+                )));
+            }
+        }
+        Ok(sequences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let stops = StopSequences::builder()
+            .xml_close("answer")
+            .xml_close("</result>")
+            .markdown_fence()
+            .build()
+            .unwrap();
+        assert_eq!(stops.as_slice(), ["</answer>", "</result>", "```"]);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(StopSequences::new(["END"]).is_ok());
+        assert!(StopSequences::new([" \n"]).is_err());
+        assert!(StopSequences::new(["END", "END"]).is_err());
+        assert!(StopSequences::new(["x".repeat(StopSequences::MAX_LEN + 1)]).is_err());
+        assert!(StopSequences::new((0..=StopSequences::MAX_COUNT).map(|i| i.to_string())).is_err());
+        assert!(serde_json::from_str::<StopSequences>(r#"["", "END"]"#).is_err());
+    }
+
+    #[test]
+    fn test_guard_and_escape() {
+        let prompt = "Example: <answer>42</answer>";
+        let mut builder = StopSequences::builder();
+        builder.xml_close("answer");
+        assert!(builder.clone().guard(prompt).build().is_err());
+
+        let stops = builder.build().unwrap();
+        let escaped = stops.escape(prompt);
+        assert_ne!(escaped, prompt);
+        assert!(stops.find_in(&escaped).is_none());
+        assert_eq!(escaped.replace(ZERO_WIDTH_SPACE, ""), prompt);
+        assert!(builder.guard(&escaped).build().is_ok());
+    }
+}
@@ -0,0 +1,241 @@
+//! Automatic agentic tool loop.
+//!
+//! [`Messages::run_tool_loop`] posts the request, runs the tools the model
+//! asks for through a [`ToolRegistry`], appends the assistant turn and the
+//! tool results to the conversation, and repeats until the model stops for
+//! another reason than `tool_use`. The result is a [`ToolLoop`] with the final
+//! response, every intermediate [`ToolStep`], and the whole conversation.
+//!
+//! [`ToolLoopLimits`] bound the number of requests, the tokens spent, and the
+//! elapsed time. When a limit is hit, the loop stops with
+//! [`AnthropicToolError::LoopLimitExceeded`], which carries the partial
+//! [`ToolLoop`] so the conversation can be inspected or resumed.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::messages::request::tool_loop::ToolLoopLimits;
+//! use anthropic_tools::tools::registry::ToolRegistry;
+//! # use anthropic_tools::tools::handler::ToolHandler;
+//! # fn weather_tool() -> Box<dyn ToolHandler> { unimplemented!() }
+//!
+//! # async fn example() -> Result<()> {
+//! let mut registry = ToolRegistry::new();
+//! registry.register(weather_tool());
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .user("Should I take an umbrella in Tokyo today?");
+//!
+//! let run = client.run_tool_loop(&registry).await?;
+//! println!("{} tool rounds, {} tokens", run.steps.len(), run.usage.total_tokens());
+//! println!("{}", run.response.get_text());
+//!
+//! // Stop runaway loops
+//! let mut limits = ToolLoopLimits::new();
+//! limits.max_iterations(10).max_total_tokens(200_000);
+//! match client.run_tool_loop_with(&registry, &limits).await {
+//!     Ok(run) => println!("{}", run.response.get_text()),
+//!     Err(AnthropicToolError::LoopLimitExceeded { limit, transcript }) => {
+//!         println!("stopped after {}: {} tool rounds", limit, transcript.steps.len());
+//!     }
+//!     Err(err) => return Err(err),
+//! }
+//!
+//! // Continue the conversation
+//! client.messages(run.messages).user("And tomorrow?");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Messages::run_tool_loop`]: crate::messages::request::Messages::run_tool_loop
+//! [`AnthropicToolError::LoopLimitExceeded`]: crate::common::errors::AnthropicToolError::LoopLimitExceeded
+//! [`ToolRegistry`]: crate::tools::registry::ToolRegistry
+
+use crate::common::usage::Usage;
+use crate::messages::request::content::ContentBlock;
+use crate::messages::request::message::Message;
+use crate::messages::response::Response;
+use std::fmt;
+use std::time::Duration;
+
+/// Default upper bound on the number of requests in one tool loop
+pub const MAX_ITERATIONS: usize = 25;
+
+/// Limits of a tool loop
+///
+/// Checked after each tool round, before the next request: a request in
+/// flight is not interrupted, and the final response is always returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolLoopLimits {
+    /// Maximum number of requests
+    pub max_iterations: usize,
+
+    /// Maximum input plus output tokens over all requests
+    pub max_total_tokens: Option<usize>,
+
+    /// Maximum time since the loop started
+    pub max_wall_time: Option<Duration>,
+}
+
+impl Default for ToolLoopLimits {
+    fn default() -> Self {
+        ToolLoopLimits {
+            max_iterations: MAX_ITERATIONS,
+            max_total_tokens: None,
+            max_wall_time: None,
+        }
+    }
+}
+
+impl ToolLoopLimits {
+    /// Create limits with the defaults ([`MAX_ITERATIONS`] requests, no token or time limit)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of requests (at least 1)
+    pub fn max_iterations(&mut self, max_iterations: usize) -> &mut Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Set the maximum input plus output tokens over all requests
+    pub fn max_total_tokens(&mut self, max_total_tokens: usize) -> &mut Self {
+        self.max_total_tokens = Some(max_total_tokens);
+        self
+    }
+
+    /// Set the maximum time since the loop started
+    pub fn max_wall_time(&mut self, max_wall_time: Duration) -> &mut Self {
+        self.max_wall_time = Some(max_wall_time);
+        self
+    }
+
+    /// The first limit reached after `requests` requests, if any
+    pub(crate) fn exceeded(
+        &self,
+        requests: usize,
+        usage: &Usage,
+        elapsed: Duration,
+    ) -> Option<LoopLimit> {
+        if requests >= self.max_iterations {
+            return Some(LoopLimit::Iterations(self.max_iterations));
+        }
+        if let Some(max) = self.max_total_tokens
+            && usage.total_tokens() > max
+        {
+            return Some(LoopLimit::TotalTokens(max));
+        }
+        if let Some(max) = self.max_wall_time
+            && elapsed > max
+        {
+            return Some(LoopLimit::WallTime(max));
+        }
+        None
+    }
+}
+
+/// Limit that stopped a tool loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopLimit {
+    /// Maximum number of requests
+    Iterations(usize),
+
+    /// Maximum input plus output tokens
+    TotalTokens(usize),
+
+    /// Maximum elapsed time
+    WallTime(Duration),
+}
+
+impl fmt::Display for LoopLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopLimit::Iterations(max) => write!(f, "{} requests", max),
+            LoopLimit::TotalTokens(max) => write!(f, "{} tokens", max),
+            LoopLimit::WallTime(max) => write!(f, "{:?} wall time", max),
+        }
+    }
+}
+
+/// One round of tool use
+#[derive(Debug, Clone)]
+pub struct ToolStep {
+    /// Response that requested the tools
+    pub response: Response,
+
+    /// User message with the `tool_result` blocks sent back
+    pub results: Message,
+}
+
+/// Outcome of a tool loop
+#[derive(Debug, Clone)]
+pub struct ToolLoop {
+    /// Final response, which did not request tools (the last response, when
+    /// the loop stopped at a limit)
+    pub response: Response,
+
+    /// Tool rounds before the final response, in order
+    pub steps: Vec<ToolStep>,
+
+    /// Number of `pause_turn` responses that were resumed
+    pub pauses: usize,
+
+    /// Whole conversation, ending with the final assistant message
+    pub messages: Vec<Message>,
+
+    /// Token usage summed over all requests
+    pub usage: Usage,
+}
+
+impl ToolLoop {
+    /// Number of requests sent
+    pub fn requests(&self) -> usize {
+        self.steps.len() + self.pauses + 1
+    }
+
+    /// Names of the tools called, in order
+    pub fn tool_calls(&self) -> Vec<&str> {
+        self.steps
+            .iter()
+            .flat_map(|step| step.response.get_tool_uses())
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_exceeded() {
+        let mut limits = ToolLoopLimits::new();
+        let usage = Usage::new(900, 200);
+        assert_eq!(limits.exceeded(3, &usage, Duration::from_secs(5)), None);
+        assert_eq!(
+            limits.exceeded(MAX_ITERATIONS, &usage, Duration::ZERO),
+            Some(LoopLimit::Iterations(MAX_ITERATIONS))
+        );
+
+        limits
+            .max_total_tokens(1000)
+            .max_wall_time(Duration::from_secs(10));
+        assert_eq!(
+            limits.exceeded(3, &usage, Duration::ZERO),
+            Some(LoopLimit::TotalTokens(1000))
+        );
+        assert_eq!(
+            limits.exceeded(3, &Usage::new(10, 10), Duration::from_secs(11)),
+            Some(LoopLimit::WallTime(Duration::from_secs(10)))
+        );
+        assert_eq!(LoopLimit::TotalTokens(1000).to_string(), "1000 tokens");
+    }
+}
@@ -4,6 +4,7 @@
 //!
 //! - [`Response`] - Complete API response with content and metadata
 //! - [`StopReason`] - Reason why the model stopped generating
+//! - [`ToolCall`] - Tool call requested in a response
 //!
 //! # Accessing Response Content
 //!
@@ -35,6 +36,26 @@
 //! # }
 //! ```
 //!
+//! # Answering Tool Calls
+//!
+//! [`Response::answer_tool_calls`] builds the assistant message and the user
+//! message with one `tool_result` per call, ready to append:
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//!
+//! # async fn example() -> Result<()> {
+//! # let mut client = Messages::new();
+//! let response = client.post().await?;
+//! let (assistant, results) = response.answer_tool_calls(|call| match call.name.as_str() {
+//!     "get_weather" => "Sunny, 22°C".to_string(),
+//!     name => format!("unknown tool: {}", name),
+//! });
+//! client.add_message(assistant).add_message(results);
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Stop Reasons
 //!
 //! - `EndTurn` - Natural end of response
@@ -44,9 +65,21 @@
 //! - `Refusal` - Content was refused
 
 use crate::common::Usage;
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::server_tool::{
+    COMPUTER_NAME, CodeExecutionResult, CodeExecutionToolResultContent,
+    CodeExecutionToolResultError, ComputerAction, Container, WebSearchResult,
+    WebSearchToolResultContent, WebSearchToolResultError,
+};
+use crate::common::tool::parse_input;
+use crate::messages::request::citation::Citation;
 use crate::messages::request::content::ContentBlock;
+use crate::messages::request::context_management::AppliedContextEdits;
+use crate::messages::request::message::Message;
 use crate::messages::request::role::Role;
+use crate::tools::handler::ToolOutput;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use strum::{Display, EnumString};
 
 /// Response from the Messages API
@@ -78,11 +111,45 @@ pub struct Response {
 
     /// Token usage information
     pub usage: Usage,
+
+    /// Container used by the code execution tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<Container>,
+
+    /// Context edits applied before the request was processed (beta)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_management: Option<AppliedContextEdits>,
+}
+
+/// Tool call requested in a response (a `tool_use` block)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    /// ID the `tool_result` must refer to
+    pub id: String,
+
+    /// Name of the tool
+    pub name: String,
+
+    /// Input chosen by the model
+    pub input: Value,
+}
+
+impl ToolCall {
+    /// Deserialize the input into a typed struct
+    pub fn parse_input<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        parse_input(&self.name, &self.input)
+    }
+
+    /// Build the `tool_result` block answering this call
+    pub fn result<T: Into<ToolOutput>>(&self, output: T) -> ContentBlock {
+        output.into().into_tool_result(&self.id)
+    }
 }
 
 /// Reason the model stopped generating
 #[derive(Serialize, Deserialize, Debug, Clone, Display, EnumString, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum StopReason {
     /// Natural end of the response
     EndTurn,
@@ -98,6 +165,14 @@ pub enum StopReason {
 
     /// Content was refused
     Refusal,
+
+    /// A server tool (web search, code execution) paused a long turn; send
+    /// the conversation again, response included, to let the model continue
+    PauseTurn,
+
+    /// Reason added to the API after this crate was released
+    #[serde(other)]
+    Unknown,
 }
 
 impl Response {
@@ -126,6 +201,73 @@ impl Response {
             .join("")
     }
 
+    /// Deserialize the JSON text of a structured output response
+    ///
+    /// Pairs with [`Messages::output_format`](crate::messages::request::Messages::output_format).
+    /// Errors name the path of the offending field; output cut off by
+    /// `max_tokens` fails to parse.
+    pub fn parse_output<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        let text = self.get_text();
+        let deserializer = &mut serde_json::Deserializer::from_str(&text);
+        serde_path_to_error::deserialize(deserializer).map_err(|err| {
+            AnthropicToolError::InvalidOutput {
+                path: err.path().to_string(),
+                message: err.inner().to_string(),
+            }
+        })
+    }
+
+    /// Get the citations of all text blocks, in order
+    pub fn citations(&self) -> Vec<&Citation> {
+        self.content
+            .iter()
+            .flat_map(|block| block.citations())
+            .collect()
+    }
+
+    /// Get the text with `[n]` markers after cited passages, followed by the sources
+    ///
+    /// Each source is listed once as `[n] "quote" — source`, in order of first
+    /// use. Without citations this is the same as [`get_text`](Self::get_text).
+    pub fn get_text_with_citations(&self) -> String {
+        let mut text = String::new();
+        let mut sources: Vec<&Citation> = Vec::new();
+        for block in &self.content {
+            let ContentBlock::Text {
+                text: block_text, ..
+            } = block
+            else {
+                continue;
+            };
+            text.push_str(block_text);
+            for citation in block.citations() {
+                let number = match sources.iter().position(|c| *c == citation) {
+                    Some(i) => i + 1,
+                    None => {
+                        sources.push(citation);
+                        sources.len()
+                    }
+                };
+                text.push_str(&format!("[{}]", number));
+            }
+        }
+        if !sources.is_empty() {
+            text.push_str("\n\nSources:");
+            for (i, citation) in sources.iter().enumerate() {
+                text.push_str(&format!("\n[{}] {}", i + 1, citation));
+            }
+        }
+        text
+    }
+
+    /// Convert into an assistant message for continuing the conversation
+    ///
+    /// All blocks are kept, including `thinking` blocks, which the API
+    /// requires when the next turn answers `tool_use` blocks.
+    pub fn to_message(&self) -> Message {
+        Message::new(Role::Assistant, self.content.clone())
+    }
+
     /// Check if the response contains tool use
     pub fn has_tool_use(&self) -> bool {
         self.content
@@ -141,13 +283,175 @@ impl Response {
             .collect()
     }
 
+    /// Get the tool calls, in order
+    pub fn tool_calls(&self) -> Vec<ToolCall> {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { id, name, input } => Some(ToolCall {
+                    id: id.clone(),
+                    name: name.clone(),
+                    input: input.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Answer every tool call, returning the assistant and user messages to append
+    ///
+    /// `answer` runs once per call, in order; its outputs become the
+    /// `tool_result` blocks of the user message.
+    pub fn answer_tool_calls<F, T>(&self, mut answer: F) -> (Message, Message)
+    where
+        F: FnMut(&ToolCall) -> T,
+        T: Into<ToolOutput>,
+    {
+        let results = self
+            .tool_calls()
+            .iter()
+            .map(|call| answer(call).into().into_tool_result(&call.id))
+            .collect();
+        (self.to_message(), Message::new(Role::User, results))
+    }
+
+    /// Pair `tool_result` blocks with the tool calls, returning the messages to append
+    ///
+    /// The results may come in any order and are sorted into the order of the
+    /// calls. Fails if a call is left unanswered, answered twice, or a result
+    /// does not answer any call.
+    pub fn pair_tool_results(&self, results: Vec<ContentBlock>) -> Result<(Message, Message)> {
+        let calls = self.tool_calls();
+        let mut slots: Vec<Option<ContentBlock>> = vec![None; calls.len()];
+        for result in results {
+            let ContentBlock::ToolResult { tool_use_id, .. } = &result else {
+                return Err(AnthropicToolError::InvalidParameter(
+                    "expected a tool_result block".to_string(),
+                ));
+            };
+            let index = calls
+                .iter()
+                .position(|call| &call.id == tool_use_id)
+                .ok_or_else(|| {
+                    AnthropicToolError::InvalidParameter(format!(
+                        "tool_result answers no tool call: {}",
+                        tool_use_id
+                    ))
+                })?;
+            if slots[index].is_some() {
+                return Err(AnthropicToolError::InvalidParameter(format!(
+                    "tool call {} is answered twice",
+                    tool_use_id
+                )));
+            }
+            slots[index] = Some(result);
+        }
+        let results = slots
+            .into_iter()
+            .zip(&calls)
+            .map(|(slot, call)| {
+                slot.ok_or_else(|| {
+                    AnthropicToolError::InvalidParameter(format!(
+                        "tool call {} ({}) has no tool_result",
+                        call.id, call.name
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok((self.to_message(), Message::new(Role::User, results)))
+    }
+
+    /// Get the calls of server tools (e.g. web search) run by the API
+    ///
+    /// Unlike [`Response::get_tool_uses`], these need no `tool_result`.
+    pub fn get_server_tool_uses(&self) -> Vec<&ContentBlock> {
+        self.content
+            .iter()
+            .filter(|block| matches!(block, ContentBlock::ServerToolUse { .. }))
+            .collect()
+    }
+
+    /// Get the results of all web searches, in order
+    pub fn web_search_results(&self) -> Vec<&WebSearchResult> {
+        self.content
+            .iter()
+            .flat_map(|block| match block {
+                ContentBlock::WebSearchToolResult { content, .. } => content.results(),
+                _ => &[],
+            })
+            .collect()
+    }
+
+    /// Get the errors of web searches that failed
+    pub fn web_search_errors(&self) -> Vec<&WebSearchToolResultError> {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::WebSearchToolResult {
+                    content: WebSearchToolResultContent::Error(error),
+                    ..
+                } => Some(error),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the outputs of the code run by the code execution tool, in order
+    pub fn get_code_results(&self) -> Vec<&CodeExecutionResult> {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::CodeExecutionToolResult {
+                    content: CodeExecutionToolResultContent::Result(result),
+                    ..
+                } => Some(result),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the errors of code executions that could not run
+    pub fn get_code_errors(&self) -> Vec<&CodeExecutionToolResultError> {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::CodeExecutionToolResult {
+                    content: CodeExecutionToolResultContent::Error(error),
+                    ..
+                } => Some(error),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Deserialize the input of the first call to a tool into a typed struct
+    pub fn parse_tool_input<T: serde::de::DeserializeOwned>(&self, name: &str) -> Result<T> {
+        self.content
+            .iter()
+            .find(|block| matches!(block, ContentBlock::ToolUse { name: tool, .. } if tool == name))
+            .ok_or_else(|| {
+                AnthropicToolError::InvalidParameter(format!("no tool_use block for tool {}", name))
+            })?
+            .parse_input()
+    }
+
+    /// Parse the computer use actions requested, with their `tool_use` IDs
+    pub fn get_computer_actions(&self) -> Result<Vec<(&str, ComputerAction)>> {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { id, name, .. } if name == COMPUTER_NAME => {
+                    Some(block.parse_input().map(|action| (id.as_str(), action)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Get tool use by ID
     pub fn get_tool_use_by_id(&self, id: &str) -> Option<&ContentBlock> {
         self.content.iter().find(|block| match block {
-            ContentBlock::ToolUse {
-                id: tool_id,
-                ..
-            } => tool_id == id,
+            ContentBlock::ToolUse { id: tool_id, .. } => tool_id == id,
             _ => false,
         })
     }
@@ -172,11 +476,50 @@ impl Response {
             .into()
     }
 
+    /// Get a copy of the response keeping only blocks of the given types
+    ///
+    /// Types are the wire names returned by [`ContentBlock::block_type`],
+    /// e.g. `["text", "tool_use"]`.
+    pub fn filtered<I, S>(&self, content_types: I) -> Response
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let content_types: Vec<S> = content_types.into_iter().collect();
+        let mut response = self.clone();
+        response.content.retain(|block| {
+            content_types
+                .iter()
+                .any(|content_type| content_type.as_ref() == block.block_type())
+        });
+        response
+    }
+
+    /// Remove thinking and redacted thinking blocks in place
+    ///
+    /// Use before persisting transcripts that must not contain chain-of-thought.
+    /// Note that extended thinking with tool use requires the thinking blocks to
+    /// be sent back on the next turn.
+    pub fn strip_thinking(&mut self) -> &mut Self {
+        self.content.retain(|block| {
+            !matches!(
+                block,
+                ContentBlock::Thinking { .. } | ContentBlock::RedactedThinking { .. }
+            )
+        });
+        self
+    }
+
     /// Check if the model stopped due to tool use
     pub fn stopped_for_tool_use(&self) -> bool {
         self.stop_reason == Some(StopReason::ToolUse)
     }
 
+    /// Check if a server tool paused the turn, to be continued by resending it
+    pub fn stopped_for_pause(&self) -> bool {
+        self.stop_reason == Some(StopReason::PauseTurn)
+    }
+
     /// Check if the model stopped naturally
     pub fn stopped_naturally(&self) -> bool {
         self.stop_reason == Some(StopReason::EndTurn)
@@ -186,6 +529,68 @@ impl Response {
     pub fn hit_max_tokens(&self) -> bool {
         self.stop_reason == Some(StopReason::MaxTokens)
     }
+
+    /// Get the stop sequence that ended the response, if one did
+    pub fn matched_stop_sequence(&self) -> Option<&str> {
+        match self.stop_reason {
+            Some(StopReason::StopSequence) => self.stop_sequence.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Get the text followed by the matched stop sequence
+    ///
+    /// The API leaves the sequence out of the text; this restores it, e.g.
+    /// to close an XML tag used as a stop sequence.
+    pub fn get_text_with_stop_sequence(&self) -> String {
+        let mut text = self.get_text();
+        text.push_str(self.matched_stop_sequence().unwrap_or_default());
+        text
+    }
+
+    /// Get the text preceded by the prefill of the request
+    ///
+    /// The API returns only the continuation of a prefilled reply; this
+    /// restores the reply as a whole.
+    pub fn get_text_with_prefill(&self, prefill: &str) -> String {
+        format!("{}{}", prefill, self.get_text())
+    }
+
+    /// Prepend the prefill of the request to the first text block
+    ///
+    /// Afterwards [`get_text`](Self::get_text) and
+    /// [`parse_output`](Self::parse_output) see the whole reply. A text block
+    /// is added when the response has none.
+    pub fn prepend_prefill(&mut self, prefill: &str) -> &mut Self {
+        match self
+            .content
+            .iter_mut()
+            .find(|block| matches!(block, ContentBlock::Text { .. }))
+        {
+            Some(ContentBlock::Text { text, .. }) => text.insert_str(0, prefill),
+            _ => self.content.push(ContentBlock::text(prefill)),
+        }
+        self
+    }
+
+    /// Remove the matched stop sequence from the end of the text, if present
+    ///
+    /// The API does not include it, but recorded or proxied responses may.
+    pub fn trim_stop_sequence(&mut self) -> &mut Self {
+        let Some(sequence) = self.matched_stop_sequence().map(str::to_string) else {
+            return self;
+        };
+        if let Some(ContentBlock::Text { text, .. }) = self
+            .content
+            .iter_mut()
+            .rev()
+            .find(|block| matches!(block, ContentBlock::Text { .. }))
+            && text.ends_with(&sequence)
+        {
+            text.truncate(text.len() - sequence.len());
+        }
+        self
+    }
 }
 
 #[cfg(test)]
@@ -199,12 +604,15 @@ mod tests {
             role: Role::Assistant,
             content: vec![ContentBlock::Text {
                 text: "Hello, world!".to_string(),
+                citations: None,
                 cache_control: None,
             }],
             model: "claude-sonnet-4-20250514".to_string(),
             stop_reason: Some(StopReason::EndTurn),
             stop_sequence: None,
             usage: Usage::new(10, 5),
+            container: None,
+            context_management: None,
         }
     }
 
@@ -214,6 +622,47 @@ mod tests {
         assert_eq!(response.get_text(), "Hello, world!");
     }
 
+    #[test]
+    fn test_prefill() {
+        let mut response = sample_response();
+        response.content = vec![ContentBlock::text(r#""name": "Ada"}"#)];
+        assert_eq!(response.get_text_with_prefill("{"), r#"{"name": "Ada"}"#);
+
+        response.prepend_prefill("{");
+        let value: Value = response.parse_output().unwrap();
+        assert_eq!(value["name"], "Ada");
+
+        response.content.clear();
+        response.prepend_prefill("{");
+        assert_eq!(response.get_text(), "{");
+    }
+
+    #[test]
+    fn test_parse_output() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Contact {
+            name: String,
+            age: u32,
+        }
+
+        let mut response = sample_response();
+        response.content = vec![ContentBlock::text(r#"{"name": "Ada", "age": 36}"#)];
+        let contact: Contact = response.parse_output().unwrap();
+        assert_eq!(
+            contact,
+            Contact {
+                name: "Ada".to_string(),
+                age: 36
+            }
+        );
+
+        response.content = vec![ContentBlock::text(r#"{"name": "Ada", "age": "36"}"#)];
+        match response.parse_output::<Contact>() {
+            Err(AnthropicToolError::InvalidOutput { path, .. }) => assert_eq!(path, "age"),
+            other => panic!("expected InvalidOutput, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_response_stop_reason() {
         let response = sample_response();
@@ -222,6 +671,24 @@ mod tests {
         assert!(!response.hit_max_tokens());
     }
 
+    #[test]
+    fn test_matched_stop_sequence() {
+        let mut response = sample_response();
+        response.stop_sequence = Some("</answer>".to_string());
+        assert_eq!(response.matched_stop_sequence(), None);
+
+        response.stop_reason = Some(StopReason::StopSequence);
+        assert_eq!(response.matched_stop_sequence(), Some("</answer>"));
+        assert_eq!(
+            response.get_text_with_stop_sequence(),
+            "Hello, world!</answer>"
+        );
+        assert_eq!(response.trim_stop_sequence().get_text(), "Hello, world!");
+
+        response.content = vec![ContentBlock::text("<answer>42</answer>")];
+        assert_eq!(response.trim_stop_sequence().get_text(), "<answer>42");
+    }
+
     #[test]
     fn test_response_with_tool_use() {
         let response = Response {
@@ -231,6 +698,7 @@ mod tests {
             content: vec![
                 ContentBlock::Text {
                     text: "Let me search for that.".to_string(),
+                    citations: None,
                     cache_control: None,
                 },
                 ContentBlock::ToolUse {
@@ -243,11 +711,271 @@ mod tests {
             stop_reason: Some(StopReason::ToolUse),
             stop_sequence: None,
             usage: Usage::new(20, 15),
+            container: None,
+            context_management: None,
         };
 
         assert!(response.has_tool_use());
         assert!(response.stopped_for_tool_use());
         assert_eq!(response.get_tool_uses().len(), 1);
+
+        #[derive(serde::Deserialize)]
+        struct Search {
+            query: String,
+        }
+        let search: Search = response.parse_tool_input("search").unwrap();
+        assert_eq!(search.query, "test");
+        assert!(response.parse_tool_input::<Search>("missing").is_err());
+        assert!(response.content[0].parse_input::<Search>().is_err());
+    }
+
+    #[test]
+    fn test_get_computer_actions() {
+        let mut response = sample_response();
+        response.content.push(ContentBlock::ToolUse {
+            id: "toolu_1".to_string(),
+            name: "computer".to_string(),
+            input: serde_json::json!({"action": "mouse_move", "coordinate": [10, 20]}),
+        });
+        response.content.push(ContentBlock::ToolUse {
+            id: "toolu_2".to_string(),
+            name: "computer".to_string(),
+            input: serde_json::json!({"action": "screenshot"}),
+        });
+
+        let actions = response.get_computer_actions().unwrap();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(
+            actions[0],
+            (
+                "toolu_1",
+                ComputerAction::MouseMove {
+                    coordinate: [10, 20]
+                }
+            )
+        );
+        assert!(actions[1].1.is_screenshot());
+
+        response.content.push(ContentBlock::ToolUse {
+            id: "toolu_3".to_string(),
+            name: "computer".to_string(),
+            input: serde_json::json!({"action": "teleport"}),
+        });
+        assert!(response.get_computer_actions().is_err());
+    }
+
+    #[test]
+    fn test_tool_calls() {
+        let mut response = sample_response();
+        response.content.push(ContentBlock::tool_use(
+            "toolu_1",
+            "get_weather",
+            serde_json::json!({"location": "Tokyo"}),
+        ));
+        response.content.push(ContentBlock::tool_use(
+            "toolu_2",
+            "get_time",
+            serde_json::json!({}),
+        ));
+
+        let calls = response.tool_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].name, "get_weather");
+        #[derive(Deserialize)]
+        struct Input {
+            location: String,
+        }
+        assert_eq!(calls[0].parse_input::<Input>().unwrap().location, "Tokyo");
+
+        let (assistant, results) = response.answer_tool_calls(|call| format!("{} done", call.name));
+        assert_eq!(assistant.role, Role::Assistant);
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["role"], "user");
+        assert_eq!(json["content"][1]["tool_use_id"], "toolu_2");
+        assert_eq!(json["content"][1]["content"][0]["text"], "get_time done");
+
+        let (_, results) = response
+            .pair_tool_results(vec![calls[1].result("12:00"), calls[0].result("Sunny")])
+            .unwrap();
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["content"][0]["tool_use_id"], "toolu_1");
+        assert_eq!(json["content"][1]["tool_use_id"], "toolu_2");
+
+        let err = response
+            .pair_tool_results(vec![calls[0].result("Sunny")])
+            .unwrap_err();
+        assert!(err.to_string().contains("(get_time) has no tool_result"));
+        assert!(
+            response
+                .pair_tool_results(vec![
+                    calls[0].result("a"),
+                    calls[0].result("b"),
+                    calls[1].result("c")
+                ])
+                .is_err()
+        );
+        assert!(
+            response
+                .pair_tool_results(vec![ContentBlock::tool_result_text("toolu_9", "x")])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_web_search_blocks() {
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "id": "msg_1", "type": "message", "role": "assistant",
+            "model": "claude-sonnet-4-20250514", "stop_reason": "end_turn",
+            "usage": {"input_tokens": 10, "output_tokens": 5},
+            "content": [
+                {"type": "server_tool_use", "id": "srvtoolu_1", "name": "web_search",
+                 "input": {"query": "rust release"}},
+                {"type": "web_search_tool_result", "tool_use_id": "srvtoolu_1", "content": [
+                    {"type": "web_search_result", "url": "https://blog.rust-lang.org",
+                     "title": "Rust Blog", "encrypted_content": "EqgfCioIARgB", "page_age": "2 days ago"}
+                ]},
+                {"type": "server_tool_use", "id": "srvtoolu_2", "name": "web_search",
+                 "input": {"query": "rust 2024"}},
+                {"type": "web_search_tool_result", "tool_use_id": "srvtoolu_2", "content":
+                    {"type": "web_search_tool_result_error", "error_code": "max_uses_exceeded"}},
+                {"type": "text", "text": "Rust 1.90 is out."}
+            ]
+        }))
+        .unwrap();
+
+        assert!(!response.has_tool_use());
+        assert_eq!(response.get_server_tool_uses().len(), 2);
+        let results = response.web_search_results();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Rust Blog");
+        assert_eq!(results[0].page_age.as_deref(), Some("2 days ago"));
+        assert_eq!(
+            response.web_search_errors()[0].error_code,
+            "max_uses_exceeded"
+        );
+
+        // Passed back verbatim
+        let json = serde_json::to_value(response.to_message()).unwrap();
+        assert_eq!(
+            json["content"][1]["content"][0]["encrypted_content"],
+            "EqgfCioIARgB"
+        );
+        assert_eq!(
+            json["content"][3]["content"]["error_code"],
+            "max_uses_exceeded"
+        );
+    }
+
+    #[test]
+    fn test_citations() {
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "id": "msg_1", "type": "message", "role": "assistant",
+            "model": "claude-sonnet-4-20250514", "stop_reason": "end_turn",
+            "usage": {"input_tokens": 10, "output_tokens": 5},
+            "content": [
+                {"type": "text", "text": "According to the report, "},
+                {"type": "text", "text": "revenue grew 12%", "citations": [
+                    {"type": "page_location", "cited_text": "Revenue grew 12%.",
+                     "document_index": 0, "document_title": "Annual Report",
+                     "start_page_number": 3, "end_page_number": 4}
+                ]},
+                {"type": "text", "text": " and margins held", "citations": [
+                    {"type": "page_location", "cited_text": "Margins were stable.",
+                     "document_index": 0, "document_title": "Annual Report",
+                     "start_page_number": 5, "end_page_number": 6},
+                    {"type": "page_location", "cited_text": "Revenue grew 12%.",
+                     "document_index": 0, "document_title": "Annual Report",
+                     "start_page_number": 3, "end_page_number": 4}
+                ]},
+                {"type": "text", "text": "."}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(response.citations().len(), 3);
+        assert_eq!(
+            response.get_text(),
+            "According to the report, revenue grew 12% and margins held."
+        );
+        assert_eq!(
+            response.get_text_with_citations(),
+            "According to the report, revenue grew 12%[1] and margins held[2][1].\n\n\
+             Sources:\n\
+             [1] \"Revenue grew 12%.\" — Annual Report, p. 3\n\
+             [2] \"Margins were stable.\" — Annual Report, p. 5"
+        );
+        assert_eq!(sample_response().get_text_with_citations(), "Hello, world!");
+
+        // Sent back with the assistant turn
+        let json = serde_json::to_value(response.to_message()).unwrap();
+        assert_eq!(json["content"][1]["citations"][0]["start_page_number"], 3);
+        assert!(json["content"][0].get("citations").is_none());
+    }
+
+    #[test]
+    fn test_code_execution_blocks() {
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "id": "msg_1", "type": "message", "role": "assistant",
+            "model": "claude-sonnet-4-20250514", "stop_reason": "end_turn",
+            "usage": {"input_tokens": 10, "output_tokens": 5},
+            "content": [
+                {"type": "server_tool_use", "id": "srvtoolu_1", "name": "code_execution",
+                 "input": {"code": "print(6 * 7)"}},
+                {"type": "code_execution_tool_result", "tool_use_id": "srvtoolu_1", "content": {
+                    "type": "code_execution_result", "stdout": "42\n", "stderr": "",
+                    "return_code": 0,
+                    "content": [{"type": "code_execution_output", "file_id": "file_011"}]
+                }},
+                {"type": "code_execution_tool_result", "tool_use_id": "srvtoolu_2", "content": {
+                    "type": "code_execution_tool_result_error", "error_code": "container_expired"
+                }}
+            ]
+        }))
+        .unwrap();
+
+        let results = response.get_code_results();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].stdout, "42\n");
+        assert!(results[0].succeeded());
+        assert_eq!(results[0].file_ids(), vec!["file_011"]);
+        assert_eq!(
+            response.get_code_errors()[0].error_code,
+            "container_expired"
+        );
+
+        let json = serde_json::to_value(response.to_message()).unwrap();
+        assert_eq!(json["content"][1]["content"]["return_code"], 0);
+    }
+
+    #[test]
+    fn test_filtered_and_strip_thinking() {
+        let mut response = sample_response();
+        response.content.insert(
+            0,
+            ContentBlock::Thinking {
+                thinking: "Let me think".to_string(),
+                signature: Some("sig".to_string()),
+            },
+        );
+        response.content.insert(
+            1,
+            ContentBlock::RedactedThinking {
+                data: "encrypted".to_string(),
+            },
+        );
+
+        let text_only = response.filtered(["text"]);
+        assert_eq!(text_only.content.len(), 1);
+        assert!(!text_only.has_thinking());
+        assert_eq!(response.content.len(), 3);
+        assert_eq!(response.to_message().content.len(), 3);
+
+        assert!(response.filtered(Vec::<String>::new()).content.is_empty());
+
+        response.strip_thinking();
+        assert!(!response.has_thinking());
+        assert_eq!(response.content.len(), 1);
+        assert_eq!(response.get_text(), "Hello, world!");
     }
 
     #[test]
@@ -286,4 +1014,26 @@ mod tests {
         let json = serde_json::to_string(&reason).unwrap();
         assert_eq!(json, "\"end_turn\"");
     }
+
+    #[test]
+    fn test_deserialize_unknown_values() {
+        let json = r#"{
+            "id": "msg_01XYZ",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "text", "text": "Hello!"},
+                {"type": "hologram", "frames": 3}
+            ],
+            "model": "claude-sonnet-4-20250514",
+            "stop_reason": "stopped_by_magic",
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }"#;
+
+        let response: Response = serde_json::from_str(json).unwrap();
+        assert_eq!(response.get_text(), "Hello!");
+        assert_eq!(response.stop_reason, Some(StopReason::Unknown));
+        assert_eq!(response.content[1].block_type(), "hologram");
+        assert_eq!(response.filtered(["hologram"]).content.len(), 1);
+    }
 }
@@ -4,10 +4,13 @@
 //! responses from the Anthropic API:
 //!
 //! - [`StreamEvent`] - Enum of all possible stream event types
-//! - [`Delta`] - Content deltas (text, tool input, thinking)
+//! - [`Delta`] - Content deltas (text, tool input, thinking, citations)
 //! - [`MessageDelta`] - Final message metadata (stop reason, usage)
 //! - [`StreamAccumulator`] - Helper for accumulating streamed content
 //! - [`parse_sse_line`] - Parse individual SSE lines
+//! - [`EventStream`] - Events of a streaming request, parsed as they arrive
+//! - [`TeeStream`] - Event stream that also feeds an accumulator and sinks
+//! - [`EventRecorder`] - Sink that keeps a copy of every event
 //!
 //! # Stream Event Types
 //!
@@ -41,16 +44,74 @@
 //!
 //! assert_eq!(acc.get_text(), "Hello");
 //! ```
+//!
+//! # Consuming a Stream
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//!
+//! # async fn example() -> Result<()> {
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .user("Tell me a story");
+//!
+//! let mut events = client.post_stream().await?;
+//! while let Some(event) = events.next().await {
+//!     if let StreamEvent::ContentBlockDelta { delta: Delta::TextDelta { text }, .. } = event? {
+//!         print!("{}", text);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Live Deltas and the Final Response
+//!
+//! [`EventStream::tee`] forwards events to the caller while feeding a
+//! [`StreamAccumulator`] and any number of [`EventSink`]s, so the complete
+//! [`Response`] is available once the stream ends.
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::messages::streaming::EventRecorder;
+//!
+//! # async fn example(client: Messages) -> Result<()> {
+//! let recorder = EventRecorder::new();
+//! let mut events = client.post_stream().await?.tee().sink(recorder.clone());
+//!
+//! while let Some(event) = events.next().await {
+//!     if let StreamEvent::ContentBlockDelta { delta: Delta::TextDelta { text }, .. } = event? {
+//!         print!("{}", text);
+//!     }
+//! }
+//!
+//! let response = events.into_response()?;
+//! std::fs::write("transcript.sse", recorder.to_sse_text()?)?;
+//! # Ok(())
+//! # }
+//! ```
 
-use crate::common::errors::{ErrorDetail, Result};
 use crate::common::Usage;
+use crate::common::cassette::{Cassette, Interaction, RecordedRequest, RecordedResponse};
+use crate::common::errors::{AnthropicToolError, ErrorDetail, ErrorResponse, Result};
+use crate::common::future::MaybeSend;
+use crate::common::server_tool::Container;
+use crate::common::transport::{BodyStream, HttpResponse};
+use crate::messages::request::citation::Citation;
 use crate::messages::request::content::ContentBlock;
-use crate::messages::response::Response;
+use crate::messages::request::context_management::AppliedContextEdits;
+use crate::messages::response::{Response, StopReason};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
 
 /// Server-Sent Events stream event types
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
+#[non_exhaustive]
 pub enum StreamEvent {
     /// Message has started
     #[serde(rename = "message_start")]
@@ -86,6 +147,10 @@ pub enum StreamEvent {
     /// Error event
     #[serde(rename = "error")]
     Error { error: ErrorDetail },
+
+    /// Event type added to the API after this crate was released
+    #[serde(other)]
+    Unknown,
 }
 
 /// Delta types for streaming content
@@ -107,6 +172,10 @@ pub enum Delta {
     /// Signature delta (for thinking)
     #[serde(rename = "signature_delta")]
     SignatureDelta { signature: String },
+
+    /// Citation of the text block (when citations are enabled)
+    #[serde(rename = "citations_delta")]
+    CitationsDelta { citation: Citation },
 }
 
 /// Message delta for final message updates
@@ -119,6 +188,14 @@ pub struct MessageDelta {
     /// Stop sequence that caused the stop
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_sequence: Option<String>,
+
+    /// Container used by the code execution tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<Container>,
+
+    /// Context edits applied before the request was processed (beta)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_management: Option<AppliedContextEdits>,
 }
 
 /// SSE data line prefix
@@ -178,6 +255,18 @@ pub struct StreamAccumulator {
 
     /// Message ID
     pub id: Option<String>,
+
+    /// Stop sequence that ended the message
+    pub stop_sequence: Option<String>,
+
+    /// Message from the `message_start` event
+    pub message: Option<Response>,
+
+    /// Container used by the code execution tool
+    pub container: Option<Container>,
+
+    /// Context edits reported by the `message_delta` event
+    pub context_management: Option<AppliedContextEdits>,
 }
 
 impl StreamAccumulator {
@@ -190,8 +279,9 @@ impl StreamAccumulator {
     pub fn process_event(&mut self, event: StreamEvent) {
         match event {
             StreamEvent::MessageStart { message } => {
-                self.id = Some(message.id);
-                self.model = Some(message.model);
+                self.id = Some(message.id.clone());
+                self.model = Some(message.model.clone());
+                self.message = Some(message);
             }
             StreamEvent::ContentBlockStart {
                 content_block,
@@ -201,6 +291,7 @@ impl StreamAccumulator {
                 while self.content_blocks.len() <= index {
                     self.content_blocks.push(ContentBlock::Text {
                         text: String::new(),
+                        citations: None,
                         cache_control: None,
                     });
                 }
@@ -219,8 +310,9 @@ impl StreamAccumulator {
                 }
                 Delta::InputJsonDelta { partial_json } => {
                     // For tool use, accumulate JSON
-                    if let Some(ContentBlock::ToolUse { id, .. }) =
-                        self.content_blocks.get(index)
+                    if let Some(
+                        ContentBlock::ToolUse { id, .. } | ContentBlock::ServerToolUse { id, .. },
+                    ) = self.content_blocks.get(index)
                     {
                         self.tool_inputs
                             .entry(id.clone())
@@ -230,9 +322,31 @@ impl StreamAccumulator {
                 }
                 Delta::ThinkingDelta { thinking } => {
                     self.thinking.push_str(&thinking);
+                    if let Some(ContentBlock::Thinking {
+                        thinking: block_thinking,
+                        ..
+                    }) = self.content_blocks.get_mut(index)
+                    {
+                        block_thinking.push_str(&thinking);
+                    }
+                }
+                Delta::SignatureDelta { signature } => {
+                    if let Some(ContentBlock::Thinking {
+                        signature: block_signature,
+                        ..
+                    }) = self.content_blocks.get_mut(index)
+                    {
+                        block_signature
+                            .get_or_insert_with(String::new)
+                            .push_str(&signature);
+                    }
                 }
-                Delta::SignatureDelta { .. } => {
-                    // Signatures are typically not accumulated
+                Delta::CitationsDelta { citation } => {
+                    if let Some(ContentBlock::Text { citations, .. }) =
+                        self.content_blocks.get_mut(index)
+                    {
+                        citations.get_or_insert_with(Vec::new).push(citation);
+                    }
                 }
             },
             StreamEvent::ContentBlockStop { .. } => {
@@ -240,6 +354,13 @@ impl StreamAccumulator {
             }
             StreamEvent::MessageDelta { delta, usage } => {
                 self.stop_reason = delta.stop_reason;
+                self.stop_sequence = delta.stop_sequence;
+                if delta.container.is_some() {
+                    self.container = delta.container;
+                }
+                if delta.context_management.is_some() {
+                    self.context_management = delta.context_management;
+                }
                 self.usage = Some(usage);
             }
             StreamEvent::MessageStop => {
@@ -251,6 +372,9 @@ impl StreamAccumulator {
             StreamEvent::Error { .. } => {
                 // Error handled separately
             }
+            StreamEvent::Unknown => {
+                // New event type, ignore
+            }
         }
     }
 
@@ -263,6 +387,325 @@ impl StreamAccumulator {
     pub fn is_complete(&self) -> bool {
         self.stop_reason.is_some()
     }
+
+    /// Reassemble the complete [`Response`] from the accumulated events
+    ///
+    /// Tool inputs streamed as partial JSON are parsed into the `tool_use`
+    /// blocks. Usage combines the input counts from `message_start` with the
+    /// final counts from `message_delta`.
+    pub fn into_response(self) -> Result<Response> {
+        let mut response = self.message.ok_or_else(|| {
+            AnthropicToolError::MissingRequiredField("message_start event".to_string())
+        })?;
+
+        let mut content = self.content_blocks;
+        for block in &mut content {
+            if let ContentBlock::ToolUse { id, input, .. }
+            | ContentBlock::ServerToolUse { id, input, .. } = block
+                && let Some(json) = self.tool_inputs.get(id.as_str())
+                && !json.is_empty()
+            {
+                *input = serde_json::from_str(json)?;
+            }
+        }
+        response.content = content;
+
+        if let Some(usage) = self.usage {
+            response.usage.output_tokens = usage.output_tokens;
+            if usage.input_tokens > 0 {
+                response.usage.input_tokens = usage.input_tokens;
+            }
+            if usage.cache_creation_input_tokens.is_some() {
+                response.usage.cache_creation_input_tokens = usage.cache_creation_input_tokens;
+            }
+            if usage.cache_read_input_tokens.is_some() {
+                response.usage.cache_read_input_tokens = usage.cache_read_input_tokens;
+            }
+        }
+        response.stop_reason = self.stop_reason.map(|reason| {
+            serde_json::from_value(serde_json::Value::String(reason)).unwrap_or(StopReason::Unknown)
+        });
+        response.stop_sequence = self.stop_sequence;
+        if self.container.is_some() {
+            response.container = self.container;
+        }
+        if self.context_management.is_some() {
+            response.context_management = self.context_management;
+        }
+        Ok(response)
+    }
+}
+
+/// Stream of events from a streaming request
+///
+/// Events are parsed as chunks arrive. `error` events are surfaced as `Err`
+/// items, after which the stream ends.
+pub struct EventStream {
+    source: Box<dyn BodyStream>,
+    buffer: Vec<u8>,
+    pending: VecDeque<Result<StreamEvent>>,
+    recording: Option<Recording>,
+    done: bool,
+}
+
+/// Raw stream being written to a cassette
+#[derive(Debug)]
+struct Recording {
+    cassette: Arc<Cassette>,
+    request: RecordedRequest,
+    status: u16,
+    raw: Vec<u8>,
+}
+
+impl EventStream {
+    /// Stream events from the body of an HTTP response
+    pub fn from_response(response: HttpResponse) -> Self {
+        EventStream {
+            source: response.body,
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+            recording: None,
+            done: false,
+        }
+    }
+
+    /// Stream events from recorded SSE text
+    pub fn from_sse_text<T: AsRef<str>>(text: T) -> Self {
+        Self::from_response(HttpResponse::from_bytes(200, text.as_ref()))
+    }
+
+    /// Write the raw stream to a cassette once it ends
+    pub(crate) fn record_to(
+        mut self,
+        cassette: Arc<Cassette>,
+        request: RecordedRequest,
+        status: u16,
+    ) -> Self {
+        self.recording = Some(Recording {
+            cassette,
+            request,
+            status,
+            raw: Vec::new(),
+        });
+        self
+    }
+
+    /// Get the next event, or `None` once the stream has ended
+    pub async fn next(&mut self) -> Option<Result<StreamEvent>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                if event.is_err() {
+                    self.pending.clear();
+                    self.done = true;
+                }
+                return Some(event);
+            }
+            if self.done {
+                return None;
+            }
+            match self.source.next_chunk().await {
+                Ok(Some(chunk)) => {
+                    if let Some(recording) = &mut self.recording {
+                        recording.raw.extend_from_slice(&chunk);
+                    }
+                    self.buffer.extend_from_slice(&chunk);
+                    self.drain_lines(false);
+                }
+                Ok(None) => {
+                    self.done = true;
+                    self.drain_lines(true);
+                    if let Err(err) = self.finish_recording() {
+                        self.pending.push_back(Err(err));
+                    }
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+
+    /// Read the whole stream into a vector of events
+    pub async fn collect(mut self) -> Result<Vec<StreamEvent>> {
+        let mut events = Vec::new();
+        while let Some(event) = self.next().await {
+            events.push(event?);
+        }
+        Ok(events)
+    }
+
+    /// Feed every event into an accumulator and sinks while still yielding it
+    pub fn tee(self) -> TeeStream {
+        TeeStream {
+            inner: self,
+            accumulator: StreamAccumulator::new(),
+            sinks: Vec::new(),
+        }
+    }
+
+    /// Parse complete lines from the buffer (and the remainder at end of stream)
+    fn drain_lines(&mut self, end_of_stream: bool) {
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            self.parse_line(&line);
+        }
+        if end_of_stream && !self.buffer.is_empty() {
+            let line = std::mem::take(&mut self.buffer);
+            self.parse_line(&line);
+        }
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        match parse_sse_line(line.trim_end_matches(['\r', '\n'])) {
+            Ok(Some(StreamEvent::Error { error })) => {
+                self.pending.push_back(Err(ErrorResponse {
+                    type_name: "error".to_string(),
+                    error,
+                    request_id: None,
+                }
+                .into_error()));
+            }
+            Ok(Some(event)) => self.pending.push_back(Ok(event)),
+            Ok(None) => {}
+            Err(err) => self.pending.push_back(Err(err)),
+        }
+    }
+
+    fn finish_recording(&mut self) -> Result<()> {
+        if let Some(recording) = self.recording.take() {
+            recording.cassette.record(Interaction {
+                request: recording.request,
+                response: RecordedResponse {
+                    status: recording.status,
+                    body: String::from_utf8_lossy(&recording.raw).into_owned(),
+                },
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream")
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Receives a copy of each event of a [`TeeStream`]
+pub trait EventSink: MaybeSend {
+    /// Handle an event (errors are not forwarded to sinks)
+    fn on_event(&mut self, event: &StreamEvent);
+}
+
+impl<F: FnMut(&StreamEvent) + MaybeSend> EventSink for F {
+    fn on_event(&mut self, event: &StreamEvent) {
+        self(event)
+    }
+}
+
+/// Sink that keeps a copy of every event
+///
+/// Clones share the same buffer, so one clone can be passed to
+/// [`TeeStream::sink`] and the other read afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct EventRecorder {
+    events: Arc<Mutex<Vec<StreamEvent>>>,
+}
+
+impl EventRecorder {
+    /// Create an empty recorder
+    pub fn new() -> Self {
+        EventRecorder::default()
+    }
+
+    /// Get the events recorded so far
+    pub fn events(&self) -> Vec<StreamEvent> {
+        self.lock().clone()
+    }
+
+    /// Render the recorded events as SSE text (readable by [`EventStream::from_sse_text`])
+    pub fn to_sse_text(&self) -> Result<String> {
+        let mut text = String::new();
+        for event in self.lock().iter() {
+            let data = serde_json::to_value(event)?;
+            let event_type = data["type"].as_str().unwrap_or_default();
+            text.push_str(&format!("event: {}\ndata: {}\n\n", event_type, data));
+        }
+        Ok(text)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<StreamEvent>> {
+        self.events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl EventSink for EventRecorder {
+    fn on_event(&mut self, event: &StreamEvent) {
+        self.lock().push(event.clone());
+    }
+}
+
+/// Event stream that also feeds a [`StreamAccumulator`] and sinks
+///
+/// Created with [`EventStream::tee`].
+pub struct TeeStream {
+    inner: EventStream,
+    accumulator: StreamAccumulator,
+    sinks: Vec<Box<dyn EventSink>>,
+}
+
+impl TeeStream {
+    /// Add a sink that receives a copy of every event
+    pub fn sink<S: EventSink + 'static>(mut self, sink: S) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Get the next event, or `None` once the stream has ended
+    pub async fn next(&mut self) -> Option<Result<StreamEvent>> {
+        let event = self.inner.next().await?;
+        if let Ok(event) = &event {
+            for sink in &mut self.sinks {
+                sink.on_event(event);
+            }
+            self.accumulator.process_event(event.clone());
+        }
+        Some(event)
+    }
+
+    /// Get the accumulator fed so far
+    pub fn accumulator(&self) -> &StreamAccumulator {
+        &self.accumulator
+    }
+
+    /// Reassemble the [`Response`] from the events consumed so far
+    pub fn into_response(self) -> Result<Response> {
+        self.accumulator.into_response()
+    }
+
+    /// Consume the remaining events and return the complete [`Response`]
+    pub async fn finish(mut self) -> Result<Response> {
+        while let Some(event) = self.next().await {
+            event?;
+        }
+        self.into_response()
+    }
+}
+
+impl fmt::Debug for TeeStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TeeStream")
+            .field("inner", &self.inner)
+            .field("accumulator", &self.accumulator)
+            .field("sinks", &self.sinks.len())
+            .finish()
+    }
 }
 
 #[cfg(test)]
@@ -302,6 +745,20 @@ mod tests {
         assert!(matches!(event, StreamEvent::Ping));
     }
 
+    #[test]
+    fn test_parse_unknown_event() {
+        let line = r#"data: {"type":"message_teleport","destination":"mars"}"#;
+        let event = parse_sse_line(line).unwrap().unwrap();
+        assert!(matches!(event, StreamEvent::Unknown));
+
+        let line =
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"hologram"}}"#;
+        let event = parse_sse_line(line).unwrap().unwrap();
+        let mut accumulator = StreamAccumulator::new();
+        accumulator.process_event(event);
+        assert_eq!(accumulator.content_blocks[0].block_type(), "hologram");
+    }
+
     #[test]
     fn test_parse_done() {
         let line = "data: [DONE]";
@@ -323,6 +780,23 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_accumulator_citations() {
+        let lines = [
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"citations_delta","citation":{"type":"char_location","cited_text":"The sky is blue.","document_index":0,"document_title":"Notes","start_char_index":0,"end_char_index":16}}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"The sky is blue"}}"#,
+        ];
+        let mut acc = StreamAccumulator::new();
+        for line in lines {
+            acc.process_event(parse_sse_line(line).unwrap().unwrap());
+        }
+
+        let block = &acc.content_blocks[0];
+        assert_eq!(block.citations().len(), 1);
+        assert_eq!(block.citations()[0].source(), "Notes, chars 0-15");
+    }
+
     #[test]
     fn test_accumulator_text() {
         let mut acc = StreamAccumulator::new();
@@ -331,6 +805,7 @@ mod tests {
             index: 0,
             content_block: ContentBlock::Text {
                 text: String::new(),
+                citations: None,
                 cache_control: None,
             },
         });
@@ -362,6 +837,8 @@ mod tests {
             delta: MessageDelta {
                 stop_reason: Some("end_turn".to_string()),
                 stop_sequence: None,
+                container: None,
+                context_management: None,
             },
             usage: Usage::new(10, 5),
         });
@@ -369,4 +846,193 @@ mod tests {
         assert!(acc.is_complete());
         assert!(acc.usage.is_some());
     }
+
+    const SSE_TEXT: &str = "event: content_block_start\r
+data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\r
+\r
+event: content_block_delta
+data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}
+
+event: message_stop
+data: {\"type\":\"message_stop\"}";
+
+    #[tokio::test]
+    async fn test_event_stream_from_sse_text() {
+        let events = EventStream::from_sse_text(SSE_TEXT)
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], StreamEvent::ContentBlockStart { .. }));
+        assert!(matches!(events[2], StreamEvent::MessageStop));
+
+        let mut acc = StreamAccumulator::new();
+        for event in events {
+            acc.process_event(event);
+        }
+        assert_eq!(acc.get_text(), "Hi");
+    }
+
+    #[tokio::test]
+    async fn test_accumulator_into_response() {
+        let text = [
+            r#"data: {"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":25,"output_tokens":1}}}"#,
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Need weather"}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"sig"}}"#,
+            r#"data: {"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"Checking."}}"#,
+            r#"data: {"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_1","name":"get_weather","input":{}}}"#,
+            r#"data: {"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"{\"city\": "}}"#,
+            r#"data: {"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"\"Paris\"}"}}"#,
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"tool_use","stop_sequence":null,"container":{"id":"container_1","expires_at":"2025-05-23T21:13:31Z"},"context_management":{"applied_edits":[{"type":"clear_tool_uses_20250919","cleared_tool_uses":2,"cleared_input_tokens":1200}]}},"usage":{"output_tokens":42}}"#,
+            r#"data: {"type":"message_stop"}"#,
+        ]
+        .join("\n\n");
+
+        let mut acc = StreamAccumulator::new();
+        for event in EventStream::from_sse_text(text).collect().await.unwrap() {
+            acc.process_event(event);
+        }
+        let response = acc.into_response().unwrap();
+
+        assert_eq!(response.id, "msg_1");
+        assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+        assert_eq!(response.usage.input_tokens, 25);
+        assert_eq!(response.usage.output_tokens, 42);
+        assert_eq!(response.container.as_ref().unwrap().id, "container_1");
+        assert_eq!(
+            response
+                .context_management
+                .as_ref()
+                .unwrap()
+                .cleared_input_tokens(),
+            1200
+        );
+        assert_eq!(response.get_text(), "Checking.");
+        assert!(matches!(
+            &response.content[0],
+            ContentBlock::Thinking { thinking, signature: Some(sig) } if thinking == "Need weather" && sig == "sig"
+        ));
+        match &response.content[2] {
+            ContentBlock::ToolUse { input, .. } => assert_eq!(input["city"], "Paris"),
+            other => panic!("Expected ToolUse, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accumulator_stop_reasons() {
+        for (wire, expected) in [
+            ("stop_sequence", StopReason::StopSequence),
+            ("max_tokens", StopReason::MaxTokens),
+            ("pause_turn", StopReason::PauseTurn),
+            ("stopped_by_magic", StopReason::Unknown),
+        ] {
+            let text = [
+                r#"data: {"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":5,"output_tokens":1}}}"#.to_string(),
+                format!(
+                    r#"data: {{"type":"message_delta","delta":{{"stop_reason":"{}"}},"usage":{{"output_tokens":2}}}}"#,
+                    wire
+                ),
+            ]
+            .join("\n\n");
+            let mut acc = StreamAccumulator::new();
+            for event in EventStream::from_sse_text(text).collect().await.unwrap() {
+                acc.process_event(event);
+            }
+            assert_eq!(acc.into_response().unwrap().stop_reason, Some(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accumulator_server_tool_use() {
+        let text = [
+            r#"data: {"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":5,"output_tokens":1}}}"#,
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"server_tool_use","id":"srvtoolu_1","name":"web_search","input":{}}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"query\": \"rust\"}"}}"#,
+            r#"data: {"type":"content_block_start","index":1,"content_block":{"type":"web_search_tool_result","tool_use_id":"srvtoolu_1","content":[]}}"#,
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":2}}"#,
+        ]
+        .join("\n\n");
+
+        let mut acc = StreamAccumulator::new();
+        for event in EventStream::from_sse_text(text).collect().await.unwrap() {
+            acc.process_event(event);
+        }
+        let response = acc.into_response().unwrap();
+        match &response.content[0] {
+            ContentBlock::ServerToolUse { input, .. } => assert_eq!(input["query"], "rust"),
+            other => panic!("Expected ServerToolUse, got {:?}", other),
+        }
+        assert!(response.web_search_results().is_empty());
+    }
+
+    #[test]
+    fn test_into_response_requires_message_start() {
+        assert!(StreamAccumulator::new().into_response().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tee_feeds_accumulator_and_sinks() {
+        let text = [
+            r#"data: {"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-20250514","usage":{"input_tokens":5,"output_tokens":1}}}"#,
+            SSE_TEXT,
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":2}}"#,
+        ]
+        .join("\n\n");
+
+        let recorder = EventRecorder::new();
+        let deltas = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&deltas);
+        let mut stream = EventStream::from_sse_text(text)
+            .tee()
+            .sink(recorder.clone())
+            .sink(move |event: &StreamEvent| {
+                if matches!(event, StreamEvent::ContentBlockDelta { .. }) {
+                    counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                }
+            });
+
+        let mut live = String::new();
+        while let Some(event) = stream.next().await {
+            if let StreamEvent::ContentBlockDelta {
+                delta: Delta::TextDelta { text },
+                ..
+            } = event.unwrap()
+            {
+                live.push_str(&text);
+            }
+        }
+        assert_eq!(live, "Hi");
+        assert_eq!(deltas.load(std::sync::atomic::Ordering::Relaxed), 1);
+        assert_eq!(stream.accumulator().get_text(), "Hi");
+
+        let response = stream.into_response().unwrap();
+        assert_eq!(response.get_text(), "Hi");
+        assert_eq!(response.usage.output_tokens, 2);
+
+        // The recording replays to the same response
+        assert_eq!(recorder.events().len(), 5);
+        let replayed = EventStream::from_sse_text(recorder.to_sse_text().unwrap())
+            .tee()
+            .finish()
+            .await
+            .unwrap();
+        assert_eq!(replayed.get_text(), "Hi");
+        assert_eq!(replayed.stop_reason, Some(StopReason::EndTurn));
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_error_event() {
+        let text = "data: {\"type\":\"ping\"}\n\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\ndata: {\"type\":\"message_stop\"}\n";
+        let mut stream = EventStream::from_sse_text(text);
+        assert!(matches!(stream.next().await, Some(Ok(StreamEvent::Ping))));
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(
+                crate::common::errors::AnthropicToolError::OverloadedError(_)
+            ))
+        ));
+        assert!(stream.next().await.is_none());
+    }
 }
@@ -0,0 +1,181 @@
+//! Models API.
+//!
+//! Look up model details and resolve aliases such as `claude-sonnet-4-0` to
+//! the dated snapshot they currently point to, so services can log exactly
+//! which model they are talking to:
+//!
+//! - [`Models`] - Client for the `/v1/models` endpoints
+//! - [`ModelInfo`] - Model ID, display name, and release date
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::models::Models;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let models = Models::new();
+//! let snapshot = models.resolve("claude-sonnet-4-0").await?;
+//! println!("claude-sonnet-4-0 -> {}", snapshot);
+//!
+//! let info = models.get(&snapshot).await?;
+//! println!("{} released {}", info.display_name, info.created_at);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::Result;
+use crate::common::pagination::{ListParams, Page, encode};
+use crate::messages::request::Messages;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Path of the models endpoint below the base URL
+pub(crate) const MODELS_PATH: &str = "/v1/models";
+
+/// Details of a model
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// Model ID (the dated snapshot, even when looked up by alias)
+    pub id: String,
+
+    /// Object type (always "model")
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    /// Human-readable name
+    pub display_name: String,
+
+    /// Release time (RFC 3339)
+    pub created_at: String,
+}
+
+/// Client for the Models API
+///
+/// Uses the API key, base URL, transport, and other connection settings of a
+/// [`Messages`] client. Alias resolutions are cached for the lifetime of the
+/// client and shared by its clones.
+#[derive(Debug, Clone)]
+pub struct Models {
+    client: Messages,
+    resolved: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl Default for Models {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Models {
+    /// Create a client, loading the API key from `ANTHROPIC_API_KEY`
+    pub fn new() -> Self {
+        Models::from_client(Messages::new())
+    }
+
+    /// Create a client with an explicit API key
+    pub fn with_api_key<T: AsRef<str>>(api_key: T) -> Self {
+        Models::from_client(Messages::with_api_key(api_key))
+    }
+
+    /// Use the connection settings of a configured Messages client
+    pub fn from_client(client: Messages) -> Self {
+        Models {
+            client,
+            resolved: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Get the underlying Messages client
+    pub fn client(&self) -> &Messages {
+        &self.client
+    }
+
+    /// Get the details of a model by ID or alias
+    pub async fn get(&self, model: &str) -> Result<ModelInfo> {
+        let path = format!("{}/{}", MODELS_PATH, encode(model));
+        let info: ModelInfo = self.client.send_json("GET", &path, None::<&()>).await?;
+        self.remember(model, &info.id);
+        Ok(info)
+    }
+
+    /// List available models, most recent first
+    pub async fn list(&self, params: &ListParams) -> Result<Page<ModelInfo>> {
+        let path = format!("{}{}", MODELS_PATH, params.query());
+        self.client.send_json("GET", &path, None::<&()>).await
+    }
+
+    /// Resolve a model alias to its dated snapshot ID
+    ///
+    /// Concrete IDs resolve to themselves. The result is cached, so only the
+    /// first call per alias hits the API.
+    pub async fn resolve(&self, model: &str) -> Result<String> {
+        if let Some(id) = self.cached(model) {
+            return Ok(id);
+        }
+        Ok(self.get(model).await?.id)
+    }
+
+    /// Get a cached resolution without calling the API
+    pub fn cached(&self, model: &str) -> Option<String> {
+        self.resolved
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(model)
+            .cloned()
+    }
+
+    fn remember(&self, model: &str, id: &str) {
+        self.resolved
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(model.to_string(), id.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::future::{BoxFuture, boxed};
+    use crate::common::transport::{HttpRequest, HttpResponse, HttpTransport};
+
+    #[derive(Debug, Default)]
+    struct RecordingTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl HttpTransport for RecordingTransport {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            let body = if request.url.ends_with("/v1/models") {
+                r#"{"data":[{"id":"claude-sonnet-4-20250514","type":"model","display_name":"Claude Sonnet 4","created_at":"2025-05-22T00:00:00Z"}],"has_more":false}"#
+            } else {
+                r#"{"id":"claude-sonnet-4-20250514","type":"model","display_name":"Claude Sonnet 4","created_at":"2025-05-22T00:00:00Z"}"#
+            };
+            self.requests.lock().unwrap().push(request);
+            boxed(async move { Ok(HttpResponse::from_bytes(200, body)) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_alias_is_cached() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mut client = Messages::with_api_key("test_key");
+        client.transport(Arc::clone(&transport));
+        let models = Models::from_client(client);
+
+        assert!(models.cached("claude-sonnet-4-0").is_none());
+        for _ in 0..3 {
+            assert_eq!(
+                models.resolve("claude-sonnet-4-0").await.unwrap(),
+                "claude-sonnet-4-20250514"
+            );
+        }
+        let page = models.list(&ListParams::new()).await.unwrap();
+        assert_eq!(page.data[0].display_name, "Claude Sonnet 4");
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "GET");
+        assert!(requests[0].url.ends_with("/v1/models/claude-sonnet-4-0"));
+    }
+}
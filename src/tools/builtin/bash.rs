@@ -0,0 +1,264 @@
+//! Local executor for the Anthropic-defined bash tool.
+//!
+//! [`BashExecutor`] answers the `tool_use` blocks of the `bash_20250124` tool
+//! ([`BashTool`]). Registered in a
+//! [`ToolRegistry`](crate::tools::registry::ToolRegistry), it is sent with its
+//! versioned `type`, so it plugs into
+//! [`Messages::run_tool_loop`](crate::messages::request::Messages::run_tool_loop)
+//! like any other tool.
+//!
+//! Each command runs in a fresh `bash -c` with a cleared environment: only the
+//! variables on the allowlist are passed through, and state such as `cd` or
+//! exported variables does not carry over to the next command. A `restart`
+//! request therefore only acknowledges.
+//!
+//! **Warning:** the commands are chosen by the model and run with the
+//! privileges of the current process. Only enable this inside a sandbox
+//! (container, VM, or throwaway user) where any command is acceptable.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::tools::builtin::bash::BashExecutor;
+//! use anthropic_tools::tools::registry::ToolRegistry;
+//!
+//! # async fn example() -> Result<()> {
+//! let mut registry = ToolRegistry::new();
+//! registry.register(
+//!     BashExecutor::new()
+//!         .working_dir("/sandbox")
+//!         .allow_env("CARGO_HOME"),
+//! );
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .user("How many Rust files are in this project?");
+//! let run = client.run_tool_loop(&registry).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{DEFAULT_MAX_OUTPUT_BYTES, truncate_output};
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::future::{BoxFuture, boxed};
+use crate::common::runtime;
+use crate::common::server_tool::{BASH_NAME, BashInput, BashTool};
+use crate::common::tool::{Tool, parse_input};
+use crate::tools::handler::{ToolHandler, ToolOutput};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Default time limit for a single command
+pub const DEFAULT_BASH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Environment variables passed to commands by default
+pub const DEFAULT_ENV_ALLOWLIST: [&str; 3] = ["PATH", "HOME", "LANG"];
+
+/// Run the commands of the bash tool (sandboxed environments only)
+#[derive(Debug, Clone)]
+pub struct BashExecutor {
+    working_dir: Option<PathBuf>,
+    env_allowlist: Vec<String>,
+    timeout: Duration,
+    max_bytes: usize,
+}
+
+impl BashExecutor {
+    /// Create a bash executor
+    ///
+    /// Logs a warning, since the executor runs arbitrary commands.
+    pub fn new() -> Self {
+        tracing::warn!("BashExecutor executes arbitrary commands; only use it inside a sandbox");
+        BashExecutor {
+            working_dir: None,
+            env_allowlist: DEFAULT_ENV_ALLOWLIST
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            timeout: DEFAULT_BASH_TIMEOUT,
+            max_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Set the directory commands run in
+    pub fn working_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.working_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Pass an environment variable of the current process through to commands
+    pub fn allow_env<S: AsRef<str>>(mut self, name: S) -> Self {
+        let name = name.as_ref().to_string();
+        if !self.env_allowlist.contains(&name) {
+            self.env_allowlist.push(name);
+        }
+        self
+    }
+
+    /// Replace the environment variables passed through to commands
+    ///
+    /// An empty list runs commands with an empty environment.
+    pub fn env_allowlist<S: AsRef<str>>(mut self, names: Vec<S>) -> Self {
+        self.env_allowlist = names.iter().map(|n| n.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Set the time limit for a single command
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the maximum number of bytes returned to the model
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Get a tool definition describing the input
+    ///
+    /// The API receives [`BashTool`] instead (see [`ToolHandler::tool_value`]);
+    /// this schema documents the input for other uses.
+    pub fn definition(&self) -> Tool {
+        let mut tool = Tool::new(BASH_NAME);
+        tool.description("Run a bash command and return its output.")
+            .add_string_property("command", Some("Command to run"), false)
+            .add_boolean_property("restart", Some("Restart the bash session"), false);
+        tool
+    }
+
+    /// Run the command of a bash tool input
+    ///
+    /// The output is stdout followed by stderr, with the exit status appended
+    /// when it is not zero.
+    pub async fn execute(&self, input: Value) -> Result<String> {
+        let input: BashInput = parse_input(BASH_NAME, &input)?;
+        if input.restart {
+            return Ok("bash session restarted".to_string());
+        }
+        let command = input.command.ok_or_else(|| {
+            AnthropicToolError::InvalidParameter("bash: missing command".to_string())
+        })?;
+
+        let mut cmd = Command::new("bash");
+        cmd.arg("-c")
+            .arg(&command)
+            .env_clear()
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        for name in &self.env_allowlist {
+            if let Some(value) = std::env::var_os(name) {
+                cmd.env(name, value);
+            }
+        }
+        if let Some(dir) = &self.working_dir {
+            cmd.current_dir(dir);
+        }
+
+        let output = runtime::timeout(self.timeout, cmd.output())
+            .await
+            .ok_or_else(|| {
+                AnthropicToolError::InvalidParameter(format!(
+                    "command timed out after {:?}",
+                    self.timeout
+                ))
+            })??;
+
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        match output.status.code() {
+            Some(0) => {}
+            Some(code) => text.push_str(&format!("\n[exit status: {}]", code)),
+            None => text.push_str("\n[terminated by signal]"),
+        }
+        Ok(truncate_output(text, self.max_bytes))
+    }
+}
+
+impl Default for BashExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToolHandler for BashExecutor {
+    fn name(&self) -> &str {
+        BASH_NAME
+    }
+
+    fn definition(&self) -> Tool {
+        BashExecutor::definition(self)
+    }
+
+    fn tool_value(&self) -> Value {
+        BashTool::new().to_value()
+    }
+
+    fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+        boxed(async move {
+            BashExecutor::execute(self, input)
+                .await
+                .map(ToolOutput::text)
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::tools::registry::ToolRegistry;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_run_command() {
+        let tool = BashExecutor::new();
+        let output = tool.execute(json!({"command": "echo hi"})).await.unwrap();
+        assert_eq!(output, "hi\n");
+
+        let output = tool
+            .execute(json!({"command": "echo oops >&2; exit 3"}))
+            .await
+            .unwrap();
+        assert_eq!(output, "oops\n\n[exit status: 3]");
+
+        let output = tool.execute(json!({"restart": true})).await.unwrap();
+        assert_eq!(output, "bash session restarted");
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_env_allowlist_and_limits() {
+        let tool = BashExecutor::new().env_allowlist(vec!["PATH"]);
+        let output = tool
+            .execute(json!({"command": "echo \"[$HOME]\""}))
+            .await
+            .unwrap();
+        assert_eq!(output, "[]\n");
+
+        let tool = BashExecutor::new().max_bytes(4);
+        let output = tool
+            .execute(json!({"command": "echo abcdefgh"}))
+            .await
+            .unwrap();
+        assert_eq!(output, "abcd\n[output truncated]");
+
+        let tool = BashExecutor::new().timeout(Duration::from_millis(50));
+        assert!(tool.execute(json!({"command": "sleep 5"})).await.is_err());
+    }
+
+    #[test]
+    fn test_registry_sends_tool_type() {
+        let mut registry = ToolRegistry::new();
+        registry.register(BashExecutor::new());
+        assert_eq!(
+            registry.tool_values(),
+            vec![json!({"type": "bash_20250124", "name": "bash"})]
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Local filesystem read tool rooted at a directory.
+//!
+//! [`FileReadTool`] lets the model read text files below a fixed root
+//! directory. Paths are resolved relative to the root and canonicalized, so
+//! `..` segments and symlinks cannot escape it.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::tools::builtin::fs::FileReadTool;
+//! use serde_json::json;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let tool = FileReadTool::new("./docs");
+//! let text = tool.execute(json!({"path": "guide.md"})).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{DEFAULT_MAX_OUTPUT_BYTES, required_str, truncate_output};
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::tool::Tool;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncReadExt;
+
+/// Tool name used in the definition
+pub const FILE_READ_TOOL_NAME: &str = "read_file";
+
+/// Read text files below a root directory
+#[derive(Debug, Clone)]
+pub struct FileReadTool {
+    root: PathBuf,
+    max_bytes: usize,
+}
+
+impl FileReadTool {
+    /// Create a tool that can read files below `root`
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        FileReadTool {
+            root: root.as_ref().to_path_buf(),
+            max_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Set the maximum number of bytes returned to the model
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Get the tool definition to send to the API
+    pub fn definition(&self) -> Tool {
+        let mut tool = Tool::new(FILE_READ_TOOL_NAME);
+        tool.description("Read a UTF-8 text file. Paths are relative to the project root.")
+            .add_string_property("path", Some("Relative path of the file to read"), true);
+        tool
+    }
+
+    /// Resolve a relative path, rejecting anything outside the root
+    pub fn resolve<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf> {
+        let path = path.as_ref();
+        if path.is_absolute() {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "path must be relative to the root: {}",
+                path.display()
+            )));
+        }
+        let root = self.root.canonicalize()?;
+        let resolved = root.join(path).canonicalize()?;
+        if !resolved.starts_with(&root) {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "path escapes the root directory: {}",
+                path.display()
+            )));
+        }
+        Ok(resolved)
+    }
+
+    /// Read the file named by the `path` field of the input
+    ///
+    /// At most `max_bytes` (plus a few bytes to detect truncation) are read.
+    pub async fn execute(&self, input: Value) -> Result<String> {
+        let path = self.resolve(required_str(&input, "path")?)?;
+        let file = tokio::fs::File::open(&path).await?;
+        let mut bytes = Vec::new();
+        file.take(self.max_bytes as u64 + 4)
+            .read_to_end(&mut bytes)
+            .await?;
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+        Ok(truncate_output(text, self.max_bytes))
+    }
+}
+
+impl_tool_handler!(FileReadTool, FILE_READ_TOOL_NAME);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "anthropic-tools-fs-{}-{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join("sub/notes.txt"), "hello notes").unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn test_read_file() {
+        let root = temp_root("read");
+        let tool = FileReadTool::new(&root);
+        let text = tool
+            .execute(json!({"path": "sub/notes.txt"}))
+            .await
+            .unwrap();
+        assert_eq!(text, "hello notes");
+
+        std::fs::write(root.join("big.txt"), "x".repeat(1000)).unwrap();
+        let text = tool
+            .max_bytes(10)
+            .execute(json!({"path": "big.txt"}))
+            .await
+            .unwrap();
+        assert_eq!(text, format!("{}\n[output truncated]", "x".repeat(10)));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_escape() {
+        let root = temp_root("escape");
+        let tool = FileReadTool::new(root.join("sub"));
+        assert!(
+            tool.execute(json!({"path": "../sub/notes.txt"}))
+                .await
+                .is_ok()
+        );
+        std::fs::write(root.join("secret.txt"), "secret").unwrap();
+        assert!(
+            tool.execute(json!({"path": "../secret.txt"}))
+                .await
+                .is_err()
+        );
+        assert!(tool.execute(json!({"path": "/etc/passwd"})).await.is_err());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_definition() {
+        let tool = FileReadTool::new(".").definition();
+        assert_eq!(tool.name, FILE_READ_TOOL_NAME);
+        assert!(
+            tool.input_schema
+                .required
+                .unwrap()
+                .contains(&"path".to_string())
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! HTTP fetch tool restricted to a domain allowlist.
+//!
+//! [`HttpFetchTool`] performs `GET` requests on behalf of the model. Only
+//! `http`/`https` URLs whose host is on the allowlist (or a subdomain of an
+//! allowed domain) are fetched; everything else is rejected before any network
+//! access happens. Redirects are checked against the allowlist at every hop
+//! (on `wasm32`, where `fetch` follows redirects itself, the final URL is
+//! checked instead), and at most `max_bytes` of the body are read.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::tools::builtin::http::HttpFetchTool;
+//! use serde_json::json;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let tool = HttpFetchTool::new(["docs.rs", "example.com"]);
+//! let page = tool.execute(json!({"url": "https://docs.rs/serde"})).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{DEFAULT_MAX_OUTPUT_BYTES, required_str, truncate_output};
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::tool::Tool;
+use serde_json::Value;
+
+/// Tool name used in the definition
+pub const HTTP_FETCH_TOOL_NAME: &str = "http_fetch";
+
+/// Maximum number of redirects followed for one fetch
+pub const MAX_REDIRECTS: usize = 10;
+
+/// Fetch web pages from an allowlist of domains
+#[derive(Debug, Clone)]
+pub struct HttpFetchTool {
+    allowed_domains: Vec<String>,
+    max_bytes: usize,
+    client: request::Client,
+}
+
+impl HttpFetchTool {
+    /// Create a tool that may fetch from the given domains (and their subdomains)
+    pub fn new<I, S>(allowed_domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let allowed_domains: Vec<String> = allowed_domains
+            .into_iter()
+            .map(|domain| domain.as_ref().trim_start_matches('.').to_lowercase())
+            .collect();
+        HttpFetchTool {
+            client: redirect_checking_client(allowed_domains.clone()),
+            allowed_domains,
+            max_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Set the maximum number of bytes returned to the model
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Get the tool definition to send to the API
+    pub fn definition(&self) -> Tool {
+        let mut tool = Tool::new(HTTP_FETCH_TOOL_NAME);
+        tool.description(format!(
+            "Fetch a web page with HTTP GET. Allowed domains: {}",
+            self.allowed_domains.join(", ")
+        ))
+        .add_string_property("url", Some("Absolute http(s) URL to fetch"), true);
+        tool
+    }
+
+    /// Check a URL against the scheme and domain allowlist
+    pub fn check_url(&self, url: &str) -> Result<request::Url> {
+        let parsed = request::Url::parse(url)
+            .map_err(|e| AnthropicToolError::InvalidParameter(format!("invalid URL: {}", e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(AnthropicToolError::InvalidParameter(format!(
+                "unsupported URL scheme: {}",
+                parsed.scheme()
+            )));
+        }
+        if !is_allowed(&self.allowed_domains, &parsed) {
+            return Err(AnthropicToolError::PermissionError(format!(
+                "domain is not on the allowlist: {}",
+                parsed.host_str().unwrap_or_default()
+            )));
+        }
+        Ok(parsed)
+    }
+
+    /// Fetch the URL named by the `url` field of the input
+    ///
+    /// Fails with `PermissionError` if a redirect leads off the allowlist.
+    pub async fn execute(&self, input: Value) -> Result<String> {
+        let url = self.check_url(required_str(&input, "url")?)?;
+        let mut response = self.client.get(url).send().await.map_err(|e| {
+            if e.is_redirect() {
+                let reason = std::error::Error::source(&e).map_or(e.to_string(), |s| s.to_string());
+                AnthropicToolError::PermissionError(format!("redirect blocked: {}", reason))
+            } else {
+                e.into()
+            }
+        })?;
+        self.check_url(response.url().as_str())?;
+        let status = response.status();
+
+        // Stop reading once the output is known to be truncated
+        let mut body = Vec::new();
+        while body.len() <= self.max_bytes
+            && let Some(chunk) = response.chunk().await?
+        {
+            body.extend_from_slice(&chunk);
+        }
+        body.truncate(self.max_bytes + 4);
+        Ok(truncate_output(
+            format!("HTTP {}\n\n{}", status, String::from_utf8_lossy(&body)),
+            self.max_bytes,
+        ))
+    }
+}
+
+/// Whether the host of a URL is an allowed domain or one of its subdomains
+fn is_allowed(allowed_domains: &[String], url: &request::Url) -> bool {
+    let host = url.host_str().unwrap_or_default().to_lowercase();
+    allowed_domains
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+}
+
+/// Build a client that follows redirects only within the allowlist
+#[cfg(not(target_arch = "wasm32"))]
+fn redirect_checking_client(allowed_domains: Vec<String>) -> request::Client {
+    let policy = request::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            attempt.error("too many redirects")
+        } else if is_allowed(&allowed_domains, attempt.url()) {
+            attempt.follow()
+        } else {
+            let message = format!(
+                "domain is not on the allowlist: {}",
+                attempt.url().host_str().unwrap_or_default()
+            );
+            attempt.error(message)
+        }
+    });
+    request::Client::builder()
+        .redirect(policy)
+        .build()
+        .expect("HTTP client builds with only a redirect policy set")
+}
+
+/// `fetch` follows redirects itself; the final URL is checked after the fact
+#[cfg(target_arch = "wasm32")]
+fn redirect_checking_client(_allowed_domains: Vec<String>) -> request::Client {
+    request::Client::new()
+}
+
+impl_tool_handler!(HttpFetchTool, HTTP_FETCH_TOOL_NAME);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_allowlist() {
+        let tool = HttpFetchTool::new(["example.com"]);
+        assert!(tool.check_url("https://example.com/a").is_ok());
+        assert!(tool.check_url("https://docs.example.com/a").is_ok());
+        assert!(tool.check_url("https://notexample.com/a").is_err());
+        assert!(tool.check_url("https://example.com.evil.io/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_scheme() {
+        let tool = HttpFetchTool::new(["example.com"]);
+        assert!(tool.check_url("file:///etc/passwd").is_err());
+        assert!(tool.check_url("not a url").is_err());
+    }
+
+    /// Serve one canned HTTP response on a local port
+    async fn serve_once(response: String) -> u16 {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_rejects_redirect_off_allowlist() {
+        let port = serve_once(
+            "HTTP/1.1 302 Found\r\nLocation: http://localhost/secret\r\nContent-Length: 0\r\n\r\n"
+                .to_string(),
+        )
+        .await;
+        let tool = HttpFetchTool::new(["127.0.0.1"]);
+        let result = tool
+            .execute(json!({"url": format!("http://127.0.0.1:{}/", port)}))
+            .await;
+        match result {
+            Err(AnthropicToolError::PermissionError(message)) => {
+                assert!(message.contains("localhost"), "{}", message)
+            }
+            other => panic!("Expected PermissionError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reads_at_most_max_bytes() {
+        let body = "x".repeat(64 * 1024);
+        let port = serve_once(format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ))
+        .await;
+        let tool = HttpFetchTool::new(["127.0.0.1"]).max_bytes(20);
+        let output = tool
+            .execute(json!({"url": format!("http://127.0.0.1:{}/", port)}))
+            .await
+            .unwrap();
+        assert_eq!(output, "HTTP 200 OK\n\nxxxxxxx\n[output truncated]");
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_before_network() {
+        let tool = HttpFetchTool::new(["example.com"]);
+        let result = tool.execute(json!({"url": "https://blocked.io"})).await;
+        assert!(matches!(
+            result,
+            Err(AnthropicToolError::PermissionError(_))
+        ));
+    }
+}
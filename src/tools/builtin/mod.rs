@@ -0,0 +1,175 @@
+//! Ready-made tools for common infrastructure.
+//!
+//! Each tool exposes a `definition()` returning the [`Tool`](crate::common::tool::Tool)
+//! schema to send to the API and an async `execute(input)` that runs the tool
+//! for a `tool_use` input and returns the text for the `tool_result`. They
+//! also implement [`ToolHandler`](crate::tools::handler::ToolHandler).
+//! All tools are opt-in via cargo features:
+//!
+//! | Tool | Feature | Description |
+//! |------|---------|-------------|
+//! | `HttpFetchTool` | `tool-http` | HTTP GET restricted to a domain allowlist |
+//! | `FileReadTool` | `tool-fs` | Read files below a root directory |
+//! | `ShellTool` | `tool-shell` | Run shell commands (**sandbox only**) |
+//! | `BashExecutor` | `tool-bash` | Run the commands of the `bash_20250124` tool (**sandbox only**) |
+//! | `TextEditorExecutor` | `tool-text-editor` | View and edit files below a root directory for the `text_editor_20250728` tool |
+//! | `SqlQueryTool` | `tool-sql` | Run read-only SQL queries via `sqlx` |
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::tools::builtin::fs::FileReadTool;
+//!
+//! let reader = FileReadTool::new("./docs");
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .tools(vec![reader.definition().to_value()])
+//!     .user("Summarize README.md");
+//!
+//! let response = client.post().await?;
+//! if let Some(ContentBlock::ToolUse { id, input, .. }) = response.get_tool_uses().first() {
+//!     let output = reader.execute(input.clone()).await?;
+//!     client.tool_result(id.as_str(), output.as_str());
+//! }
+//! ```
+
+/// Implement [`ToolHandler`](crate::tools::handler::ToolHandler) for a tool
+/// with inherent `definition()` and `execute()` methods
+#[cfg(any(
+    feature = "tool-fs",
+    feature = "tool-http",
+    feature = "tool-shell",
+    feature = "tool-sql"
+))]
+macro_rules! impl_tool_handler {
+    ($tool:ty, $name:expr) => {
+        impl crate::tools::handler::ToolHandler for $tool {
+            fn name(&self) -> &str {
+                $name
+            }
+
+            fn definition(&self) -> crate::common::tool::Tool {
+                <$tool>::definition(self)
+            }
+
+            fn execute(
+                &self,
+                input: serde_json::Value,
+            ) -> crate::common::future::BoxFuture<
+                '_,
+                crate::common::errors::Result<crate::tools::handler::ToolOutput>,
+            > {
+                crate::common::future::boxed(async move {
+                    <$tool>::execute(self, input)
+                        .await
+                        .map(crate::tools::handler::ToolOutput::text)
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "tool-bash")]
+pub mod bash;
+#[cfg(feature = "tool-fs")]
+pub mod fs;
+#[cfg(feature = "tool-http")]
+pub mod http;
+#[cfg(feature = "tool-shell")]
+pub mod shell;
+#[cfg(feature = "tool-sql")]
+pub mod sql;
+#[cfg(feature = "tool-text-editor")]
+pub mod text_editor;
+
+#[cfg(any(
+    feature = "tool-fs",
+    feature = "tool-http",
+    feature = "tool-shell",
+    feature = "tool-sql"
+))]
+use crate::common::errors::{AnthropicToolError, Result};
+#[cfg(any(
+    feature = "tool-fs",
+    feature = "tool-http",
+    feature = "tool-shell",
+    feature = "tool-sql"
+))]
+use serde_json::Value;
+
+/// Default cap on the amount of output returned to the model
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 100 * 1024;
+
+/// Read a required string field from a tool input
+#[cfg(any(
+    feature = "tool-fs",
+    feature = "tool-http",
+    feature = "tool-shell",
+    feature = "tool-sql"
+))]
+pub(crate) fn required_str<'a>(input: &'a Value, field: &str) -> Result<&'a str> {
+    input.get(field).and_then(Value::as_str).ok_or_else(|| {
+        AnthropicToolError::InvalidParameter(format!("missing required string field: {}", field))
+    })
+}
+
+/// Truncate output to at most `max_bytes`, respecting UTF-8 boundaries
+#[cfg(any(
+    feature = "tool-bash",
+    feature = "tool-fs",
+    feature = "tool-http",
+    feature = "tool-shell",
+    feature = "tool-sql",
+    feature = "tool-text-editor"
+))]
+pub(crate) fn truncate_output(mut output: String, max_bytes: usize) -> String {
+    if output.len() <= max_bytes {
+        return output;
+    }
+    let mut end = max_bytes;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    output.truncate(end);
+    output.push_str("\n[output truncated]");
+    output
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "tool-bash",
+        feature = "tool-fs",
+        feature = "tool-http",
+        feature = "tool-shell",
+        feature = "tool-sql",
+        feature = "tool-text-editor"
+    )
+))]
+mod tests {
+    use super::*;
+
+    #[cfg(any(
+        feature = "tool-fs",
+        feature = "tool-http",
+        feature = "tool-shell",
+        feature = "tool-sql"
+    ))]
+    #[test]
+    fn test_required_str() {
+        let input = serde_json::json!({"path": "a.txt", "count": 3});
+        assert_eq!(required_str(&input, "path").unwrap(), "a.txt");
+        assert!(required_str(&input, "count").is_err());
+        assert!(required_str(&input, "missing").is_err());
+    }
+
+    #[test]
+    fn test_truncate_output() {
+        assert_eq!(truncate_output("short".to_string(), 10), "short");
+        let truncated = truncate_output("héllo".to_string(), 2);
+        assert_eq!(truncated, "h\n[output truncated]");
+    }
+}
@@ -0,0 +1,161 @@
+//! Shell command tool.
+//!
+//! **Warning:** [`ShellTool`] runs arbitrary commands chosen by the model with
+//! the privileges of the current process. Only enable it inside a sandbox
+//! (container, VM, or throwaway user) where any command is acceptable.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::tools::builtin::shell::ShellTool;
+//! use serde_json::json;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let tool = ShellTool::new()
+//!     .working_dir("/sandbox")
+//!     .timeout(Duration::from_secs(10));
+//! let output = tool.execute(json!({"command": "ls -la"})).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{DEFAULT_MAX_OUTPUT_BYTES, required_str, truncate_output};
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::runtime;
+use crate::common::tool::Tool;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Tool name used in the definition
+pub const SHELL_TOOL_NAME: &str = "shell";
+
+/// Default time limit for a single command
+pub const DEFAULT_SHELL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Run shell commands (sandboxed environments only)
+#[derive(Debug, Clone)]
+pub struct ShellTool {
+    working_dir: Option<PathBuf>,
+    timeout: Duration,
+    max_bytes: usize,
+}
+
+impl ShellTool {
+    /// Create a shell tool
+    ///
+    /// Logs a warning, since the tool executes arbitrary commands.
+    pub fn new() -> Self {
+        tracing::warn!("ShellTool executes arbitrary commands; only use it inside a sandbox");
+        ShellTool {
+            working_dir: None,
+            timeout: DEFAULT_SHELL_TIMEOUT,
+            max_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Set the directory commands run in
+    pub fn working_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.working_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the time limit for a single command
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the maximum number of bytes returned to the model
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Get the tool definition to send to the API
+    pub fn definition(&self) -> Tool {
+        let mut tool = Tool::new(SHELL_TOOL_NAME);
+        tool.description("Run a shell command and return its exit status, stdout, and stderr.")
+            .add_string_property("command", Some("Command line to run with `sh -c`"), true);
+        tool
+    }
+
+    /// Run the command named by the `command` field of the input
+    pub async fn execute(&self, input: Value) -> Result<String> {
+        let command = required_str(&input, "command")?;
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        if let Some(dir) = &self.working_dir {
+            cmd.current_dir(dir);
+        }
+
+        let output = runtime::timeout(self.timeout, cmd.output())
+            .await
+            .ok_or_else(|| {
+                AnthropicToolError::InvalidParameter(format!(
+                    "command timed out after {:?}",
+                    self.timeout
+                ))
+            })??;
+
+        let status = match output.status.code() {
+            Some(code) => code.to_string(),
+            None => "terminated by signal".to_string(),
+        };
+        let text = format!(
+            "exit status: {}\n\nstdout:\n{}\nstderr:\n{}",
+            status,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(truncate_output(text, self.max_bytes))
+    }
+}
+
+impl Default for ShellTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_tool_handler!(ShellTool, SHELL_TOOL_NAME);
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_echo() {
+        let tool = ShellTool::new();
+        let output = tool.execute(json!({"command": "echo hi"})).await.unwrap();
+        assert!(output.starts_with("exit status: 0"));
+        assert!(output.contains("stdout:\nhi\n"));
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let tool = ShellTool::new().timeout(Duration::from_millis(50));
+        assert!(tool.execute(json!({"command": "sleep 5"})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stdin_is_empty() {
+        let tool = ShellTool::new().timeout(Duration::from_secs(5));
+        let output = tool.execute(json!({"command": "cat"})).await.unwrap();
+        assert!(output.starts_with("exit status: 0"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_command() {
+        let tool = ShellTool::new();
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Read-only SQL query tool backed by `sqlx`.
+//!
+//! [`SqlQueryTool`] runs a single `SELECT` (or `WITH ... SELECT`) statement
+//! against a connection pool and returns the rows as a JSON array. The query
+//! runs in a read-only transaction (`START TRANSACTION READ ONLY` on
+//! PostgreSQL and MySQL, `PRAGMA query_only` on SQLite) that is rolled back
+//! afterwards, so the database itself rejects writes.
+//!
+//! Statements that do not start with `SELECT` or `WITH` are refused up front,
+//! but only to give the model a clear error; that check is not a safeguard.
+//! Read-only transactions do not stop everything either (e.g. PostgreSQL
+//! sequences or functions with side effects), so connect with a read-only
+//! database user when exposing production data.
+//!
+//! Supported databases are SQLite, PostgreSQL, and MySQL via
+//! [`sqlx::AnyPool`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::tools::builtin::sql::SqlQueryTool;
+//! use serde_json::json;
+//!
+//! # async fn example() -> anthropic_tools::Result<()> {
+//! let tool = SqlQueryTool::connect("sqlite://app.db").await?;
+//! let rows = tool.execute(json!({"query": "SELECT id, name FROM users LIMIT 5"})).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{DEFAULT_MAX_OUTPUT_BYTES, required_str, truncate_output};
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::tool::Tool;
+use serde_json::{Map, Value};
+use sqlx::any::AnyRow;
+use sqlx::pool::PoolConnection;
+use sqlx::{Any, AnyConnection, AnyPool, Column, Connection, Row};
+
+/// Tool name used in the definition
+pub const SQL_QUERY_TOOL_NAME: &str = "sql_query";
+
+/// Run read-only SQL queries
+#[derive(Debug, Clone)]
+pub struct SqlQueryTool {
+    pool: AnyPool,
+    description: Option<String>,
+    max_bytes: usize,
+}
+
+impl SqlQueryTool {
+    /// Create a tool from an existing pool
+    pub fn new(pool: AnyPool) -> Self {
+        SqlQueryTool {
+            pool,
+            description: None,
+            max_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Connect to a database URL (`sqlite:`, `postgres:`, or `mysql:`)
+    pub async fn connect<S: AsRef<str>>(url: S) -> Result<Self> {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPool::connect(url.as_ref()).await.map_err(sql_error)?;
+        Ok(Self::new(pool))
+    }
+
+    /// Describe the schema to the model (appended to the tool description)
+    pub fn schema_description<S: AsRef<str>>(mut self, description: S) -> Self {
+        self.description = Some(description.as_ref().to_string());
+        self
+    }
+
+    /// Set the maximum number of bytes returned to the model
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Get the tool definition to send to the API
+    pub fn definition(&self) -> Tool {
+        let mut description =
+            "Run a read-only SQL query (SELECT only) and return the rows as JSON.".to_string();
+        if let Some(schema) = &self.description {
+            description.push_str("\n\n");
+            description.push_str(schema);
+        }
+        let mut tool = Tool::new(SQL_QUERY_TOOL_NAME);
+        tool.description(description).add_string_property(
+            "query",
+            Some("A single SELECT statement"),
+            true,
+        );
+        tool
+    }
+
+    /// Run the query named by the `query` field of the input
+    ///
+    /// The query runs in a read-only transaction that is rolled back.
+    pub async fn execute(&self, input: Value) -> Result<String> {
+        let query = check_statement(required_str(&input, "query")?)?;
+        let mut conn = self.pool.acquire().await.map_err(sql_error)?;
+        let rows = if conn.backend_name() == "SQLite" {
+            // SQLite has no read-only transactions; the pragma applies to the
+            // whole connection until it is turned off again
+            let mut guard = QueryOnlyGuard { conn, reset: false };
+            sqlx::query("PRAGMA query_only = ON")
+                .execute(&mut *guard.conn)
+                .await
+                .map_err(sql_error)?;
+            let rows = fetch_read_only(&mut guard.conn, "BEGIN", query).await;
+            sqlx::query("PRAGMA query_only = OFF")
+                .execute(&mut *guard.conn)
+                .await
+                .map_err(sql_error)?;
+            guard.reset = true;
+            rows?
+        } else {
+            fetch_read_only(&mut conn, "START TRANSACTION READ ONLY", query).await?
+        };
+        let rows: Vec<Value> = rows.iter().map(row_to_json).collect();
+        Ok(truncate_output(
+            serde_json::to_string(&rows)?,
+            self.max_bytes,
+        ))
+    }
+}
+
+/// Pooled SQLite connection that may be in `query_only` mode
+///
+/// Unless the pragma was turned off again, the connection is closed instead
+/// of going back to the pool, e.g. when `execute` is cancelled by a timeout.
+struct QueryOnlyGuard {
+    conn: PoolConnection<Any>,
+    reset: bool,
+}
+
+impl Drop for QueryOnlyGuard {
+    fn drop(&mut self) {
+        if !self.reset {
+            self.conn.close_on_drop();
+        }
+    }
+}
+
+/// Run a query in a transaction opened with `begin`, then roll it back
+async fn fetch_read_only(
+    conn: &mut AnyConnection,
+    begin: &'static str,
+    query: &str,
+) -> Result<Vec<AnyRow>> {
+    let mut tx = conn.begin_with(begin).await.map_err(sql_error)?;
+    let rows = sqlx::query(query)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(sql_error)?;
+    tx.rollback().await.map_err(sql_error)?;
+    Ok(rows)
+}
+
+/// Accept a single `SELECT`/`WITH` statement, rejecting anything else
+///
+/// This only turns obvious mistakes into clear errors; the read-only
+/// transaction is what keeps the query from writing.
+fn check_statement(query: &str) -> Result<&str> {
+    let query = query.trim().trim_end_matches(';').trim_end();
+    let first = query
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_uppercase();
+    if !matches!(first.as_str(), "SELECT" | "WITH") {
+        return Err(AnthropicToolError::PermissionError(
+            "only SELECT queries are allowed".to_string(),
+        ));
+    }
+    if has_separator(query) {
+        return Err(AnthropicToolError::PermissionError(
+            "only a single statement is allowed".to_string(),
+        ));
+    }
+    Ok(query)
+}
+
+/// Whether a query has a `;` outside string literals, quoted identifiers,
+/// and comments
+fn has_separator(query: &str) -> bool {
+    let bytes = query.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b';' => return true,
+            // A doubled quote escapes itself, so skipping to the next quote
+            // and continuing from there handles it
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Convert a row into a JSON object keyed by column name
+fn row_to_json(row: &AnyRow) -> Value {
+    let mut object = Map::new();
+    for (i, column) in row.columns().iter().enumerate() {
+        let value = if let Ok(v) = row.try_get::<Option<i64>, _>(i) {
+            v.map(Value::from)
+        } else if let Ok(v) = row.try_get::<Option<f64>, _>(i) {
+            v.map(Value::from)
+        } else if let Ok(v) = row.try_get::<Option<bool>, _>(i) {
+            v.map(Value::from)
+        } else if let Ok(v) = row.try_get::<Option<String>, _>(i) {
+            v.map(Value::from)
+        } else {
+            None
+        };
+        object.insert(column.name().to_string(), value.unwrap_or(Value::Null));
+    }
+    Value::Object(object)
+}
+
+fn sql_error(err: sqlx::Error) -> AnthropicToolError {
+    AnthropicToolError::InvalidRequestError(format!("SQL error: {}", err))
+}
+
+impl_tool_handler!(SqlQueryTool, SQL_QUERY_TOOL_NAME);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_statement() {
+        assert!(check_statement("SELECT 1").is_ok());
+        assert!(check_statement("  with t as (select 1) select * from t;").is_ok());
+        assert!(check_statement("DELETE FROM users").is_err());
+        assert!(check_statement("SELECT 1; DROP TABLE users").is_err());
+        assert!(check_statement("SELECT ';' AS s, \"a;b\" -- ;\n FROM t /* ; */").is_ok());
+        assert!(check_statement("SELECT 'it''s'; DROP TABLE users").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_query() {
+        let tool = SqlQueryTool::connect("sqlite::memory:").await.unwrap();
+        let output = tool
+            .execute(json!({"query": "SELECT 1 AS id, 'alice' AS name, 2.5 AS score"}))
+            .await
+            .unwrap();
+        let rows: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(rows, json!([{"id": 1, "name": "alice", "score": 2.5}]));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_transaction() {
+        let path =
+            std::env::temp_dir().join(format!("anthropic-tools-sql-{}.db", std::process::id()));
+        let url = format!("sqlite://{}?mode=rwc", path.display());
+        let tool = SqlQueryTool::connect(&url).await.unwrap();
+        sqlx::query("CREATE TABLE users (id INTEGER)")
+            .execute(&tool.pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO users VALUES (1)")
+            .execute(&tool.pool)
+            .await
+            .unwrap();
+
+        // Passes the statement check, but the database refuses to write
+        let result = tool
+            .execute(json!({"query": "WITH t AS (SELECT 1) DELETE FROM users"}))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AnthropicToolError::InvalidRequestError(_))
+        ));
+        let output = tool
+            .execute(json!({"query": "SELECT id FROM users"}))
+            .await
+            .unwrap();
+        assert_eq!(output, r#"[{"id":1}]"#);
+
+        // The pooled connections are writable again afterwards
+        sqlx::query("INSERT INTO users VALUES (2)")
+            .execute(&tool.pool)
+            .await
+            .unwrap();
+        tool.pool.close().await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_query_does_not_poison_pool() {
+        let path = std::env::temp_dir().join(format!(
+            "anthropic-tools-sql-cancel-{}.db",
+            std::process::id()
+        ));
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite://{}?mode=rwc", path.display()))
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE users (id INTEGER)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let tool = SqlQueryTool::new(pool.clone());
+
+        let slow = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 3000000) SELECT count(*) AS n FROM c";
+        let result = tokio::time::timeout(
+            std::time::Duration::from_millis(5),
+            tool.execute(json!({"query": slow})),
+        )
+        .await;
+        assert!(result.is_err());
+
+        // The only pooled connection was replaced, so writes still work
+        sqlx::query("INSERT INTO users VALUES (1)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_write() {
+        let tool = SqlQueryTool::connect("sqlite::memory:").await.unwrap();
+        let result = tool
+            .execute(json!({"query": "CREATE TABLE t (id INT)"}))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AnthropicToolError::PermissionError(_))
+        ));
+    }
+}
@@ -0,0 +1,417 @@
+//! Local executor for the Anthropic-defined text editor tool.
+//!
+//! [`TextEditorExecutor`] answers the `tool_use` blocks of the
+//! `text_editor_20250728` tool ([`TextEditorTool`]) on the files below a root
+//! directory: `view`, `str_replace`, `create`, and `insert`. Registered in a
+//! [`ToolRegistry`](crate::tools::registry::ToolRegistry), it is sent with its
+//! versioned `type`, so it plugs into
+//! [`Messages::run_tool_loop`](crate::messages::request::Messages::run_tool_loop).
+//!
+//! Relative paths are resolved against the root, and absolute paths must lie
+//! below it. `..` segments are rejected, and the nearest existing ancestor is
+//! canonicalized, so symlinks cannot escape the root either; dangling
+//! symlinks are rejected. `create` refuses to overwrite existing files.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::tools::builtin::text_editor::TextEditorExecutor;
+//! use anthropic_tools::tools::registry::ToolRegistry;
+//!
+//! # async fn example() -> Result<()> {
+//! let mut registry = ToolRegistry::new();
+//! registry.register(TextEditorExecutor::new("./workspace"));
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(4096)
+//!     .user("Fix the typo in README.md");
+//! let run = client.run_tool_loop(&registry).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{DEFAULT_MAX_OUTPUT_BYTES, truncate_output};
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::future::{BoxFuture, boxed};
+use crate::common::server_tool::{TEXT_EDITOR_NAME, TextEditorCommand, TextEditorTool};
+use crate::common::tool::{Tool, parse_input};
+use crate::tools::handler::{ToolHandler, ToolOutput};
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// View, create, and edit text files below a root directory
+#[derive(Debug, Clone)]
+pub struct TextEditorExecutor {
+    root: PathBuf,
+    max_bytes: usize,
+}
+
+impl TextEditorExecutor {
+    /// Create an executor that can edit files below `root`
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        TextEditorExecutor {
+            root: root.as_ref().to_path_buf(),
+            max_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Set the maximum number of bytes returned to the model
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Get a tool definition describing the input
+    ///
+    /// The API receives [`TextEditorTool`] instead (see
+    /// [`ToolHandler::tool_value`]); this schema documents the input for other
+    /// uses.
+    pub fn definition(&self) -> Tool {
+        let mut tool = Tool::new(TEXT_EDITOR_NAME);
+        tool.description("View, create, and edit text files.")
+            .add_enum_property(
+                "command",
+                Some("Command to run"),
+                vec!["view", "str_replace", "create", "insert"],
+                true,
+            )
+            .add_string_property("path", Some("Path of the file or directory"), true);
+        tool
+    }
+
+    /// Resolve a path, rejecting anything outside the root
+    ///
+    /// The path itself does not need to exist, so files can be created.
+    pub fn resolve<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf> {
+        let path = path.as_ref();
+        let escape = || {
+            AnthropicToolError::InvalidParameter(format!(
+                "path escapes the root directory: {}",
+                path.display()
+            ))
+        };
+        if path.components().any(|c| c == Component::ParentDir) {
+            return Err(escape());
+        }
+
+        let root = self.root.canonicalize()?;
+        let resolved = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            root.join(path)
+        };
+        // `symlink_metadata` stops at dangling symlinks, which `exists` skips
+        let mut existing = resolved.as_path();
+        while existing.symlink_metadata().is_err() {
+            existing = existing.parent().ok_or_else(escape)?;
+        }
+        let canonical = existing.canonicalize().map_err(|_| escape())?;
+        if !canonical.starts_with(&root) {
+            return Err(escape());
+        }
+        match resolved.strip_prefix(existing) {
+            Ok(rest) if !rest.as_os_str().is_empty() => Ok(canonical.join(rest)),
+            _ => Ok(canonical),
+        }
+    }
+
+    /// Run the command of a text editor tool input
+    pub async fn execute(&self, input: Value) -> Result<String> {
+        let command: TextEditorCommand = parse_input(TEXT_EDITOR_NAME, &input)?;
+        let path = self.resolve(command.path())?;
+        let output = match command {
+            TextEditorCommand::View { view_range, .. } => self.view(&path, view_range).await?,
+            TextEditorCommand::StrReplace {
+                path: name,
+                old_str,
+                new_str,
+            } => {
+                let text = tokio::fs::read_to_string(&path).await?;
+                match text.matches(&old_str).count() {
+                    1 => {
+                        tokio::fs::write(&path, text.replacen(&old_str, &new_str, 1)).await?;
+                        "Successfully replaced text at exactly one location.".to_string()
+                    }
+                    0 => {
+                        return Err(AnthropicToolError::InvalidParameter(format!(
+                            "no match found for replacement text in {}",
+                            name
+                        )));
+                    }
+                    count => {
+                        return Err(AnthropicToolError::InvalidParameter(format!(
+                            "found {} matches for replacement text in {}; add context to make the match unique",
+                            count, name
+                        )));
+                    }
+                }
+            }
+            TextEditorCommand::Create {
+                path: name,
+                file_text,
+            } => {
+                if tokio::fs::symlink_metadata(&path).await.is_ok() {
+                    return Err(AnthropicToolError::InvalidParameter(format!(
+                        "file already exists: {}; use str_replace to edit it",
+                        name
+                    )));
+                }
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                    // Re-check in case a directory was swapped for a symlink
+                    if !tokio::fs::canonicalize(parent)
+                        .await?
+                        .starts_with(tokio::fs::canonicalize(&self.root).await?)
+                    {
+                        return Err(AnthropicToolError::InvalidParameter(format!(
+                            "path escapes the root directory: {}",
+                            name
+                        )));
+                    }
+                }
+                // `create_new` does not follow a symlink at the final component
+                let mut file = tokio::fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&path)
+                    .await?;
+                file.write_all(file_text.as_bytes()).await?;
+                file.flush().await?;
+                format!("File created: {}", name)
+            }
+            TextEditorCommand::Insert {
+                path: name,
+                insert_line,
+                insert_text,
+            } => {
+                let text = tokio::fs::read_to_string(&path).await?;
+                let mut lines: Vec<&str> = text.lines().collect();
+                if insert_line > lines.len() {
+                    return Err(AnthropicToolError::InvalidParameter(format!(
+                        "insert_line {} is past the end of {} ({} lines)",
+                        insert_line,
+                        name,
+                        lines.len()
+                    )));
+                }
+                lines.splice(insert_line..insert_line, insert_text.lines());
+                let mut updated = lines.join("\n");
+                if text.ends_with('\n') || text.is_empty() {
+                    updated.push('\n');
+                }
+                tokio::fs::write(&path, updated).await?;
+                format!("Text inserted after line {} of {}", insert_line, name)
+            }
+        };
+        Ok(truncate_output(output, self.max_bytes))
+    }
+
+    /// List a directory, or show (part of) a file with line numbers
+    async fn view(&self, path: &Path, view_range: Option<[i64; 2]>) -> Result<String> {
+        if tokio::fs::metadata(path).await.is_ok_and(|m| m.is_dir()) {
+            let mut dir = tokio::fs::read_dir(path).await?;
+            let mut entries = Vec::new();
+            while let Some(entry) = dir.next_entry().await? {
+                let mut name = entry.file_name().to_string_lossy().into_owned();
+                if entry.file_type().await?.is_dir() {
+                    name.push('/');
+                }
+                entries.push(name);
+            }
+            entries.sort();
+            return Ok(entries.join("\n"));
+        }
+
+        let text = tokio::fs::read_to_string(path).await?;
+        let lines: Vec<&str> = text.lines().collect();
+        let (start, end) = match view_range {
+            None => (1, lines.len()),
+            Some([start, end]) => {
+                let end = if end == -1 { lines.len() as i64 } else { end };
+                if start < 1 || end < start || end > lines.len() as i64 {
+                    return Err(AnthropicToolError::InvalidParameter(format!(
+                        "invalid view_range [{}, {}] for a file of {} lines",
+                        start,
+                        end,
+                        lines.len()
+                    )));
+                }
+                (start as usize, end as usize)
+            }
+        };
+        Ok(lines
+            .iter()
+            .enumerate()
+            .take(end)
+            .skip(start.saturating_sub(1))
+            .map(|(i, line)| format!("{:>6}\t{}", i + 1, line))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+impl ToolHandler for TextEditorExecutor {
+    fn name(&self) -> &str {
+        TEXT_EDITOR_NAME
+    }
+
+    fn definition(&self) -> Tool {
+        TextEditorExecutor::definition(self)
+    }
+
+    fn tool_value(&self) -> Value {
+        TextEditorTool::new().to_value()
+    }
+
+    fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+        boxed(async move {
+            TextEditorExecutor::execute(self, input)
+                .await
+                .map(ToolOutput::text)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "anthropic-tools-editor-{}-{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/main.rs"),
+            "fn main() {\n    println!(\"hi\");\n}\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[tokio::test]
+    async fn test_view() {
+        let root = temp_root("view");
+        let tool = TextEditorExecutor::new(&root);
+        let text = tool
+            .execute(json!({"command": "view", "path": "src/main.rs", "view_range": [2, -1]}))
+            .await
+            .unwrap();
+        assert_eq!(text, "     2\t    println!(\"hi\");\n     3\t}");
+
+        let listing = tool
+            .execute(json!({"command": "view", "path": "."}))
+            .await
+            .unwrap();
+        assert_eq!(listing, "src/");
+        assert!(
+            tool.execute(json!({"command": "view", "path": "src/main.rs", "view_range": [2, 9]}))
+                .await
+                .is_err()
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_edit_commands() {
+        let root = temp_root("edit");
+        let tool = TextEditorExecutor::new(&root);
+        tool.execute(json!({
+            "command": "str_replace",
+            "path": "src/main.rs",
+            "old_str": "\"hi\"",
+            "new_str": "\"hello\""
+        }))
+        .await
+        .unwrap();
+        tool.execute(json!({
+            "command": "insert",
+            "path": "src/main.rs",
+            "insert_line": 0,
+            "insert_text": "// entry point"
+        }))
+        .await
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/main.rs")).unwrap(),
+            "// entry point\nfn main() {\n    println!(\"hello\");\n}\n"
+        );
+
+        let absolute = root.canonicalize().unwrap().join("docs/notes.md");
+        tool.execute(json!({
+            "command": "create",
+            "path": absolute.to_str().unwrap(),
+            "file_text": "notes"
+        }))
+        .await
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("docs/notes.md")).unwrap(),
+            "notes"
+        );
+
+        let ambiguous = json!({
+            "command": "str_replace",
+            "path": "src/main.rs",
+            "old_str": "n"
+        });
+        assert!(tool.execute(ambiguous).await.is_err());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_escape() {
+        let root = temp_root("escape");
+        let tool = TextEditorExecutor::new(root.join("src"));
+        let create = |path: &str| json!({"command": "create", "path": path, "file_text": "x"});
+        assert!(tool.execute(create("../outside.txt")).await.is_err());
+        assert!(tool.execute(create("/tmp/outside.txt")).await.is_err());
+        assert!(!root.join("outside.txt").exists());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_rejects_dangling_symlink() {
+        let root = temp_root("dangling");
+        let outside = root.join("outside.txt");
+        std::os::unix::fs::symlink(&outside, root.join("src/link.txt")).unwrap();
+        let tool = TextEditorExecutor::new(root.join("src"));
+        assert!(tool.resolve("link.txt").is_err());
+        assert!(tool.resolve("link.txt/nested").is_err());
+        let create = json!({"command": "create", "path": "link.txt", "file_text": "x"});
+        assert!(tool.execute(create).await.is_err());
+        assert!(outside.symlink_metadata().is_err());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_refuses_overwrite() {
+        let root = temp_root("overwrite");
+        let tool = TextEditorExecutor::new(&root);
+        let create = json!({"command": "create", "path": "src/main.rs", "file_text": "x"});
+        assert!(tool.execute(create).await.is_err());
+        assert!(
+            std::fs::read_to_string(root.join("src/main.rs"))
+                .unwrap()
+                .starts_with("fn main()")
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_tool_value() {
+        let tool = TextEditorExecutor::new(".");
+        assert_eq!(
+            tool.tool_value(),
+            json!({"type": "text_editor_20250728", "name": "str_replace_based_edit_tool"})
+        );
+    }
+}
@@ -0,0 +1,372 @@
+//! Tool implementations as first-class values.
+//!
+//! A [`ToolHandler`] bundles the definition sent to the API with the code
+//! that runs when the model calls the tool, so tools can be stored, passed
+//! around, and dispatched by name instead of matching on
+//! [`Response::get_tool_uses`](crate::messages::response::Response::get_tool_uses)
+//! by hand.
+//!
+//! - [`ToolHandler`] - Name, definition, and async `execute`
+//! - [`ToolOutput`] - Content (and error flag) of the `tool_result`
+//!
+//! The [built-in tools](super::builtin) implement the trait.
+//!
+//! With the `derive` feature, `#[tool]` turns a function into a handler: the
+//! parameters become the input schema and `execute` calls the function.
+//!
+//! ```rust,ignore
+//! use anthropic_tools::tools::handler::tool;
+//! use anthropic_tools::tools::registry::ToolRegistry;
+//!
+//! /// Get the current weather for a location
+//! #[tool(params(location = "City name", unit = "Temperature unit"))]
+//! async fn get_weather(location: String, unit: Option<Unit>) -> Result<Weather> {
+//!     weather_api::current(&location, unit.unwrap_or_default()).await
+//! }
+//!
+//! let mut registry = ToolRegistry::new();
+//! registry.register(GetWeatherTool);
+//! ```
+//!
+//! The handler is a unit struct named after the function (`get_weather` ->
+//! `GetWeatherTool`). `#[tool]` accepts `name`, `description` (defaults to
+//! the doc comment), and `params(...)` with parameter descriptions.
+//! Parameters must implement
+//! [`ToolInputType`](crate::common::tool::ToolInputType) and
+//! `DeserializeOwned`; an input that does not deserialize is answered with an
+//! `is_error` result. The return value goes through [`ToolOutput::json`]
+//! (a [`ToolOutput`] is used as is), and an `Err(e)` becomes an `is_error`
+//! result with the text of `e`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use anthropic_tools::common::future::{BoxFuture, boxed};
+//! use anthropic_tools::common::tool::Tool;
+//! use anthropic_tools::tools::handler::{ToolHandler, ToolOutput};
+//! use anthropic_tools::Result;
+//! use serde_json::Value;
+//!
+//! struct Weather;
+//!
+//! impl ToolHandler for Weather {
+//!     fn name(&self) -> &str {
+//!         "get_weather"
+//!     }
+//!
+//!     fn definition(&self) -> Tool {
+//!         let mut tool = Tool::new("get_weather");
+//!         tool.description("Get the current weather")
+//!             .add_string_property("location", Some("City name"), true);
+//!         tool
+//!     }
+//!
+//!     fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+//!         boxed(async move {
+//!             let location = input["location"].as_str().unwrap_or("somewhere");
+//!             Ok(ToolOutput::text(format!("Sunny in {}", location)))
+//!         })
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let handler: Box<dyn ToolHandler> = Box::new(Weather);
+//! let output = handler.execute(serde_json::json!({"location": "Tokyo"})).await.unwrap();
+//! assert_eq!(output.text_content(), "Sunny in Tokyo");
+//! # }
+//! ```
+
+use crate::common::errors::Result;
+use crate::common::future::BoxFuture;
+use crate::common::tool::Tool;
+use crate::messages::request::content::{ContentBlock, ImageSource};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "derive")]
+pub use anthropic_tools_derive::tool;
+
+/// Executable tool
+pub trait ToolHandler: Send + Sync {
+    /// Tool name, matching the `name` of the definition
+    fn name(&self) -> &str;
+
+    /// Definition to send to the API
+    fn definition(&self) -> Tool;
+
+    /// Definition as the JSON sent in the `tools` array
+    ///
+    /// Defaults to [`ToolHandler::definition`]. Executors of Anthropic-defined
+    /// tools (e.g. `bash_20250124`) override it to send their versioned
+    /// `type` instead of an input schema.
+    fn tool_value(&self) -> Value {
+        self.definition().to_value()
+    }
+
+    /// Run the tool for the `input` of a `tool_use` block
+    ///
+    /// Return `Ok` with [`ToolOutput::error`] for failures the model should see
+    /// and react to, and `Err` for failures of the tool itself.
+    fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>>;
+
+    /// Time limit for one execution
+    ///
+    /// When a [`ToolRegistry`](super::registry::ToolRegistry) runs the tool
+    /// and the limit expires, the execution is cancelled (its future is
+    /// dropped) and the model gets an `is_error` result instead. `None` (the
+    /// default) falls back to the registry's timeout.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl<T: ToolHandler + ?Sized> ToolHandler for Box<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn definition(&self) -> Tool {
+        (**self).definition()
+    }
+
+    fn tool_value(&self) -> Value {
+        (**self).tool_value()
+    }
+
+    fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+        (**self).execute(input)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        (**self).timeout()
+    }
+}
+
+impl<T: ToolHandler + ?Sized> ToolHandler for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn definition(&self) -> Tool {
+        (**self).definition()
+    }
+
+    fn tool_value(&self) -> Value {
+        (**self).tool_value()
+    }
+
+    fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+        (**self).execute(input)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        (**self).timeout()
+    }
+}
+
+/// Output of a tool, sent back as the content of a `tool_result` block
+#[derive(Debug, Clone, Default)]
+pub struct ToolOutput {
+    /// Content blocks of the result
+    pub content: Vec<ContentBlock>,
+
+    /// Whether the result reports an error to the model
+    pub is_error: bool,
+}
+
+impl ToolOutput {
+    /// Create a text output
+    pub fn text<T: AsRef<str>>(text: T) -> Self {
+        ToolOutput {
+            content: vec![ContentBlock::text(text)],
+            is_error: false,
+        }
+    }
+
+    /// Create an error output the model can react to
+    pub fn error<T: AsRef<str>>(message: T) -> Self {
+        ToolOutput {
+            content: vec![ContentBlock::text(message)],
+            is_error: true,
+        }
+    }
+
+    /// Create an image output (e.g. a screenshot)
+    pub fn image(source: ImageSource) -> Self {
+        ToolOutput::blocks(vec![ContentBlock::Image {
+            source,
+            cache_control: None,
+        }])
+    }
+
+    /// Create an output from content blocks
+    pub fn blocks(content: Vec<ContentBlock>) -> Self {
+        ToolOutput {
+            content,
+            is_error: false,
+        }
+    }
+
+    /// Create an output from a serializable value
+    ///
+    /// Strings are sent as they are, `null` (e.g. `()`) as an empty output,
+    /// and anything else as JSON text.
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<Self> {
+        Ok(match serde_json::to_value(value)? {
+            Value::String(text) => ToolOutput::text(text),
+            Value::Null => ToolOutput::default(),
+            value => ToolOutput::text(value.to_string()),
+        })
+    }
+
+    /// Concatenated text of the text blocks
+    pub fn text_content(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Convert into a `tool_result` block answering the given `tool_use` ID
+    pub fn into_tool_result<S: AsRef<str>>(self, tool_use_id: S) -> ContentBlock {
+        ContentBlock::ToolResult {
+            tool_use_id: tool_use_id.as_ref().to_string(),
+            content: Some(self.content),
+            is_error: self.is_error.then_some(true),
+            cache_control: None,
+        }
+    }
+}
+
+impl From<String> for ToolOutput {
+    fn from(text: String) -> Self {
+        ToolOutput::text(text)
+    }
+}
+
+impl From<&str> for ToolOutput {
+    fn from(text: &str) -> Self {
+        ToolOutput::text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_tool_result() {
+        let block = ToolOutput::error("not found").into_tool_result("toolu_1");
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(json["type"], "tool_result");
+        assert_eq!(json["tool_use_id"], "toolu_1");
+        assert_eq!(json["is_error"], true);
+        assert_eq!(json["content"][0]["text"], "not found");
+
+        let json =
+            serde_json::to_value(ToolOutput::from("ok").into_tool_result("toolu_2")).unwrap();
+        assert!(json.get("is_error").is_none());
+    }
+
+    #[test]
+    fn test_json_output() {
+        let output = ToolOutput::json(&serde_json::json!({"temp": 21})).unwrap();
+        assert_eq!(output.text_content(), r#"{"temp":21}"#);
+        assert_eq!(ToolOutput::json("sunny").unwrap().text_content(), "sunny");
+        assert!(ToolOutput::json(&()).unwrap().content.is_empty());
+    }
+
+    #[cfg(feature = "derive")]
+    mod derive {
+        use super::super::*;
+        use crate::common::tool::ToolInput;
+        use serde::Deserialize;
+
+        #[derive(ToolInput, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Unit {
+            Celsius,
+            Fahrenheit,
+        }
+
+        #[derive(Serialize)]
+        struct Weather {
+            location: String,
+            temperature: f64,
+        }
+
+        /// Get the current weather
+        #[tool(params(location = "City name"))]
+        async fn get_weather(location: String, unit: Option<Unit>) -> Result<Weather> {
+            let temperature = match unit {
+                Some(Unit::Fahrenheit) => 70.0,
+                Some(Unit::Celsius) | None => 21.0,
+            };
+            Ok(Weather {
+                location,
+                temperature,
+            })
+        }
+
+        #[tool(name = "shout", description = "Uppercase text")]
+        fn upper(text: String) -> std::result::Result<String, String> {
+            if text.is_empty() {
+                return Err("nothing to shout".to_string());
+            }
+            Ok(text.to_uppercase())
+        }
+
+        #[tokio::test]
+        async fn test_tool_attribute() {
+            let json = GetWeatherTool.definition().to_value();
+            assert_eq!(GetWeatherTool.name(), "get_weather");
+            assert_eq!(json["description"], "Get the current weather");
+            let properties = &json["input_schema"]["properties"];
+            assert_eq!(properties["location"]["description"], "City name");
+            assert_eq!(
+                properties["unit"]["enum"],
+                serde_json::json!(["celsius", "fahrenheit"])
+            );
+            assert_eq!(
+                json["input_schema"]["required"],
+                serde_json::json!(["location"])
+            );
+
+            let output = GetWeatherTool
+                .execute(serde_json::json!({"location": "Tokyo", "unit": "fahrenheit"}))
+                .await
+                .unwrap();
+            assert!(!output.is_error);
+            assert_eq!(
+                output.text_content(),
+                r#"{"location":"Tokyo","temperature":70.0}"#
+            );
+
+            let output = GetWeatherTool
+                .execute(serde_json::json!({"unit": "kelvin"}))
+                .await
+                .unwrap();
+            assert!(output.is_error);
+            assert!(output.text_content().starts_with("invalid `location`"));
+
+            assert_eq!(UpperTool.name(), "shout");
+            let output = UpperTool
+                .execute(serde_json::json!({"text": "hi"}))
+                .await
+                .unwrap();
+            assert_eq!(output.text_content(), "HI");
+            let output = UpperTool
+                .execute(serde_json::json!({"text": ""}))
+                .await
+                .unwrap();
+            assert!(output.is_error);
+            assert_eq!(output.text_content(), "nothing to shout");
+        }
+    }
+}
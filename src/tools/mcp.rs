@@ -0,0 +1,670 @@
+//! Client for local MCP (Model Context Protocol) servers.
+//!
+//! The `mcp_servers` field of the request
+//! ([`McpServer`](crate::messages::request::mcp::McpServer)) lets the API call
+//! remote servers itself. [`McpClient`] instead connects from this process,
+//! to a server spawned over stdio ([`StdioTransport`]) or reached over
+//! Streamable HTTP ([`StreamableHttpTransport`]), discovers its tools with
+//! `tools/list`, and exposes each one as an [`McpTool`] handler whose calls
+//! are forwarded with `tools/call`. Registered in a [`ToolRegistry`], the
+//! server's tools plug into
+//! [`Messages::run_tool_loop`](crate::messages::request::Messages::run_tool_loop)
+//! like local ones.
+//!
+//! Requests on one connection are sent one at a time. Other transports can
+//! be plugged in by implementing [`McpTransport`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::tools::mcp::McpClient;
+//! use anthropic_tools::tools::registry::ToolRegistry;
+//!
+//! # async fn example() -> Result<()> {
+//! let github = McpClient::stdio("npx", ["-y", "@modelcontextprotocol/server-github"]).await?;
+//! let docs = McpClient::http("http://localhost:8080/mcp").await?;
+//!
+//! let mut registry = ToolRegistry::new();
+//! github.register_tools(&mut registry).await?;
+//! docs.register_tools(&mut registry).await?;
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .max_tokens(1024)
+//!     .user("Summarize the open issues of rust-lang/rust");
+//! let run = client.run_tool_loop(&registry).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::{AnthropicToolError, Result};
+use crate::common::future::{BoxFuture, boxed};
+use crate::common::tool::{JsonSchema, Tool};
+use crate::messages::request::content::{ContentBlock, ImageSource};
+use crate::tools::handler::{ToolHandler, ToolOutput};
+use crate::tools::registry::ToolRegistry;
+use futures::lock::Mutex;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::ffi::OsStr;
+use std::fmt;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+/// MCP protocol version requested in the handshake
+pub const MCP_PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Connection carrying JSON-RPC messages to an MCP server
+pub trait McpTransport: Send + Sync {
+    /// Send a request and wait for the response with the same `id`
+    fn request(&self, message: Value) -> BoxFuture<'_, Result<Value>>;
+
+    /// Send a notification, which has no response
+    fn notify(&self, message: Value) -> BoxFuture<'_, Result<()>>;
+}
+
+/// Error for a transport-level failure
+fn mcp_error<S: AsRef<str>>(message: S) -> AnthropicToolError {
+    AnthropicToolError::ApiError {
+        error_type: "mcp_error".to_string(),
+        message: message.as_ref().to_string(),
+        request_id: None,
+    }
+}
+
+/// MCP server spawned as a child process, speaking newline-delimited JSON
+///
+/// The process is killed when the transport is dropped. Server requests
+/// other than `ping` are answered with a "method not found" error, and
+/// notifications are ignored.
+pub struct StdioTransport {
+    io: Mutex<(ChildStdin, BufReader<ChildStdout>)>,
+    _child: Child,
+}
+
+impl StdioTransport {
+    /// Spawn a server from a prepared command (arguments, environment, ...)
+    ///
+    /// stdin and stdout are taken over by the transport; stderr is inherited
+    /// unless the command sets it.
+    pub fn spawn(mut command: Command) -> Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take();
+        let (Some(stdin), Some(stdout)) = (stdin, stdout) else {
+            return Err(mcp_error("MCP server has no stdin/stdout"));
+        };
+        Ok(StdioTransport {
+            io: Mutex::new((stdin, BufReader::new(stdout))),
+            _child: child,
+        })
+    }
+}
+
+async fn write_message(stdin: &mut ChildStdin, message: &Value) -> Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    stdin.write_all(line.as_bytes()).await?;
+    stdin.flush().await?;
+    Ok(())
+}
+
+impl McpTransport for StdioTransport {
+    fn request(&self, message: Value) -> BoxFuture<'_, Result<Value>> {
+        boxed(async move {
+            let mut io = self.io.lock().await;
+            let (stdin, stdout) = &mut *io;
+            write_message(stdin, &message).await?;
+            loop {
+                let mut line = String::new();
+                if stdout.read_line(&mut line).await? == 0 {
+                    return Err(mcp_error("MCP server closed the connection"));
+                }
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let received: Value = serde_json::from_str(&line)?;
+                match (received.get("id"), received.get("method")) {
+                    (Some(id), None) if *id == message["id"] => return Ok(received),
+                    (Some(id), Some(method)) => {
+                        let reply = if method == "ping" {
+                            json!({"jsonrpc": "2.0", "id": id, "result": {}})
+                        } else {
+                            json!({
+                                "jsonrpc": "2.0",
+                                "id": id,
+                                "error": {"code": -32601, "message": "Method not found"}
+                            })
+                        };
+                        write_message(stdin, &reply).await?;
+                    }
+                    _ => {}
+                }
+            }
+        })
+    }
+
+    fn notify(&self, message: Value) -> BoxFuture<'_, Result<()>> {
+        boxed(async move {
+            let mut io = self.io.lock().await;
+            write_message(&mut io.0, &message).await
+        })
+    }
+}
+
+impl fmt::Debug for StdioTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StdioTransport").finish_non_exhaustive()
+    }
+}
+
+/// MCP server reached over Streamable HTTP
+///
+/// Responses may be plain JSON or a `text/event-stream`; the session ID
+/// returned by the server is sent with every later request.
+#[derive(Debug)]
+pub struct StreamableHttpTransport {
+    url: String,
+    authorization_token: Option<String>,
+    client: request::Client,
+    session_id: std::sync::Mutex<Option<String>>,
+}
+
+impl StreamableHttpTransport {
+    /// Create a transport for the server's MCP endpoint
+    pub fn new<S: AsRef<str>>(url: S) -> Self {
+        StreamableHttpTransport {
+            url: url.as_ref().to_string(),
+            authorization_token: None,
+            client: request::Client::new(),
+            session_id: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Send a bearer token with every request
+    pub fn authorization_token<S: AsRef<str>>(mut self, token: S) -> Self {
+        self.authorization_token = Some(token.as_ref().to_string());
+        self
+    }
+
+    async fn post(&self, message: &Value) -> Result<request::Response> {
+        let mut builder = self
+            .client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("MCP-Protocol-Version", MCP_PROTOCOL_VERSION)
+            .json(message);
+        if let Some(token) = &self.authorization_token {
+            builder = builder.bearer_auth(token);
+        }
+        let session_id = self
+            .session_id
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        if let Some(session_id) = session_id {
+            builder = builder.header("Mcp-Session-Id", session_id);
+        }
+
+        let response = builder.send().await?;
+        if let Some(session_id) = response
+            .headers()
+            .get("Mcp-Session-Id")
+            .and_then(|value| value.to_str().ok())
+        {
+            *self
+                .session_id
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(session_id.to_string());
+        }
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(mcp_error(format!("HTTP {}: {}", status, body)));
+        }
+        Ok(response)
+    }
+}
+
+/// Find the response with the given `id` in a `text/event-stream` body
+fn parse_event_stream(body: &str, id: &Value) -> Option<Value> {
+    body.split("\n\n")
+        .map(|event| {
+            event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim_start)
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter_map(|data| serde_json::from_str::<Value>(&data).ok())
+        .find(|message| message.get("id") == Some(id) && message.get("method").is_none())
+}
+
+impl McpTransport for StreamableHttpTransport {
+    fn request(&self, message: Value) -> BoxFuture<'_, Result<Value>> {
+        boxed(async move {
+            let response = self.post(&message).await?;
+            let is_stream = response
+                .headers()
+                .get("Content-Type")
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("text/event-stream"));
+            let body = response.text().await?;
+            if is_stream {
+                parse_event_stream(&body, &message["id"])
+                    .ok_or_else(|| mcp_error("MCP server sent no response"))
+            } else {
+                Ok(serde_json::from_str(&body)?)
+            }
+        })
+    }
+
+    fn notify(&self, message: Value) -> BoxFuture<'_, Result<()>> {
+        boxed(async move {
+            self.post(&message).await?;
+            Ok(())
+        })
+    }
+}
+
+/// Tool entry of a `tools/list` result
+#[derive(Debug, Deserialize)]
+struct ListedTool {
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "inputSchema", default)]
+    input_schema: Value,
+}
+
+/// Connection to an MCP server
+///
+/// Cloning is cheap; clones share the connection.
+#[derive(Clone)]
+pub struct McpClient {
+    transport: Arc<dyn McpTransport>,
+    next_id: Arc<AtomicU64>,
+    server_name: String,
+}
+
+impl McpClient {
+    /// Connect over a transport and perform the `initialize` handshake
+    pub async fn connect<T: McpTransport + 'static>(transport: T) -> Result<Self> {
+        let mut client = McpClient {
+            transport: Arc::new(transport),
+            next_id: Arc::new(AtomicU64::new(1)),
+            server_name: String::new(),
+        };
+        let result = client
+            .call(
+                "initialize",
+                json!({
+                    "protocolVersion": MCP_PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "anthropic-tools",
+                        "version": env!("CARGO_PKG_VERSION")
+                    }
+                }),
+            )
+            .await?;
+        client.server_name = result["serverInfo"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        client
+            .transport
+            .notify(json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+            .await?;
+        Ok(client)
+    }
+
+    /// Spawn a server and connect to it over stdio
+    pub async fn stdio<I, S>(program: &str, args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = Command::new(program);
+        command.args(args);
+        McpClient::connect(StdioTransport::spawn(command)?).await
+    }
+
+    /// Connect to a server over Streamable HTTP
+    pub async fn http<S: AsRef<str>>(url: S) -> Result<Self> {
+        McpClient::connect(StreamableHttpTransport::new(url)).await
+    }
+
+    /// Name the server reported in the handshake
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    /// Send a JSON-RPC request and return its `result`
+    pub async fn call<S: AsRef<str>>(&self, method: S, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let response = self
+            .transport
+            .request(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": method.as_ref(),
+                "params": params
+            }))
+            .await?;
+        if let Some(error) = response.get("error") {
+            return Err(mcp_error(format!(
+                "{} ({})",
+                error["message"].as_str().unwrap_or("unknown error"),
+                error["code"]
+            )));
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    /// Discover the server's tools, following pagination
+    pub async fn tools(&self) -> Result<Vec<McpTool>> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({"cursor": cursor}),
+                None => json!({}),
+            };
+            let result = self.call("tools/list", params).await?;
+            let listed: Vec<ListedTool> =
+                serde_json::from_value(result.get("tools").cloned().unwrap_or_default())?;
+            tools.extend(
+                listed
+                    .into_iter()
+                    .map(|tool| McpTool::new(self.clone(), tool)),
+            );
+            match result.get("nextCursor").and_then(Value::as_str) {
+                Some(next) => cursor = Some(next.to_string()),
+                None => return Ok(tools),
+            }
+        }
+    }
+
+    /// Call a tool and convert its result
+    ///
+    /// Text and image content become content blocks, other content is sent
+    /// as JSON text, and `isError` results become `is_error` outputs.
+    pub async fn call_tool<S: AsRef<str>>(&self, name: S, arguments: Value) -> Result<ToolOutput> {
+        let result = self
+            .call(
+                "tools/call",
+                json!({"name": name.as_ref(), "arguments": arguments}),
+            )
+            .await?;
+        let mut content: Vec<ContentBlock> = result["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(content_block)
+            .collect();
+        if content.is_empty()
+            && let Some(structured) = result.get("structuredContent")
+        {
+            content.push(ContentBlock::text(structured.to_string()));
+        }
+        Ok(ToolOutput {
+            content,
+            is_error: result["isError"].as_bool().unwrap_or(false),
+        })
+    }
+
+    /// Register all tools of the server, returning their names
+    pub async fn register_tools(&self, registry: &mut ToolRegistry) -> Result<Vec<String>> {
+        let tools = self.tools().await?;
+        let names = tools.iter().map(|tool| tool.name.clone()).collect();
+        for tool in tools {
+            registry.register(tool);
+        }
+        Ok(names)
+    }
+}
+
+impl fmt::Debug for McpClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McpClient")
+            .field("server_name", &self.server_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Convert an MCP content item into a content block
+fn content_block(content: &Value) -> ContentBlock {
+    match content["type"].as_str() {
+        Some("text") => ContentBlock::text(content["text"].as_str().unwrap_or_default()),
+        Some("image") => ContentBlock::Image {
+            source: ImageSource {
+                type_name: "base64".to_string(),
+                media_type: content["mimeType"].as_str().map(str::to_string),
+                data: content["data"].as_str().map(str::to_string),
+                url: None,
+                file_id: None,
+            },
+            cache_control: None,
+        },
+        _ => ContentBlock::text(content.to_string()),
+    }
+}
+
+/// Tool of an MCP server, forwarding calls to it
+///
+/// The input schema is sent to the API exactly as the server declared it;
+/// [`ToolHandler::definition`] parses it on a best-effort basis.
+#[derive(Debug, Clone)]
+pub struct McpTool {
+    client: McpClient,
+    name: String,
+    description: Option<String>,
+    input_schema: Value,
+}
+
+impl McpTool {
+    fn new(client: McpClient, tool: ListedTool) -> Self {
+        McpTool {
+            client,
+            name: tool.name,
+            description: tool.description,
+            input_schema: tool.input_schema,
+        }
+    }
+
+    /// Input schema as declared by the server
+    pub fn input_schema(&self) -> &Value {
+        &self.input_schema
+    }
+}
+
+impl ToolHandler for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn definition(&self) -> Tool {
+        let input_schema =
+            serde_json::from_value(self.input_schema.clone()).unwrap_or_else(|err| {
+                tracing::warn!(tool = %self.name, "unsupported MCP input schema: {}", err);
+                JsonSchema::empty_object()
+            });
+        Tool {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            input_schema,
+            cache_control: None,
+        }
+    }
+
+    fn tool_value(&self) -> Value {
+        let mut value = json!({"name": self.name, "input_schema": self.input_schema});
+        if let Some(description) = &self.description {
+            value["description"] = json!(description);
+        }
+        value
+    }
+
+    fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+        boxed(self.client.call_tool(&self.name, input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    /// Answers from canned results and records the messages it receives
+    struct Mock {
+        sent: StdMutex<Vec<Value>>,
+    }
+
+    impl McpTransport for Arc<Mock> {
+        fn request(&self, message: Value) -> BoxFuture<'_, Result<Value>> {
+            boxed(async move {
+                self.sent.lock().unwrap().push(message.clone());
+                let result = match (message["method"].as_str(), &message["params"]) {
+                    (Some("initialize"), _) => json!({"serverInfo": {"name": "mock"}}),
+                    (Some("tools/list"), params) if params.get("cursor").is_none() => json!({
+                        "tools": [{
+                            "name": "add",
+                            "description": "Add two numbers",
+                            "inputSchema": {
+                                "type": "object",
+                                "properties": {"a": {"type": "number"}, "b": {"type": "number"}},
+                                "required": ["a", "b"]
+                            }
+                        }],
+                        "nextCursor": "page2"
+                    }),
+                    (Some("tools/list"), _) => json!({
+                        "tools": [{"name": "fail", "inputSchema": {"type": "object"}}]
+                    }),
+                    (Some("tools/call"), params) if params["name"] == "add" => {
+                        let sum = params["arguments"]["a"].as_f64().unwrap()
+                            + params["arguments"]["b"].as_f64().unwrap();
+                        json!({"content": [{"type": "text", "text": sum.to_string()}]})
+                    }
+                    (Some("tools/call"), _) => json!({
+                        "content": [{"type": "text", "text": "boom"}],
+                        "isError": true
+                    }),
+                    _ => {
+                        return Ok(json!({
+                            "jsonrpc": "2.0",
+                            "id": message["id"],
+                            "error": {"code": -32601, "message": "Method not found"}
+                        }));
+                    }
+                };
+                Ok(json!({"jsonrpc": "2.0", "id": message["id"], "result": result}))
+            })
+        }
+
+        fn notify(&self, message: Value) -> BoxFuture<'_, Result<()>> {
+            boxed(async move {
+                self.sent.lock().unwrap().push(message);
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_bridges_tools() {
+        let mock = Arc::new(Mock {
+            sent: StdMutex::new(Vec::new()),
+        });
+        let client = McpClient::connect(mock.clone()).await.unwrap();
+        assert_eq!(client.server_name(), "mock");
+
+        let mut registry = ToolRegistry::new();
+        let names = client.register_tools(&mut registry).await.unwrap();
+        assert_eq!(names, vec!["add", "fail"]);
+        assert_eq!(
+            registry.tool_values()[0]["input_schema"]["required"],
+            json!(["a", "b"])
+        );
+        assert_eq!(
+            registry.definitions()[0].description.as_deref(),
+            Some("Add two numbers")
+        );
+
+        let add = registry.get("add").unwrap();
+        let output = add.execute(json!({"a": 1, "b": 2})).await.unwrap();
+        assert_eq!(output.text_content(), "3");
+        assert!(!output.is_error);
+        let output = registry
+            .get("fail")
+            .unwrap()
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        assert!(client.call("resources/list", json!({})).await.is_err());
+
+        let sent = mock.sent.lock().unwrap();
+        let methods: Vec<&str> = sent.iter().filter_map(|m| m["method"].as_str()).collect();
+        assert_eq!(
+            methods,
+            vec![
+                "initialize",
+                "notifications/initialized",
+                "tools/list",
+                "tools/list",
+                "tools/call",
+                "tools/call",
+                "resources/list"
+            ]
+        );
+        assert_eq!(sent[3]["params"]["cursor"], "page2");
+    }
+
+    #[test]
+    fn test_parse_event_stream() {
+        let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n\
+                    event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":7,\"result\":{}}\n\n";
+        assert_eq!(
+            parse_event_stream(body, &json!(7)),
+            Some(json!({"jsonrpc": "2.0", "id": 7, "result": {}}))
+        );
+        assert_eq!(parse_event_stream(body, &json!(8)), None);
+    }
+
+    #[test]
+    fn test_content_block() {
+        let block =
+            content_block(&json!({"type": "image", "data": "aGk=", "mimeType": "image/png"}));
+        let json = serde_json::to_value(block).unwrap();
+        assert_eq!(json["source"]["media_type"], "image/png");
+        let block = content_block(&json!({"type": "resource", "resource": {"uri": "file:///a"}}));
+        assert!(matches!(block, ContentBlock::Text { text, .. } if text.contains("file:///a")));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdio_transport() {
+        let script = r#"
+            read line
+            echo '{"jsonrpc":"2.0","id":1,"result":{"serverInfo":{"name":"echo"}}}'
+            read line
+            read line
+            echo '{"jsonrpc":"2.0","method":"notifications/message","params":{}}'
+            echo '{"jsonrpc":"2.0","id":2,"result":{"content":[{"type":"text","text":"hi"}]}}'
+        "#;
+        let client = McpClient::stdio("bash", ["-c", script]).await.unwrap();
+        assert_eq!(client.server_name(), "echo");
+        let output = client.call_tool("echo", json!({})).await.unwrap();
+        assert_eq!(output.text_content(), "hi");
+    }
+}
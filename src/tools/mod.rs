@@ -0,0 +1,15 @@
+//! Tool implementations for agents built on this crate.
+//!
+//! While [`common::tool`](crate::common::tool) describes tool *definitions*
+//! sent to the API, this module contains code that *executes* tools locally:
+//!
+//! - [`handler`] - [`ToolHandler`](handler::ToolHandler) trait for executable tools
+//! - [`registry`] - [`ToolRegistry`](registry::ToolRegistry) dispatching `tool_use` blocks by name
+//! - [`builtin`] - Ready-made tools for common infrastructure (behind cargo features)
+//! - [`mcp`] - Tools of local MCP servers, bridged as handlers (feature `mcp-client`)
+
+pub mod builtin;
+pub mod handler;
+#[cfg(feature = "mcp-client")]
+pub mod mcp;
+pub mod registry;
@@ -0,0 +1,538 @@
+//! Registry of tool handlers, dispatched by name.
+//!
+//! A [`ToolRegistry`] holds [`ToolHandler`]s, produces the `tools` array for
+//! the request body, and answers the `tool_use` blocks of a response with a
+//! ready-to-send user message of `tool_result` blocks.
+//!
+//! The tools requested in one turn run concurrently, and the results come
+//! back in the order of the `tool_use` blocks. Calls to unknown tools,
+//! handler errors, and panics are answered with `is_error` results, so one
+//! failing tool neither affects the others nor stops the conversation.
+//! Tools that run longer than their
+//! [`timeout`](crate::tools::handler::ToolHandler::timeout) (or the registry's
+//! default, see [`ToolRegistry::timeout`]) are cancelled and answered with an
+//! `is_error` result as well.
+//!
+//! What happens when a handler returns `Err` is set with a
+//! [`ToolErrorPolicy`]: report it to the model (the default), retry the tool,
+//! or abort with the error.
+//!
+//! With [`ToolRegistry::validate_inputs`], inputs are checked against the
+//! handler's input schema first (see [`Tool::validate_input`]), and invalid
+//! ones are answered with an `is_error` result asking the model to correct
+//! the call, without running the handler.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use anthropic_tools::prelude::*;
+//! use anthropic_tools::tools::registry::ToolRegistry;
+//! # use anthropic_tools::tools::handler::ToolHandler;
+//! # fn weather_tool() -> Box<dyn ToolHandler> { unimplemented!() }
+//!
+//! # async fn example() -> Result<()> {
+//! let mut registry = ToolRegistry::new();
+//! registry.register(weather_tool());
+//!
+//! let mut client = Messages::new();
+//! client
+//!     .model("claude-sonnet-4-20250514")
+//!     .tools(registry.tool_values())
+//!     .user("What's the weather in Tokyo?");
+//!
+//! let response = client.post().await?;
+//! if let Some(results) = registry.handle(&response).await? {
+//!     client.add_message(response.to_message()).add_message(results);
+//!     let answer = client.post().await?;
+//!     println!("{}", answer.get_text());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::common::errors::Result;
+use crate::common::runtime::timeout;
+use crate::common::tool::Tool;
+use crate::messages::request::content::ContentBlock;
+use crate::messages::request::message::Message;
+use crate::messages::request::role::Role;
+use crate::messages::response::Response;
+use crate::tools::handler::{ToolHandler, ToolOutput};
+use futures::FutureExt;
+use serde_json::Value;
+use std::fmt;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// What to do when a tool handler returns `Err`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToolErrorPolicy {
+    /// Answer with an `is_error` result, so the model can react
+    #[default]
+    Report,
+
+    /// Run the tool up to this many more times, then report the last error
+    Retry(u32),
+
+    /// Stop and return the handler's error
+    Abort,
+}
+
+/// Tool handlers by name
+///
+/// Cloning is cheap; clones share the handlers.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    handlers: Vec<Arc<dyn ToolHandler>>,
+    timeout: Option<Duration>,
+    error_policy: ToolErrorPolicy,
+    validate_inputs: bool,
+}
+
+impl ToolRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler, replacing any handler with the same name
+    pub fn register<H: ToolHandler + 'static>(&mut self, handler: H) -> &mut Self {
+        let handler: Arc<dyn ToolHandler> = Arc::new(handler);
+        match self
+            .handlers
+            .iter_mut()
+            .find(|existing| existing.name() == handler.name())
+        {
+            Some(existing) => *existing = handler,
+            None => self.handlers.push(handler),
+        }
+        self
+    }
+
+    /// Set the time limit for tools that do not declare their own
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set what to do when a handler returns `Err`
+    pub fn error_policy(&mut self, policy: ToolErrorPolicy) -> &mut Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Check inputs against the input schema before running a handler
+    pub fn validate_inputs(&mut self, validate: bool) -> &mut Self {
+        self.validate_inputs = validate;
+        self
+    }
+
+    /// Get the handler for a tool name
+    pub fn get(&self, name: &str) -> Option<&dyn ToolHandler> {
+        self.handlers
+            .iter()
+            .find(|handler| handler.name() == name)
+            .map(|handler| handler.as_ref())
+    }
+
+    /// Check whether a tool is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Names of the registered tools, in registration order
+    pub fn names(&self) -> Vec<&str> {
+        self.handlers.iter().map(|handler| handler.name()).collect()
+    }
+
+    /// Number of registered tools
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Check whether no tools are registered
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Definitions of the registered tools
+    pub fn definitions(&self) -> Vec<Tool> {
+        self.handlers
+            .iter()
+            .map(|handler| handler.definition())
+            .collect()
+    }
+
+    /// Definitions as JSON values, for [`Messages::tools`](crate::messages::request::Messages::tools)
+    ///
+    /// Uses [`ToolHandler::tool_value`], so executors of Anthropic-defined
+    /// tools are sent with their versioned `type`.
+    pub fn tool_values(&self) -> Vec<Value> {
+        self.handlers
+            .iter()
+            .map(|handler| handler.tool_value())
+            .collect()
+    }
+
+    /// Run the tool requested by a `tool_use` block
+    ///
+    /// Returns the `tool_result` block answering it, or `None` if the block
+    /// is not a `tool_use` block. Fails only with [`ToolErrorPolicy::Abort`].
+    pub async fn dispatch(&self, block: &ContentBlock) -> Result<Option<ContentBlock>> {
+        let ContentBlock::ToolUse { id, name, input } = block else {
+            return Ok(None);
+        };
+        let Some(handler) = self.get(name) else {
+            let output = ToolOutput::error(format!("unknown tool: {}", name));
+            return Ok(Some(output.into_tool_result(id)));
+        };
+        if self.validate_inputs
+            && let Err(err) = handler.definition().validate_input(input)
+        {
+            let output = ToolOutput::error(format!(
+                "{}. Correct the input and call the tool again.",
+                err
+            ));
+            return Ok(Some(output.into_tool_result(id)));
+        }
+
+        let retries = match self.error_policy {
+            ToolErrorPolicy::Retry(retries) => retries,
+            _ => 0,
+        };
+        let mut attempt = 0;
+        let output = loop {
+            let execution = AssertUnwindSafe(handler.execute(input.clone())).catch_unwind();
+            let result = match handler.timeout().or(self.timeout) {
+                Some(limit) => timeout(limit, execution).await,
+                None => Some(execution.await),
+            };
+            match result {
+                Some(Ok(Ok(output))) => break output,
+                Some(Ok(Err(_))) if attempt < retries => attempt += 1,
+                Some(Ok(Err(err))) if self.error_policy == ToolErrorPolicy::Abort => {
+                    return Err(err);
+                }
+                Some(Ok(Err(err))) => {
+                    break ToolOutput::error(format!("tool {} failed: {}", name, err));
+                }
+                Some(Err(_)) => break ToolOutput::error(format!("tool {} panicked", name)),
+                None => break ToolOutput::error(format!("tool {} timed out", name)),
+            }
+        };
+        Ok(Some(output.into_tool_result(id)))
+    }
+
+    /// Answer all `tool_use` blocks of a response
+    ///
+    /// The tools run concurrently. Returns a user message with one
+    /// `tool_result` block per `tool_use` block, in the same order, or `None`
+    /// if the response requested no tools. Fails only with
+    /// [`ToolErrorPolicy::Abort`], after all tools have finished.
+    pub async fn handle(&self, response: &Response) -> Result<Option<Message>> {
+        let results = futures::future::join_all(
+            response
+                .content
+                .iter()
+                .filter(|block| matches!(block, ContentBlock::ToolUse { .. }))
+                .map(|block| self.dispatch(block)),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+        let results: Vec<ContentBlock> = results.into_iter().flatten().collect();
+        Ok((!results.is_empty()).then(|| Message::new(Role::User, results)))
+    }
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.names())
+            .field("timeout", &self.timeout)
+            .field("error_policy", &self.error_policy)
+            .field("validate_inputs", &self.validate_inputs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::errors::AnthropicToolError;
+    use crate::common::future::{BoxFuture, boxed};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Echo;
+
+    impl ToolHandler for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn definition(&self) -> Tool {
+            let mut tool = Tool::new("echo");
+            tool.add_string_property("text", Some("Text to echo"), true);
+            tool
+        }
+
+        fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+            boxed(async move {
+                match input["text"].as_str() {
+                    Some(text) => Ok(ToolOutput::text(text)),
+                    None => Err(AnthropicToolError::MissingRequiredField("text".to_string())),
+                }
+            })
+        }
+    }
+
+    /// Sleeps for `millis`, then echoes it; panics for 0
+    struct Sleep;
+
+    impl ToolHandler for Sleep {
+        fn name(&self) -> &str {
+            "sleep"
+        }
+
+        fn definition(&self) -> Tool {
+            Tool::new("sleep")
+        }
+
+        fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+            boxed(async move {
+                let millis = input["millis"].as_u64().unwrap_or_default();
+                assert!(millis > 0, "no sleep");
+                crate::common::runtime::sleep(std::time::Duration::from_millis(millis)).await;
+                Ok(ToolOutput::text(millis.to_string()))
+            })
+        }
+    }
+
+    fn response(content: Vec<ContentBlock>) -> Response {
+        serde_json::from_value(serde_json::json!({
+            "id": "msg_1", "type": "message", "role": "assistant",
+            "content": content, "model": "claude-sonnet-4-20250514",
+            "stop_reason": "tool_use",
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_handle_tool_uses() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Echo).register(Echo);
+        assert_eq!(registry.names(), vec!["echo"]);
+        assert_eq!(registry.tool_values()[0]["name"], "echo");
+
+        let response = response(vec![
+            ContentBlock::text("Let me check."),
+            ContentBlock::tool_use("toolu_1", "echo", serde_json::json!({"text": "hi"})),
+            ContentBlock::tool_use("toolu_2", "echo", serde_json::json!({})),
+            ContentBlock::tool_use("toolu_3", "missing", serde_json::json!({})),
+        ]);
+        let message = registry.handle(&response).await.unwrap().unwrap();
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["role"], "user");
+        assert_eq!(json["content"].as_array().unwrap().len(), 3);
+        assert_eq!(json["content"][0]["tool_use_id"], "toolu_1");
+        assert_eq!(json["content"][0]["content"][0]["text"], "hi");
+        assert!(json["content"][0].get("is_error").is_none());
+        assert_eq!(json["content"][1]["is_error"], true);
+        assert_eq!(json["content"][2]["is_error"], true);
+        assert_eq!(
+            json["content"][2]["content"][0]["text"],
+            "unknown tool: missing"
+        );
+
+        assert!(
+            registry
+                .handle(&self::response(vec![ContentBlock::text("Done")]))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_runs_tools_concurrently() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Sleep);
+
+        let response = response(vec![
+            ContentBlock::tool_use("toolu_1", "sleep", serde_json::json!({"millis": 400})),
+            ContentBlock::tool_use("toolu_2", "sleep", serde_json::json!({"millis": 0})),
+            ContentBlock::tool_use("toolu_3", "sleep", serde_json::json!({"millis": 200})),
+            ContentBlock::tool_use("toolu_4", "sleep", serde_json::json!({"millis": 400})),
+        ]);
+        let started = std::time::Instant::now();
+        let message = registry.handle(&response).await.unwrap().unwrap();
+        // One after the other would take 1s
+        assert!(started.elapsed() < std::time::Duration::from_millis(900));
+
+        let json = serde_json::to_value(&message).unwrap();
+        let ids: Vec<&str> = json["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|block| block["tool_use_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["toolu_1", "toolu_2", "toolu_3", "toolu_4"]);
+        assert_eq!(json["content"][0]["content"][0]["text"], "400");
+        assert_eq!(json["content"][1]["is_error"], true);
+        assert_eq!(
+            json["content"][1]["content"][0]["text"],
+            "tool sleep panicked"
+        );
+        assert_eq!(json["content"][2]["content"][0]["text"], "200");
+    }
+
+    #[tokio::test]
+    async fn test_tool_timeout() {
+        struct Slow;
+
+        impl ToolHandler for Slow {
+            fn name(&self) -> &str {
+                "slow"
+            }
+
+            fn definition(&self) -> Tool {
+                Tool::new("slow")
+            }
+
+            fn execute(&self, input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+                Sleep.execute(input)
+            }
+
+            fn timeout(&self) -> Option<std::time::Duration> {
+                Some(std::time::Duration::from_millis(20))
+            }
+        }
+
+        let mut registry = ToolRegistry::new();
+        registry
+            .register(Slow)
+            .register(Sleep)
+            .timeout(std::time::Duration::from_millis(100));
+
+        let response = response(vec![
+            ContentBlock::tool_use("toolu_1", "slow", serde_json::json!({"millis": 50})),
+            ContentBlock::tool_use("toolu_2", "sleep", serde_json::json!({"millis": 50})),
+            ContentBlock::tool_use("toolu_3", "sleep", serde_json::json!({"millis": 5000})),
+        ]);
+        let started = std::time::Instant::now();
+        let json =
+            serde_json::to_value(registry.handle(&response).await.unwrap().unwrap()).unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        assert_eq!(json["content"][0]["is_error"], true);
+        assert_eq!(
+            json["content"][0]["content"][0]["text"],
+            "tool slow timed out"
+        );
+        assert_eq!(json["content"][1]["content"][0]["text"], "50");
+        assert_eq!(
+            json["content"][2]["content"][0]["text"],
+            "tool sleep timed out"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_inputs() {
+        let mut registry = ToolRegistry::new();
+        registry.register(Echo).validate_inputs(true);
+        let block = ContentBlock::tool_use("toolu_1", "echo", serde_json::json!({"text": 3}));
+        let result = registry.dispatch(&block).await.unwrap().unwrap();
+        let json = serde_json::to_value(result).unwrap();
+        assert_eq!(json["is_error"], true);
+        assert_eq!(
+            json["content"][0]["text"],
+            "Invalid input for tool echo at `text`: expected string, found 3. \
+             Correct the input and call the tool again."
+        );
+
+        let block = ContentBlock::tool_use("toolu_2", "echo", serde_json::json!({"text": "hi"}));
+        let result = registry.dispatch(&block).await.unwrap().unwrap();
+        assert_eq!(
+            serde_json::to_value(result).unwrap()["content"][0]["text"],
+            "hi"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_policy() {
+        /// Fails until called `failures` times
+        struct Flaky {
+            failures: u32,
+            calls: AtomicU32,
+        }
+
+        impl ToolHandler for Flaky {
+            fn name(&self) -> &str {
+                "flaky"
+            }
+
+            fn definition(&self) -> Tool {
+                Tool::new("flaky")
+            }
+
+            fn execute(&self, _input: Value) -> BoxFuture<'_, Result<ToolOutput>> {
+                boxed(async move {
+                    let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    if call <= self.failures {
+                        Err(AnthropicToolError::InvalidParameter(format!(
+                            "attempt {}",
+                            call
+                        )))
+                    } else {
+                        Ok(ToolOutput::text("ok"))
+                    }
+                })
+            }
+        }
+
+        let flaky = |failures| Flaky {
+            failures,
+            calls: AtomicU32::new(0),
+        };
+        let block = ContentBlock::tool_use("toolu_1", "flaky", serde_json::json!({}));
+        let text = |block: ContentBlock| {
+            serde_json::to_value(block).unwrap()["content"][0]["text"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+
+        let mut registry = ToolRegistry::new();
+        registry.register(flaky(1));
+        let result = registry.dispatch(&block).await.unwrap().unwrap();
+        assert_eq!(
+            text(result),
+            "tool flaky failed: Invalid parameter: attempt 1"
+        );
+
+        registry
+            .register(flaky(2))
+            .error_policy(ToolErrorPolicy::Retry(2));
+        let result = registry.dispatch(&block).await.unwrap().unwrap();
+        assert_eq!(text(result), "ok");
+
+        registry
+            .register(flaky(3))
+            .error_policy(ToolErrorPolicy::Retry(1));
+        let result = registry.dispatch(&block).await.unwrap().unwrap();
+        assert_eq!(
+            text(result),
+            "tool flaky failed: Invalid parameter: attempt 2"
+        );
+
+        registry
+            .register(flaky(1))
+            .error_policy(ToolErrorPolicy::Abort);
+        let err = registry
+            .handle(&response(vec![block.clone()]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AnthropicToolError::InvalidParameter(_)));
+    }
+}
@@ -1,27 +1,57 @@
 //! Integration tests for the Anthropic API.
 //!
-//! These tests require a valid ANTHROPIC_API_KEY environment variable.
-//! Run with: `cargo test --test api_integration --ignored`
+//! Each test replays its API traffic from the cassette committed at
+//! `tests/cassettes/<test name>.json`, so they run in CI without an API key
+//! or network access.
 //!
-//! Note: These tests make actual API calls and will consume tokens.
+//! Re-record a test by deleting its cassette and running it with a valid
+//! ANTHROPIC_API_KEY environment variable (this consumes tokens):
+//! `cargo test --test api_integration <test name>`
 
+use anthropic_tools::common::cassette::Cassette;
+use anthropic_tools::messages::request::DEFAULT_BASE_URL;
 use anthropic_tools::prelude::*;
 
 /// Helper to ensure API key is set, panics if not
 fn require_api_key() {
     match std::env::var("ANTHROPIC_API_KEY") {
         Ok(key) if !key.is_empty() => {}
-        _ => panic!("ANTHROPIC_API_KEY environment variable is not set. Set it to run API integration tests."),
+        _ => panic!(
+            "ANTHROPIC_API_KEY environment variable is not set. Set it to run API integration tests."
+        ),
     }
 }
 
+/// Open `tests/cassettes/<name>.json`, replaying it if it exists
+fn cassette(name: &str) -> Cassette {
+    let path = format!(
+        "{}/tests/cassettes/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    Cassette::new(path).expect("failed to open cassette")
+}
+
+/// Create a client that records to (or replays from) `tests/cassettes/<name>.json`
+///
+/// The API key is only required while the cassette has not been recorded yet.
+/// Requests always go to the public API so that cassettes match whatever
+/// ANTHROPIC_BASE_URL is set to.
+fn recording_client(name: &str) -> Messages {
+    let cassette = cassette(name);
+    if !cassette.is_replaying() {
+        require_api_key();
+    }
+
+    let mut client = Messages::new();
+    client.base_url(DEFAULT_BASE_URL).cassette(cassette);
+    client
+}
+
 /// Basic message test - simple text completion
 #[tokio::test]
-#[ignore]
 async fn test_basic_message() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_basic_message");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(100)
@@ -52,11 +82,8 @@ async fn test_basic_message() {
 
 /// Test with system prompt
 #[tokio::test]
-#[ignore]
 async fn test_system_prompt() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_system_prompt");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(50)
@@ -83,11 +110,8 @@ async fn test_system_prompt() {
 
 /// Test with temperature parameter
 #[tokio::test]
-#[ignore]
 async fn test_temperature() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_temperature");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(50)
@@ -107,10 +131,7 @@ async fn test_temperature() {
 
 /// Test tool use
 #[tokio::test]
-#[ignore]
 async fn test_tool_use() {
-    require_api_key();
-
     // Define a weather tool
     let mut tool = Tool::new("get_weather");
     tool.description("Get the current weather in a given location")
@@ -126,7 +147,7 @@ async fn test_tool_use() {
             false,
         );
 
-    let mut client = Messages::new();
+    let mut client = recording_client("test_tool_use");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(200)
@@ -160,17 +181,14 @@ async fn test_tool_use() {
 
 /// Test tool use with tool result
 #[tokio::test]
-#[ignore]
 async fn test_tool_use_conversation() {
-    require_api_key();
-
     // Define a calculator tool
     let mut tool = Tool::new("calculate");
     tool.description("Perform a mathematical calculation")
         .add_string_property("expression", Some("The math expression to evaluate"), true);
 
     // First request - ask Claude to calculate
-    let mut client = Messages::new();
+    let mut client = recording_client("test_tool_use_conversation");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(200)
@@ -180,30 +198,17 @@ async fn test_tool_use_conversation() {
     let response = client.post().await.expect("API call failed");
     assert!(response.has_tool_use(), "Should request tool use");
 
-    // Get the tool use ID
-    let tool_uses = response.get_tool_uses();
-    let (tool_id, _tool_name) =
-        if let Some(ContentBlock::ToolUse { id, name, .. }) = tool_uses.first() {
-            (id.clone(), name.clone())
-        } else {
-            panic!("Expected tool use");
-        };
-
     // Second request - provide tool result
-    let mut client2 = Messages::new();
+    let mut client2 = recording_client("test_tool_use_conversation_2");
     client2
         .model("claude-sonnet-4-20250514")
         .max_tokens(200)
         .tools(vec![tool.to_value()])
         .user("Calculate 15 * 7 for me.");
 
-    // Add assistant's response with tool use
-    for block in &response.content {
-        client2.add_message(Message::new(Role::Assistant, vec![block.clone()]));
-    }
-
-    // Add tool result
-    client2.tool_result(&tool_id, &("105".to_string()));
+    // Add the assistant's tool use and the tool result
+    let (assistant, results) = response.answer_tool_calls(|_| "105");
+    client2.add_message(assistant).add_message(results);
 
     let response2 = client2.post().await.expect("API call failed");
 
@@ -215,10 +220,7 @@ async fn test_tool_use_conversation() {
 
 /// Test forced tool choice
 #[tokio::test]
-#[ignore]
 async fn test_forced_tool_choice() {
-    require_api_key();
-
     let mut tool = Tool::new("greet");
     tool.description("Generate a greeting").add_string_property(
         "name",
@@ -226,7 +228,7 @@ async fn test_forced_tool_choice() {
         true,
     );
 
-    let mut client = Messages::new();
+    let mut client = recording_client("test_forced_tool_choice");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(200)
@@ -249,11 +251,8 @@ async fn test_forced_tool_choice() {
 
 /// Test stop reason - max tokens
 #[tokio::test]
-#[ignore]
 async fn test_stop_reason_max_tokens() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_stop_reason_max_tokens");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(5) // Very low limit
@@ -270,11 +269,8 @@ async fn test_stop_reason_max_tokens() {
 
 /// Test stop reason - natural end
 #[tokio::test]
-#[ignore]
 async fn test_stop_reason_end_turn() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_stop_reason_end_turn");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(100)
@@ -291,11 +287,8 @@ async fn test_stop_reason_end_turn() {
 
 /// Test multi-turn conversation
 #[tokio::test]
-#[ignore]
 async fn test_multi_turn_conversation() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_multi_turn_conversation");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(100)
@@ -316,10 +309,11 @@ async fn test_multi_turn_conversation() {
 
 /// Test error handling - invalid API key
 #[tokio::test]
-#[ignore]
 async fn test_invalid_api_key() {
     let mut client = Messages::with_api_key("invalid_key");
     client
+        .base_url(DEFAULT_BASE_URL)
+        .cassette(cassette("test_invalid_api_key"))
         .model("claude-sonnet-4-20250514")
         .max_tokens(100)
         .user("Hello");
@@ -379,11 +373,8 @@ async fn test_missing_messages_error() {
 
 /// Test image from URL (vision)
 #[tokio::test]
-#[ignore]
 async fn test_vision_url() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_vision_url");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(200)
@@ -401,11 +392,8 @@ async fn test_vision_url() {
 
 /// Test with stop sequences
 #[tokio::test]
-#[ignore]
 async fn test_stop_sequences() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_stop_sequences");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(200)
@@ -426,11 +414,8 @@ async fn test_stop_sequences() {
 
 /// Test metadata (user_id)
 #[tokio::test]
-#[ignore]
 async fn test_metadata() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_metadata");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(50)
@@ -445,11 +430,8 @@ async fn test_metadata() {
 
 /// Test top_p and top_k parameters
 #[tokio::test]
-#[ignore]
 async fn test_sampling_parameters() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_sampling_parameters");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(50)
@@ -467,11 +449,8 @@ async fn test_sampling_parameters() {
 
 /// Test response helper methods
 #[tokio::test]
-#[ignore]
 async fn test_response_helpers() {
-    require_api_key();
-
-    let mut client = Messages::new();
+    let mut client = recording_client("test_response_helpers");
     client
         .model("claude-sonnet-4-20250514")
         .max_tokens(100)